@@ -2,7 +2,41 @@ pub const MAX_NAME_LEN: usize = 80;
 pub const MAX_DETAILS_LEN: usize = 250;
 pub const MAX_RESOURCE_PATH_LEN: usize = 128;
 
+pub const MAX_HERO_TAGS: usize = 3;
+pub const MAX_TRUSTED_REALMS: usize = 8;
+
+pub const DEFAULT_BASE_XP: u64 = 100;
+pub const DEFAULT_XP_GROWTH: u64 = 50;
+pub const DEFAULT_STAT_POINTS_PER_LEVEL: u64 = 3;
+pub const DEFAULT_STAT_CAP: u64 = 1_000;
+
+pub const MAX_INVENTORY_ITEMS: usize = 32;
+
+pub const MAX_MOVE_DISTANCE: i64 = 20;
+pub const MAX_COORDINATE: i32 = 10_000; // Keeps Rect::area within i32
+
+pub const MAX_WALKABILITY_GRID_CELLS: u64 = 256 * 256; // Fits the account creation limit
+
+pub const MAX_ALLOWLIST_PROOF_LEN: usize = 32;
+
+pub const MAX_SPAWN_POINTS: u32 = 16;
+
+pub const MAX_SESSION_DURATION: i64 = 7 * 24 * 60 * 60; // 7 days
+
+pub const MAX_PROPOSAL_VOTES: usize = 16;
+
+pub const MAX_TIMELOCK: i64 = 30 * 24 * 60 * 60; // 30 days
+
 pub const REALM_SEED: &[u8] = b"realm";
+pub const MASTER_SEED: &[u8] = b"master";
 pub const DIMENSION_SEED: &[u8] = b"dimension";
+pub const PORTAL_SEED: &[u8] = b"portal";
+pub const SPAWN_POINT_SEED: &[u8] = b"spawn_point";
+pub const WALKABILITY_GRID_SEED: &[u8] = b"walkability_grid";
+pub const PROPOSAL_SEED: &[u8] = b"proposal";
+pub const QUEUED_ACTION_SEED: &[u8] = b"queued_action";
 pub const JOURNEY_SEED: &[u8] = b"journey";
+pub const SESSION_KEY_SEED: &[u8] = b"session_key";
 pub const HERO_SEED: &[u8] = b"hero";
+pub const PLAYER_HERO_SEED: &[u8] = b"player_hero";
+pub const ITEM_SEED: &[u8] = b"item";
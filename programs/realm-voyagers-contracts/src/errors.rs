@@ -14,6 +14,84 @@ pub enum ErrorCode {
     #[msg("Duplicate realm master")]
     DuplicateRealmMaster,
 
+    #[msg("Realm master membership was not provided")]
+    RealmMasterNotProvided,
+
+    #[msg("Realm masters are already migrated")]
+    RealmAlreadyMigrated,
+
+    #[msg("Realm is closing")]
+    RealmClosing,
+
+    #[msg("Realm teardown has not started")]
+    RealmNotClosing,
+
+    #[msg("Realm still has dimensions or masters to close")]
+    RealmTeardownIncomplete,
+
+    #[msg("Player is not invited to the realm")]
+    PlayerNotInvited,
+
+    #[msg("Allowlist proof is too long")]
+    AllowlistProofTooLong,
+
+    #[msg("Owner role can only be transferred")]
+    CantAssignOwnerRole,
+
+    #[msg("Owner permissions can't be changed")]
+    CantChangeOwnerPermissions,
+
+    #[msg("Invalid realm permissions")]
+    InvalidRealmPermissions,
+
+    #[msg("Invalid approval threshold")]
+    InvalidApprovalThreshold,
+
+    #[msg("Action requires an approved proposal")]
+    ProposalRequired,
+
+    #[msg("Proposal is for a different action")]
+    ProposalActionMismatch,
+
+    #[msg("Proposal is not approved")]
+    ProposalNotApproved,
+
+    #[msg("Proposal was rejected")]
+    ProposalRejected,
+
+    #[msg("Proposal was made before the realm was created")]
+    StaleProposal,
+
+    #[msg("Too few approvers would be left to reach the approval threshold")]
+    ApprovalThresholdUnreachable,
+
+    #[msg("Realm master already voted")]
+    AlreadyVoted,
+
+    #[msg("Proposal has too many votes")]
+    TooManyVotes,
+
+    #[msg("Invalid timelock")]
+    InvalidTimelock,
+
+    #[msg("Action must be queued behind the realm timelock")]
+    TimelockRequired,
+
+    #[msg("Queued action is for a different action")]
+    QueuedActionMismatch,
+
+    #[msg("Action was queued before the realm was created")]
+    StaleQueuedAction,
+
+    #[msg("Timelock has not elapsed yet")]
+    TimelockNotElapsed,
+
+    #[msg("Realm has no pending owner")]
+    NoPendingRealmOwner,
+
+    #[msg("Signer is not the pending realm owner")]
+    NotPendingRealmOwner,
+
     #[msg("Name is too long")]
     NameTooLong,
 
@@ -23,9 +101,147 @@ pub enum ErrorCode {
     #[msg("Resource path is too long")]
     ResourcePathTooLong,
 
+    #[msg("Area coordinates are out of bounds")]
+    AreaOutOfBounds,
+
+    #[msg("Area top left lies past its bottom right")]
+    InvertedArea,
+
+    #[msg("Dimension areas overlap")]
+    OverlappingAreas,
+
+    #[msg("Duplicate area id")]
+    DuplicateAreaId,
+
+    #[msg("Area not found")]
+    AreaNotFound,
+
     #[msg("Dimension was not provided")]
     DimensionNotProvided,
 
+    #[msg("Dimension owner was not provided")]
+    DimensionOwnerNotProvided,
+
     #[msg("Realm has no starting dimension")]
     RealmHasNoStartingDimension,
+
+    #[msg("Too many spawn points")]
+    TooManySpawnPoints,
+
+    #[msg("Spawn point was not provided")]
+    SpawnPointNotProvided,
+
+    #[msg("Spawn point requirements are not met")]
+    SpawnRequirementsNotMet,
+
+    #[msg("No spawn point is available to the player")]
+    NoEligibleSpawnPoint,
+
+    #[msg("Position is outside of the dimension areas")]
+    PositionOutsideDimension,
+
+    #[msg("Starting position would lie outside of the starting dimension areas")]
+    StartingPointOutsideDimension,
+
+    #[msg("Position is not walkable")]
+    PositionNotWalkable,
+
+    #[msg("Walkability grid was not provided")]
+    WalkabilityGridNotProvided,
+
+    #[msg("Invalid walkability grid size")]
+    InvalidWalkabilityGridSize,
+
+    #[msg("Walkability chunk is out of the grid")]
+    WalkabilityChunkOutOfBounds,
+
+    #[msg("Move distance is too long")]
+    MoveDistanceTooLong,
+
+    #[msg("Hero is not standing in the portal")]
+    HeroNotInPortal,
+
+    #[msg("Portal area lies outside of its dimension areas")]
+    PortalOutsideDimension,
+
+    #[msg("Portal was not provided")]
+    PortalNotProvided,
+
+    #[msg("Portal creator was not provided")]
+    PortalCreatorNotProvided,
+
+    #[msg("Journey started before the realm was created")]
+    StaleJourney,
+
+    #[msg("Signer is neither the player nor one of their session keys")]
+    UnauthorizedJourneyAuthority,
+
+    #[msg("Invalid session scope")]
+    InvalidSessionScope,
+
+    #[msg("Invalid session expiry")]
+    InvalidSessionExpiry,
+
+    #[msg("Session key has expired")]
+    SessionKeyExpired,
+
+    #[msg("Session key was created for a previous journey")]
+    StaleSessionKey,
+
+    #[msg("Session key is not allowed to perform this action")]
+    SessionScopeNotAllowed,
+
+    #[msg("Duplicate hero tag")]
+    DuplicateHeroTag,
+
+    #[msg("Too many hero tags")]
+    TooManyHeroTags,
+
+    #[msg("Too many trusted realms")]
+    TooManyTrustedRealms,
+
+    #[msg("Unauthorized experience authority")]
+    UnauthorizedXpAuthority,
+
+    #[msg("Invalid experience curve")]
+    InvalidXpCurve,
+
+    #[msg("Not enough experience to level up")]
+    NotEnoughXp,
+
+    #[msg("Stat allocation exceeds available stat points")]
+    InvalidStatAllocation,
+
+    #[msg("Arithmetic overflow")]
+    ArithmeticOverflow,
+
+    #[msg("Stat overflows its cap")]
+    StatOverflow,
+
+    #[msg("Unauthorized item master")]
+    UnauthorizedItemMaster,
+
+    #[msg("Inventory is full")]
+    InventoryFull,
+
+    #[msg("Item not found in inventory")]
+    ItemNotInInventory,
+
+    #[msg("Item can't be equipped into this slot")]
+    InvalidEquipmentSlot,
+
+    #[msg("Equipment slot is already occupied")]
+    EquipmentSlotOccupied,
+
+    #[msg("Equipment slot is empty")]
+    EquipmentSlotEmpty,
+
+    #[msg("Item is equipped")]
+    ItemIsEquipped,
+
+    #[msg("Equipped item was not provided")]
+    EquippedItemNotProvided,
+
+    #[msg("Hero does not meet item requirements")]
+    ItemRequirementsNotMet,
 }
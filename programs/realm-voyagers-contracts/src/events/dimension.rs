@@ -1,10 +1,53 @@
 use anchor_lang::prelude::*;
 
+use crate::state::*;
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub enum RealmDimensionEventType {
-    DimensionAdded { name: String },
-    DimensionUpdated { name: String },
+    DimensionAdded {
+        name: String,
+    },
+    DimensionUpdated {
+        name: String,
+    },
     DimensionRemoved {},
+    AreaAdded {
+        area_id: u32,
+        name: String,
+    },
+    AreaUpdated {
+        area_id: u32,
+        name: String,
+    },
+    AreaRemoved {
+        area_id: u32,
+    },
+    WalkabilityGridCreated {
+        grid_pubkey: Pubkey,
+        origin: Position,
+        width: u32,
+        height: u32,
+    },
+    WalkabilityChunkUploaded {
+        grid_pubkey: Pubkey,
+        offset: u32,
+        len: u32,
+    },
+    WalkabilityGridRemoved {
+        grid_pubkey: Pubkey,
+    },
+    DimensionOwnerChanged {
+        previous_owner: Pubkey,
+        owner: Pubkey,
+    },
+
+    PortalAdded {
+        portal_pubkey: Pubkey,
+        target_dimension: Pubkey,
+    },
+    PortalRemoved {
+        portal_pubkey: Pubkey,
+    },
 }
 
 #[event]
@@ -1,9 +1,43 @@
 use anchor_lang::prelude::*;
 
+use crate::state::*;
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub enum HeroEventType {
-    HeroCreated { hero_pubkey: Pubkey },
-    HeroUpdated { hero_pubkey: Pubkey },
+    HeroCreated {
+        hero_pubkey: Pubkey,
+    },
+    HeroUpdated {
+        hero_pubkey: Pubkey,
+    },
+    PlayerHeroMinted {
+        player_hero_pubkey: Pubkey,
+        hero_pubkey: Pubkey,
+        player: Pubkey,
+    },
+    HeroXpCurveUpdated {
+        hero_pubkey: Pubkey,
+        xp_curve: XpCurve,
+    },
+    HeroStatCapsUpdated {
+        hero_pubkey: Pubkey,
+        stat_caps: HeroStats,
+    },
+    HeroTrustedRealmsUpdated {
+        hero_pubkey: Pubkey,
+        trusted_realms: Vec<Pubkey>,
+    },
+    XpGranted {
+        player_hero_pubkey: Pubkey,
+        authority: Pubkey,
+        amount: u64,
+        xp: u64,
+    },
+    HeroLeveledUp {
+        player_hero_pubkey: Pubkey,
+        level: u64,
+        stats: HeroStats,
+    },
 }
 
 #[event]
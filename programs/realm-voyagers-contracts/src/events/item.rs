@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub enum ItemEventType {
+    ItemCreated {
+        item_pubkey: Pubkey,
+    },
+    ItemGranted {
+        item_pubkey: Pubkey,
+        player_hero_pubkey: Pubkey,
+    },
+    ItemDropped {
+        item_pubkey: Pubkey,
+        player_hero_pubkey: Pubkey,
+    },
+    ItemTransferred {
+        item_pubkey: Pubkey,
+        from_player_hero: Pubkey,
+        to_player_hero: Pubkey,
+    },
+    ItemEquipped {
+        item_pubkey: Pubkey,
+        player_hero_pubkey: Pubkey,
+        slot: EquipmentSlot,
+        effective_stats: HeroStats,
+    },
+    ItemUnequipped {
+        item_pubkey: Pubkey,
+        player_hero_pubkey: Pubkey,
+        slot: EquipmentSlot,
+    },
+}
+
+#[event]
+pub struct ItemEvent {
+    pub event_type: ItemEventType,
+}
@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub enum JourneyEventType {
+    JourneyStarted {
+        dimension: Pubkey,
+        position: Position,
+        spawn_point: Option<Pubkey>,
+    },
+    JourneyEnded {},
+    VoyagerKicked {
+        master: Pubkey,
+    },
+    SessionKeyCreated {
+        authority: Pubkey,
+        scope: u8,
+        expires_at: i64,
+    },
+    SessionKeyRevoked {
+        authority: Pubkey,
+    },
+
+    HeroMoved {
+        from: Position,
+        to: Position,
+    },
+    HeroTraveled {
+        portal_pubkey: Pubkey,
+        from_dimension: Pubkey,
+        to_dimension: Pubkey,
+        position: Position,
+    },
+}
+
+#[event]
+pub struct JourneyEvent {
+    pub event_type: JourneyEventType,
+    pub journey_pubkey: Pubkey,
+    pub realm_pubkey: Pubkey,
+    pub player_pubkey: Pubkey,
+}
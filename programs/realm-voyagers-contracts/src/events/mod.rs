@@ -1,7 +1,11 @@
 pub mod dimension;
 pub mod hero;
+pub mod item;
+pub mod journey;
 pub mod realm;
 
 pub use dimension::*;
 pub use hero::*;
+pub use item::*;
+pub use journey::*;
 pub use realm::*;
@@ -11,6 +11,58 @@ pub enum RealmEventType {
         description: RealmDescription,
     },
     RealmDeleted {},
+    RealmStartingPointChanged {
+        dimension: Option<Pubkey>, // None once the starting dimension is removed
+        position: Position,
+    },
+    SpawnPointAdded {
+        spawn_point: Pubkey,
+        dimension: Pubkey,
+        settings: SpawnPointSettings,
+    },
+    SpawnPointUpdated {
+        spawn_point: Pubkey,
+        dimension: Pubkey,
+        settings: SpawnPointSettings,
+    },
+    SpawnPointRemoved {
+        spawn_point: Pubkey,
+    },
+    RealmVisibilityChanged {
+        visibility: RealmVisibility,
+    },
+    RealmTeardownStarted {
+        dimensions_left: u32,
+        masters_left: u32,
+        spawn_points_left: u32,
+    },
+    RealmTeardownProgress {
+        dimensions_closed: u32,
+        masters_closed: u32,
+        spawn_points_closed: u32,
+        dimensions_left: u32,
+        masters_left: u32,
+        spawn_points_left: u32,
+    },
+    RealmApprovalThresholdChanged {
+        threshold: u8,
+    },
+    RealmTimelockChanged {
+        timelock: i64,
+    },
+    RealmActionQueued {
+        queued_action: Pubkey,
+        action: RealmTimelockAction,
+        execute_after: i64,
+    },
+    RealmActionExecuted {
+        queued_action: Pubkey,
+        action: RealmTimelockAction,
+    },
+    RealmActionCancelled {
+        queued_action: Pubkey,
+        action: RealmTimelockAction,
+    },
 
     RealmMasterAdded {
         master: RealmMaster,
@@ -18,12 +70,49 @@ pub enum RealmEventType {
     RealmMasterRemoved {
         master: RealmMaster,
     },
+    RealmMasterResigned {
+        master: RealmMaster,
+    },
+    RealmMastersMigrated {
+        masters_count: u32,
+    },
+    RealmMasterRoleChanged {
+        master: RealmMaster,
+        previous_role: Option<RealmMasterRole>,
+    },
+    RealmMasterPermissionsChanged {
+        master: RealmMaster,
+        previous_permissions: u32,
+    },
+    RealmOwnershipProposed {
+        owner: RealmMaster,
+        pending_owner: Pubkey,
+    },
+    RealmOwnershipProposalCancelled {
+        pending_owner: Pubkey,
+    },
     RealmOwnershipTransferred {
         old_owner: RealmMaster,
         new_owner: RealmMaster,
     },
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub enum RealmProposalEventType {
+    ProposalCreated { action: RealmProposalAction },
+    ProposalApproved { master: Pubkey, approvals: u8 },
+    ProposalRejected { master: Pubkey, rejections: u8 },
+    ProposalCancelled {},
+    ProposalExecuted {},
+}
+
+#[event]
+pub struct RealmProposalEvent {
+    pub event_type: RealmProposalEventType,
+    pub proposal_pubkey: Pubkey,
+    pub realm_pubkey: Pubkey,
+}
+
 #[event]
 pub struct RealmEvent {
     pub event_type: RealmEventType,
@@ -3,6 +3,8 @@ use anchor_lang::prelude::*;
 use crate::constants::*;
 use crate::errors::ErrorCode;
 use crate::events::*;
+use crate::instructions::portals::{close_dimension_portals, close_incoming_portals};
+use crate::instructions::walkability::close_dimension_walkability_grid;
 use crate::state::*;
 
 #[derive(Accounts)]
@@ -12,6 +14,7 @@ pub struct AddRealmDimension<'info> {
         mut,
         seeds = [REALM_SEED, realm_id.as_bytes()],
         bump,
+        constraint = realm.is_active() @ ErrorCode::RealmClosing,
         realloc = crate::realm_space!(
             realm.description,
             realm.masters.len(),
@@ -33,10 +36,15 @@ pub struct AddRealmDimension<'info> {
 
     #[account(
         mut,
-        constraint = realm.masters.iter().any(|m|
-            m.pubkey == master.key() && m.can_add_realm_dimension()
+        seeds = [MASTER_SEED, realm.key().as_ref(), master.key().as_ref()],
+        bump,
+        constraint = master_membership.master.has_permission(
+            RealmPermissions::ADD_DIMENSIONS
         ) @ ErrorCode::UnauthorizedRealmMaster
     )]
+    pub master_membership: Account<'info, RealmMembership>,
+
+    #[account(mut)]
     pub master: Signer<'info>,
 
     pub system_program: Program<'info, System>,
@@ -48,8 +56,10 @@ pub fn add_realm_dimension(
     _dimension_id: String,
     name: String,
     areas: Vec<RealmDimensionArea>,
+    allow_overlapping_areas: bool,
 ) -> Result<()> {
     require!(name.len() <= MAX_NAME_LEN, ErrorCode::NameTooLong);
+    RealmDimension::validate_areas(&areas, allow_overlapping_areas)?;
 
     let realm = &mut ctx.accounts.realm;
     let dimension = &mut ctx.accounts.dimension;
@@ -58,10 +68,27 @@ pub fn add_realm_dimension(
     dimension.owner = *ctx.accounts.master.key;
     dimension.name = name.clone();
     dimension.areas = areas;
+    dimension.allow_overlapping_areas = allow_overlapping_areas;
+
+    let master_membership = &mut ctx.accounts.master_membership;
+    master_membership.dimensions_count = master_membership
+        .dimensions_count
+        .checked_add(1)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
 
     realm.dimensions.push(dimension.key());
-    if realm.starting_dimension.is_none() {
+    // The first dimension with areas becomes the starting one, starting at its first area
+    if let (None, Some(area)) = (realm.starting_dimension, dimension.areas.first()) {
         realm.starting_dimension = Some(dimension.key());
+        realm.starting_position = area.area.top_left;
+
+        emit!(RealmEvent {
+            realm_pubkey: realm.key(),
+            event_type: RealmEventType::RealmStartingPointChanged {
+                dimension: Some(dimension.key()),
+                position: realm.starting_position,
+            },
+        });
     }
     realm.updated_at = Clock::get()?.unix_timestamp;
 
@@ -81,6 +108,7 @@ pub struct UpdateRealmDimension<'info> {
         mut,
         seeds = [REALM_SEED, realm_id.as_bytes()],
         bump,
+        constraint = realm.is_active() @ ErrorCode::RealmClosing,
     )]
     pub realm: Account<'info, Realm>,
 
@@ -95,11 +123,15 @@ pub struct UpdateRealmDimension<'info> {
     pub dimension: Account<'info, RealmDimension>,
 
     #[account(
-        mut,
-        constraint = realm.masters.iter().any(|m|
-            m.pubkey == master.key() && m.can_manage_realm_dimension(&dimension)
+        seeds = [MASTER_SEED, realm.key().as_ref(), master.key().as_ref()],
+        bump,
+        constraint = master_membership.master.can_manage_realm_dimension(
+            &dimension
         ) @ ErrorCode::UnauthorizedRealmMaster
     )]
+    pub master_membership: Account<'info, RealmMembership>,
+
+    #[account(mut)]
     pub master: Signer<'info>,
 
     pub system_program: Program<'info, System>,
@@ -111,12 +143,18 @@ pub fn update_realm_dimension(
     _dimension_id: String,
     name: String,
     areas: Vec<RealmDimensionArea>,
+    allow_overlapping_areas: bool,
 ) -> Result<()> {
     require!(name.len() <= MAX_NAME_LEN, ErrorCode::NameTooLong);
+    RealmDimension::validate_areas(&areas, allow_overlapping_areas)?;
 
     let dimension = &mut ctx.accounts.dimension;
     dimension.name = name.clone();
     dimension.areas = areas;
+    dimension.allow_overlapping_areas = allow_overlapping_areas;
+    ctx.accounts
+        .realm
+        .validate_starting_point(&dimension.key(), dimension)?;
 
     emit!(RealmDimensionEvent {
         realm_pubkey: ctx.accounts.realm.key(),
@@ -127,6 +165,192 @@ pub fn update_realm_dimension(
     Ok(())
 }
 
+#[derive(Accounts)]
+#[instruction(realm_id: String, dimension_id: String, area: RealmDimensionArea)]
+pub struct AddDimensionArea<'info> {
+    #[account(
+        seeds = [REALM_SEED, realm_id.as_bytes()],
+        bump,
+        constraint = realm.is_active() @ ErrorCode::RealmClosing
+    )]
+    pub realm: Account<'info, Realm>,
+
+    #[account(
+        mut,
+        seeds = [DIMENSION_SEED, realm_id.as_bytes(), dimension_id.as_bytes()],
+        bump,
+        realloc = crate::realm_dimension_space!(
+            dimension.name,
+            dimension.areas.iter().chain(std::iter::once(&area))
+        ),
+        realloc::payer = master,
+        realloc::zero = false
+    )]
+    pub dimension: Account<'info, RealmDimension>,
+
+    #[account(
+        seeds = [MASTER_SEED, realm.key().as_ref(), master.key().as_ref()],
+        bump,
+        constraint = master_membership.master.can_manage_realm_dimension(
+            &dimension
+        ) @ ErrorCode::UnauthorizedRealmMaster
+    )]
+    pub master_membership: Account<'info, RealmMembership>,
+
+    #[account(mut)]
+    pub master: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn add_dimension_area(
+    ctx: Context<AddDimensionArea>,
+    _realm_id: String,
+    _dimension_id: String,
+    area: RealmDimensionArea,
+) -> Result<()> {
+    let dimension = &mut ctx.accounts.dimension;
+    let (area_id, name) = (area.id, area.name.clone());
+
+    dimension.areas.push(area);
+    RealmDimension::validate_areas(&dimension.areas, dimension.allow_overlapping_areas)?;
+
+    emit!(RealmDimensionEvent {
+        realm_pubkey: ctx.accounts.realm.key(),
+        dimension_pubkey: dimension.key(),
+        event_type: RealmDimensionEventType::AreaAdded { area_id, name },
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(realm_id: String, dimension_id: String, area: RealmDimensionArea)]
+pub struct UpdateDimensionArea<'info> {
+    #[account(
+        seeds = [REALM_SEED, realm_id.as_bytes()],
+        bump,
+        constraint = realm.is_active() @ ErrorCode::RealmClosing
+    )]
+    pub realm: Account<'info, Realm>,
+
+    #[account(
+        mut,
+        seeds = [DIMENSION_SEED, realm_id.as_bytes(), dimension_id.as_bytes()],
+        bump,
+        realloc = crate::realm_dimension_space!(
+            dimension.name,
+            dimension.areas.iter().map(|current| {
+                if current.id == area.id { &area } else { current }
+            })
+        ),
+        realloc::payer = master,
+        realloc::zero = false
+    )]
+    pub dimension: Account<'info, RealmDimension>,
+
+    #[account(
+        seeds = [MASTER_SEED, realm.key().as_ref(), master.key().as_ref()],
+        bump,
+        constraint = master_membership.master.can_manage_realm_dimension(
+            &dimension
+        ) @ ErrorCode::UnauthorizedRealmMaster
+    )]
+    pub master_membership: Account<'info, RealmMembership>,
+
+    #[account(mut)]
+    pub master: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// The area to update is addressed by its id
+pub fn update_dimension_area(
+    ctx: Context<UpdateDimensionArea>,
+    _realm_id: String,
+    _dimension_id: String,
+    area: RealmDimensionArea,
+) -> Result<()> {
+    let dimension = &mut ctx.accounts.dimension;
+    let (area_id, name) = (area.id, area.name.clone());
+
+    let index = dimension.area_index(area_id)?;
+    dimension.areas[index] = area;
+    RealmDimension::validate_areas(&dimension.areas, dimension.allow_overlapping_areas)?;
+    ctx.accounts
+        .realm
+        .validate_starting_point(&dimension.key(), dimension)?;
+
+    emit!(RealmDimensionEvent {
+        realm_pubkey: ctx.accounts.realm.key(),
+        dimension_pubkey: dimension.key(),
+        event_type: RealmDimensionEventType::AreaUpdated { area_id, name },
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(realm_id: String, dimension_id: String, area_id: u32)]
+pub struct RemoveDimensionArea<'info> {
+    #[account(
+        seeds = [REALM_SEED, realm_id.as_bytes()],
+        bump,
+        constraint = realm.is_active() @ ErrorCode::RealmClosing
+    )]
+    pub realm: Account<'info, Realm>,
+
+    #[account(
+        mut,
+        seeds = [DIMENSION_SEED, realm_id.as_bytes(), dimension_id.as_bytes()],
+        bump,
+        realloc = crate::realm_dimension_space!(
+            dimension.name,
+            dimension.areas.iter().filter(|area| area.id != area_id)
+        ),
+        realloc::payer = master,
+        realloc::zero = false
+    )]
+    pub dimension: Account<'info, RealmDimension>,
+
+    #[account(
+        seeds = [MASTER_SEED, realm.key().as_ref(), master.key().as_ref()],
+        bump,
+        constraint = master_membership.master.can_manage_realm_dimension(
+            &dimension
+        ) @ ErrorCode::UnauthorizedRealmMaster
+    )]
+    pub master_membership: Account<'info, RealmMembership>,
+
+    #[account(mut)]
+    pub master: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn remove_dimension_area(
+    ctx: Context<RemoveDimensionArea>,
+    _realm_id: String,
+    _dimension_id: String,
+    area_id: u32,
+) -> Result<()> {
+    let dimension = &mut ctx.accounts.dimension;
+
+    let index = dimension.area_index(area_id)?;
+    dimension.areas.remove(index);
+    ctx.accounts
+        .realm
+        .validate_starting_point(&dimension.key(), dimension)?;
+
+    emit!(RealmDimensionEvent {
+        realm_pubkey: ctx.accounts.realm.key(),
+        dimension_pubkey: dimension.key(),
+        event_type: RealmDimensionEventType::AreaRemoved { area_id },
+    });
+
+    Ok(())
+}
+
 #[derive(Accounts)]
 #[instruction(realm_id: String, dimension_id: String)]
 pub struct RemoveRealmDimension<'info> {
@@ -134,6 +358,7 @@ pub struct RemoveRealmDimension<'info> {
         mut,
         seeds = [REALM_SEED, realm_id.as_bytes()],
         bump,
+        constraint = realm.is_active() @ ErrorCode::RealmClosing,
         realloc = crate::realm_space!(
             realm.description,
             realm.masters.len(),
@@ -148,27 +373,80 @@ pub struct RemoveRealmDimension<'info> {
         mut,
         seeds = [DIMENSION_SEED, realm_id.as_bytes(), dimension_id.as_bytes()],
         bump,
-        close = master
+        close = owner
     )]
     pub dimension: Account<'info, RealmDimension>,
 
     #[account(
-        mut,
-        constraint = realm.masters.iter().any(|m|
-            m.pubkey == master.key() && m.can_manage_realm_dimension(&dimension)
+        seeds = [MASTER_SEED, realm.key().as_ref(), master.key().as_ref()],
+        bump,
+        constraint = master_membership.master.can_manage_realm_dimension(
+            &dimension
         ) @ ErrorCode::UnauthorizedRealmMaster
     )]
+    pub master_membership: Account<'info, RealmMembership>,
+
+    /// CHECK: Membership of the dimension owner, no longer initialized once the owner left the realm
+    #[account(
+        mut,
+        seeds = [MASTER_SEED, realm.key().as_ref(), dimension.owner.as_ref()],
+        bump
+    )]
+    pub owner_membership: UncheckedAccount<'info>,
+
+    #[account(mut)]
     pub master: Signer<'info>,
 
+    #[account(mut, address = dimension.owner)]
+    pub owner: SystemAccount<'info>,
+
+    #[account(mut, has_one = realm, close = master)]
+    pub queued_action: Option<Account<'info, RealmQueuedAction>>,
+
     pub system_program: Program<'info, System>,
 }
 
-pub fn remove_realm_dimension(
-    ctx: Context<RemoveRealmDimension>,
+// Portals leading out of and into the dimension, their creators, the source dimensions
+// of the incoming ones and its walkability grid are expected in the remaining accounts
+pub fn remove_realm_dimension<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RemoveRealmDimension<'info>>,
     _realm_id: String,
     _dimension_id: String,
 ) -> Result<()> {
     let realm = &mut ctx.accounts.realm;
+    let now = Clock::get()?.unix_timestamp;
+    let action = RealmTimelockAction::RemoveDimension {
+        dimension: ctx.accounts.dimension.key(),
+    };
+
+    realm.require_timelock(ctx.accounts.queued_action.as_deref(), &action, now)?;
+    close_dimension_portals(
+        &ctx.accounts.dimension,
+        &ctx.accounts.master,
+        ctx.remaining_accounts,
+    )?;
+    close_incoming_portals(
+        &ctx.accounts.dimension,
+        &ctx.accounts.master,
+        ctx.remaining_accounts,
+    )?;
+    close_dimension_walkability_grid(
+        &ctx.accounts.dimension,
+        ctx.accounts.owner.to_account_info(),
+        ctx.remaining_accounts,
+    )?;
+
+    let owner_membership_info = ctx.accounts.owner_membership.to_account_info();
+    if *owner_membership_info.owner == crate::ID {
+        let mut owner_membership =
+            RealmMembership::try_deserialize(&mut &owner_membership_info.try_borrow_data()?[..])?;
+        owner_membership.dimensions_count = owner_membership
+            .dimensions_count
+            .checked_sub(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        owner_membership
+            .try_serialize(&mut &mut owner_membership_info.try_borrow_mut_data()?[..])?;
+    }
 
     realm
         .dimensions
@@ -177,8 +455,26 @@ pub fn remove_realm_dimension(
     if realm.starting_dimension == Some(ctx.accounts.dimension.key()) {
         realm.starting_dimension = None;
         realm.starting_position = Position::default();
+
+        emit!(RealmEvent {
+            realm_pubkey: realm.key(),
+            event_type: RealmEventType::RealmStartingPointChanged {
+                dimension: None,
+                position: realm.starting_position,
+            },
+        });
+    }
+    realm.updated_at = now;
+
+    if let Some(queued_action) = &ctx.accounts.queued_action {
+        emit!(RealmEvent {
+            realm_pubkey: realm.key(),
+            event_type: RealmEventType::RealmActionExecuted {
+                queued_action: queued_action.key(),
+                action,
+            },
+        });
     }
-    realm.updated_at = Clock::get()?.unix_timestamp;
 
     emit!(RealmDimensionEvent {
         realm_pubkey: ctx.accounts.realm.key(),
@@ -196,6 +492,7 @@ pub struct SetRealmStartingDimension<'info> {
         mut,
         seeds = [REALM_SEED, realm_id.as_bytes()],
         bump,
+        constraint = realm.is_active() @ ErrorCode::RealmClosing,
     )]
     pub realm: Account<'info, Realm>,
 
@@ -207,11 +504,15 @@ pub struct SetRealmStartingDimension<'info> {
     pub dimension: Account<'info, RealmDimension>,
 
     #[account(
-        mut,
-        constraint = realm.masters.iter().any(|m|
-            m.pubkey == master.key() && m.can_set_realm_starting_point()
+        seeds = [MASTER_SEED, realm.key().as_ref(), master.key().as_ref()],
+        bump,
+        constraint = master_membership.master.has_permission(
+            RealmPermissions::SET_STARTING_POINT
         ) @ ErrorCode::UnauthorizedRealmMaster
     )]
+    pub master_membership: Account<'info, RealmMembership>,
+
+    #[account(mut)]
     pub master: Signer<'info>,
 
     pub system_program: Program<'info, System>,
@@ -223,10 +524,23 @@ pub fn set_realm_starting_point(
     _dimension_id: String,
     position: Position,
 ) -> Result<()> {
+    require!(
+        ctx.accounts.dimension.contains(&position),
+        ErrorCode::PositionOutsideDimension
+    );
+
     let realm = &mut ctx.accounts.realm;
     realm.starting_dimension = Some(ctx.accounts.dimension.key());
     realm.starting_position = position;
     realm.updated_at = Clock::get()?.unix_timestamp;
 
+    emit!(RealmEvent {
+        realm_pubkey: realm.key(),
+        event_type: RealmEventType::RealmStartingPointChanged {
+            dimension: Some(ctx.accounts.dimension.key()),
+            position,
+        },
+    });
+
     Ok(())
 }
@@ -1,16 +1,17 @@
 use anchor_lang::prelude::*;
 
 use crate::constants::*;
+use crate::errors::ErrorCode;
 use crate::events::*;
 use crate::state::*;
 
 #[derive(Accounts)]
-#[instruction(hero_id: String, description: HeroDescription)]
+#[instruction(hero_id: String, description: HeroDescription, tags: Vec<HeroTag>)]
 pub struct CreateHero<'info> {
     #[account(
         init,
         payer = master,
-        space = crate::hero_space!(description),
+        space = crate::hero_space!(description, tags.len(), 0),
         seeds = [HERO_SEED, master.key().as_ref(), hero_id.as_bytes()],
         bump
     )]
@@ -26,13 +27,20 @@ pub fn create_hero(
     ctx: Context<CreateHero>,
     _hero_id: String,
     description: HeroDescription,
+    tags: Vec<HeroTag>,
 ) -> Result<()> {
+    Hero::validate_tags(&tags)?;
+
     let now = Clock::get()?.unix_timestamp;
 
     let hero = &mut ctx.accounts.hero;
     hero.master = *ctx.accounts.master.key;
     hero.description = description;
-    // TODO: hero stats
+    hero.base_stats = HeroTag::compose_stats(&tags);
+    hero.tags = tags;
+    hero.xp_curve = XpCurve::default();
+    hero.stat_caps = HeroStats::uniform(DEFAULT_STAT_CAP);
+    hero.trusted_realms = vec![];
     hero.created_at = now;
     hero.updated_at = now;
 
@@ -52,7 +60,7 @@ pub struct UpdateHeroDescription<'info> {
         mut,
         seeds = [HERO_SEED, master.key().as_ref(), hero_id.as_bytes()],
         bump,
-        realloc = crate::hero_space!(description),
+        realloc = crate::hero_space!(description, hero.tags.len(), hero.trusted_realms.len()),
         realloc::payer = master,
         realloc::zero = false
     )]
@@ -82,50 +90,278 @@ pub fn update_hero_description(
     Ok(())
 }
 
-// #[derive(Accounts)]
-// #[instruction(hero_id: String, description: HeroDescription)]
-// pub struct CreateHero<'info> {
-//     #[account(
-//         init,
-//         payer = player,
-//         space = crate::player_hero_space!(description, 0, 0),
-//         seeds = [HERO_SEED, player.key().as_ref(), hero_id.as_bytes()],
-//         bump
-//     )]
-//     pub hero: Account<'info, PlayerHero>,
-
-//     #[account(mut)]
-//     pub player: Signer<'info>,
-
-//     pub system_program: Program<'info, System>,
-// }
-
-// pub fn create_hero(
-//     ctx: Context<CreateHero>,
-//     _hero_id: String,
-//     description: HeroDescription,
-// ) -> Result<()> {
-//     let now = Clock::get()?.unix_timestamp;
-
-//     let hero = &mut ctx.accounts.hero;
-//     hero.player = *ctx.accounts.player.key;
-//     hero.description = description.clone();
-//     // TODO: Add tags
-//     // hero.stats = tags
-//     //     .iter()
-//     //     .fold(HeroStats::zero(), |stats, tag| stats + tag.base_stats());
-//     // hero.tags = tags;
-//     hero.level = 1;
-//     hero.xp = 0;
-//     hero.items = vec![];
-//     hero.created_at = now;
-//     hero.updated_at = now;
-
-//     emit!(HeroEvent {
-//         player: *ctx.accounts.player.key,
-//         hero_pubkey: hero.key(),
-//         event_type: HeroEventType::HeroCreated { description },
-//     });
-
-//     Ok(())
-// }
+#[derive(Accounts)]
+#[instruction(player_hero_id: String, name: Option<String>)]
+pub struct MintPlayerHero<'info> {
+    pub hero: Account<'info, Hero>,
+
+    #[account(
+        init,
+        payer = player,
+        space = crate::player_hero_space!(name.as_ref().unwrap_or(&hero.description.name), 0, 0),
+        seeds = [PLAYER_HERO_SEED, player.key().as_ref(), player_hero_id.as_bytes()],
+        bump
+    )]
+    pub player_hero: Account<'info, PlayerHero>,
+
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn mint_player_hero(
+    ctx: Context<MintPlayerHero>,
+    _player_hero_id: String,
+    name: Option<String>,
+) -> Result<()> {
+    let hero = &ctx.accounts.hero;
+    let name = name.unwrap_or_else(|| hero.description.name.clone());
+    require!(name.len() <= MAX_NAME_LEN, ErrorCode::NameTooLong);
+
+    let now = Clock::get()?.unix_timestamp;
+
+    let player_hero = &mut ctx.accounts.player_hero;
+    player_hero.player = *ctx.accounts.player.key;
+    player_hero.hero = hero.key();
+    player_hero.name = name;
+    player_hero.level = 1;
+    player_hero.xp = 0;
+    player_hero.stats = hero.base_stats.capped(&hero.stat_caps);
+    player_hero.stat_points = 0;
+    player_hero.items = vec![];
+    player_hero.equipment = vec![];
+    player_hero.created_at = now;
+    player_hero.updated_at = now;
+
+    emit!(HeroEvent {
+        event_type: HeroEventType::PlayerHeroMinted {
+            player_hero_pubkey: player_hero.key(),
+            hero_pubkey: hero.key(),
+            player: player_hero.player,
+        },
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(hero_id: String)]
+pub struct SetHeroXpCurve<'info> {
+    #[account(
+        mut,
+        seeds = [HERO_SEED, master.key().as_ref(), hero_id.as_bytes()],
+        bump,
+    )]
+    pub hero: Account<'info, Hero>,
+
+    pub master: Signer<'info>,
+}
+
+pub fn set_hero_xp_curve(
+    ctx: Context<SetHeroXpCurve>,
+    _hero_id: String,
+    xp_curve: XpCurve,
+) -> Result<()> {
+    xp_curve.validate()?;
+
+    let hero = &mut ctx.accounts.hero;
+    hero.xp_curve = xp_curve.clone();
+    hero.updated_at = Clock::get()?.unix_timestamp;
+
+    emit!(HeroEvent {
+        event_type: HeroEventType::HeroXpCurveUpdated {
+            hero_pubkey: hero.key(),
+            xp_curve,
+        },
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(hero_id: String)]
+pub struct SetHeroStatCaps<'info> {
+    #[account(
+        mut,
+        seeds = [HERO_SEED, master.key().as_ref(), hero_id.as_bytes()],
+        bump,
+    )]
+    pub hero: Account<'info, Hero>,
+
+    pub master: Signer<'info>,
+}
+
+pub fn set_hero_stat_caps(
+    ctx: Context<SetHeroStatCaps>,
+    _hero_id: String,
+    stat_caps: HeroStats,
+) -> Result<()> {
+    let hero = &mut ctx.accounts.hero;
+    hero.stat_caps = stat_caps.clone();
+    hero.updated_at = Clock::get()?.unix_timestamp;
+
+    emit!(HeroEvent {
+        event_type: HeroEventType::HeroStatCapsUpdated {
+            hero_pubkey: hero.key(),
+            stat_caps,
+        },
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(hero_id: String, trusted_realms: Vec<Pubkey>)]
+pub struct SetHeroTrustedRealms<'info> {
+    #[account(
+        mut,
+        seeds = [HERO_SEED, master.key().as_ref(), hero_id.as_bytes()],
+        bump,
+        realloc = crate::hero_space!(hero.description, hero.tags.len(), trusted_realms.len()),
+        realloc::payer = master,
+        realloc::zero = false
+    )]
+    pub hero: Account<'info, Hero>,
+
+    #[account(mut)]
+    pub master: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn set_hero_trusted_realms(
+    ctx: Context<SetHeroTrustedRealms>,
+    _hero_id: String,
+    trusted_realms: Vec<Pubkey>,
+) -> Result<()> {
+    Hero::validate_trusted_realms(&trusted_realms)?;
+
+    let hero = &mut ctx.accounts.hero;
+    hero.trusted_realms = trusted_realms.clone();
+    hero.updated_at = Clock::get()?.unix_timestamp;
+
+    emit!(HeroEvent {
+        event_type: HeroEventType::HeroTrustedRealmsUpdated {
+            hero_pubkey: hero.key(),
+            trusted_realms,
+        },
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct GrantXp<'info> {
+    #[account(mut, has_one = hero)]
+    pub player_hero: Account<'info, PlayerHero>,
+
+    pub hero: Account<'info, Hero>,
+
+    // Masters of realms trusted by the hero class can grant experience to voyagers of their realm
+    pub realm: Option<Account<'info, Realm>>,
+    pub journey: Option<Account<'info, Journey>>,
+    pub master_membership: Option<Account<'info, RealmMembership>>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn grant_xp(ctx: Context<GrantXp>, amount: u64) -> Result<()> {
+    let authority = ctx.accounts.authority.key();
+    let player_hero = &mut ctx.accounts.player_hero;
+
+    let realm_master = match (
+        &ctx.accounts.realm,
+        &ctx.accounts.journey,
+        &ctx.accounts.master_membership,
+    ) {
+        (Some(realm), Some(journey), Some(membership)) => Some((realm, journey, membership)),
+        _ => None,
+    };
+    require!(
+        ctx.accounts.hero.can_reward(
+            &authority,
+            player_hero,
+            realm_master,
+            RealmPermissions::GRANT_XP
+        )?,
+        ErrorCode::UnauthorizedXpAuthority
+    );
+
+    player_hero.xp = player_hero
+        .xp
+        .checked_add(amount)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    player_hero.updated_at = Clock::get()?.unix_timestamp;
+
+    emit!(HeroEvent {
+        event_type: HeroEventType::XpGranted {
+            player_hero_pubkey: player_hero.key(),
+            authority,
+            amount,
+            xp: player_hero.xp,
+        },
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(player_hero_id: String)]
+pub struct LevelUp<'info> {
+    #[account(
+        mut,
+        seeds = [PLAYER_HERO_SEED, player.key().as_ref(), player_hero_id.as_bytes()],
+        bump,
+        has_one = hero
+    )]
+    pub player_hero: Account<'info, PlayerHero>,
+
+    pub hero: Account<'info, Hero>,
+
+    pub player: Signer<'info>,
+}
+
+pub fn level_up(
+    ctx: Context<LevelUp>,
+    _player_hero_id: String,
+    allocation: HeroStats,
+) -> Result<()> {
+    let hero = &ctx.accounts.hero;
+    let xp_curve = &hero.xp_curve;
+    let player_hero = &mut ctx.accounts.player_hero;
+
+    let required_xp = xp_curve.xp_for_next_level(player_hero.level)?;
+    require!(player_hero.xp >= required_xp, ErrorCode::NotEnoughXp);
+
+    let available_points = player_hero
+        .stat_points
+        .checked_add(xp_curve.stat_points_per_level)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    let spent_points = allocation.total().ok_or(ErrorCode::ArithmeticOverflow)?;
+    require!(
+        spent_points <= available_points,
+        ErrorCode::InvalidStatAllocation
+    );
+
+    player_hero.xp -= required_xp;
+    player_hero.level = player_hero
+        .level
+        .checked_add(1)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    player_hero.stat_points = available_points - spent_points;
+    player_hero.stats = player_hero
+        .stats
+        .checked_add_capped(&allocation, &hero.stat_caps)?;
+    player_hero.updated_at = Clock::get()?.unix_timestamp;
+
+    emit!(HeroEvent {
+        event_type: HeroEventType::HeroLeveledUp {
+            player_hero_pubkey: player_hero.key(),
+            level: player_hero.level,
+            stats: player_hero.stats.clone(),
+        },
+    });
+
+    Ok(())
+}
@@ -0,0 +1,408 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+#[instruction(item_id: String, description: ItemDescription)]
+pub struct CreateItem<'info> {
+    #[account(
+        init,
+        payer = master,
+        space = crate::item_space!(description),
+        seeds = [ITEM_SEED, master.key().as_ref(), item_id.as_bytes()],
+        bump
+    )]
+    pub item: Account<'info, Item>,
+
+    #[account(mut)]
+    pub master: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_item(
+    ctx: Context<CreateItem>,
+    _item_id: String,
+    description: ItemDescription,
+    rarity: ItemRarity,
+    slot: Option<EquipmentSlot>,
+    modifiers: HeroStats,
+    requirements: HeroStats,
+) -> Result<()> {
+    description.validate()?;
+
+    let now = Clock::get()?.unix_timestamp;
+
+    let item = &mut ctx.accounts.item;
+    item.master = *ctx.accounts.master.key;
+    item.description = description;
+    item.rarity = rarity;
+    item.slot = slot;
+    item.modifiers = modifiers;
+    item.requirements = requirements;
+    item.created_at = now;
+    item.updated_at = now;
+
+    emit!(ItemEvent {
+        event_type: ItemEventType::ItemCreated {
+            item_pubkey: item.key()
+        },
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct GrantItem<'info> {
+    #[account(
+        has_one = master @ ErrorCode::UnauthorizedItemMaster
+    )]
+    pub item: Account<'info, Item>,
+
+    #[account(
+        mut,
+        has_one = hero,
+        has_one = player,
+        realloc = crate::player_hero_space!(
+            player_hero.name,
+            player_hero.items.len() + 1, // Increment
+            player_hero.equipment.len()
+        ),
+        realloc::payer = master,
+        realloc::zero = false
+    )]
+    pub player_hero: Account<'info, PlayerHero>,
+
+    pub hero: Account<'info, Hero>,
+
+    // Masters of realms trusted by the hero class can grant items to voyagers of their realm
+    pub realm: Option<Account<'info, Realm>>,
+    pub journey: Option<Account<'info, Journey>>,
+    pub master_membership: Option<Account<'info, RealmMembership>>,
+
+    // Players accept every item added to their inventory
+    pub player: Signer<'info>,
+
+    #[account(mut)]
+    pub master: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn grant_item(ctx: Context<GrantItem>) -> Result<()> {
+    let realm_master = match (
+        &ctx.accounts.realm,
+        &ctx.accounts.journey,
+        &ctx.accounts.master_membership,
+    ) {
+        (Some(realm), Some(journey), Some(membership)) => Some((realm, journey, membership)),
+        _ => None,
+    };
+    require!(
+        ctx.accounts.hero.can_reward(
+            ctx.accounts.master.key,
+            &ctx.accounts.player_hero,
+            realm_master,
+            RealmPermissions::GRANT_ITEMS
+        )?,
+        ErrorCode::UnauthorizedItemMaster
+    );
+
+    let item = &ctx.accounts.item;
+    let player_hero = &mut ctx.accounts.player_hero;
+
+    require!(
+        player_hero.items.len() < MAX_INVENTORY_ITEMS,
+        ErrorCode::InventoryFull
+    );
+
+    player_hero.items.push(item.key());
+    player_hero.updated_at = Clock::get()?.unix_timestamp;
+
+    emit!(ItemEvent {
+        event_type: ItemEventType::ItemGranted {
+            item_pubkey: item.key(),
+            player_hero_pubkey: player_hero.key(),
+        },
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(player_hero_id: String, item_pubkey: Pubkey)]
+pub struct DropItem<'info> {
+    #[account(
+        mut,
+        seeds = [PLAYER_HERO_SEED, player.key().as_ref(), player_hero_id.as_bytes()],
+        bump,
+        constraint = player_hero.items.contains(&item_pubkey) @ ErrorCode::ItemNotInInventory,
+        constraint = player_hero.can_release_item(&item_pubkey) @ ErrorCode::ItemIsEquipped,
+        realloc = crate::player_hero_space!(
+            player_hero.name,
+            player_hero.items.len() - 1, // Decrement
+            player_hero.equipment.len()
+        ),
+        realloc::payer = player,
+        realloc::zero = false
+    )]
+    pub player_hero: Account<'info, PlayerHero>,
+
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn drop_item(
+    ctx: Context<DropItem>,
+    _player_hero_id: String,
+    item_pubkey: Pubkey,
+) -> Result<()> {
+    let player_hero = &mut ctx.accounts.player_hero;
+
+    let item_index = player_hero
+        .items
+        .iter()
+        .position(|item| *item == item_pubkey)
+        .ok_or(ErrorCode::ItemNotInInventory)?;
+
+    player_hero.items.remove(item_index);
+    player_hero.updated_at = Clock::get()?.unix_timestamp;
+
+    emit!(ItemEvent {
+        event_type: ItemEventType::ItemDropped {
+            item_pubkey,
+            player_hero_pubkey: player_hero.key(),
+        },
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(player_hero_id: String, item_pubkey: Pubkey)]
+pub struct TransferItem<'info> {
+    #[account(
+        mut,
+        seeds = [PLAYER_HERO_SEED, player.key().as_ref(), player_hero_id.as_bytes()],
+        bump,
+        constraint = player_hero.items.contains(&item_pubkey) @ ErrorCode::ItemNotInInventory,
+        constraint = player_hero.can_release_item(&item_pubkey) @ ErrorCode::ItemIsEquipped,
+        realloc = crate::player_hero_space!(
+            player_hero.name,
+            player_hero.items.len() - 1, // Decrement
+            player_hero.equipment.len()
+        ),
+        realloc::payer = player,
+        realloc::zero = false
+    )]
+    pub player_hero: Account<'info, PlayerHero>,
+
+    #[account(
+        mut,
+        constraint = target_player_hero.key() != player_hero.key(),
+        constraint = target_player_hero.player == target_player.key(),
+        realloc = crate::player_hero_space!(
+            target_player_hero.name,
+            target_player_hero.items.len() + 1, // Increment
+            target_player_hero.equipment.len()
+        ),
+        realloc::payer = player,
+        realloc::zero = false
+    )]
+    pub target_player_hero: Account<'info, PlayerHero>,
+
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    // Players accept every item added to their inventory
+    pub target_player: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn transfer_item(
+    ctx: Context<TransferItem>,
+    _player_hero_id: String,
+    item_pubkey: Pubkey,
+) -> Result<()> {
+    let player_hero = &mut ctx.accounts.player_hero;
+    let target_player_hero = &mut ctx.accounts.target_player_hero;
+
+    require!(
+        target_player_hero.items.len() < MAX_INVENTORY_ITEMS,
+        ErrorCode::InventoryFull
+    );
+
+    let item_index = player_hero
+        .items
+        .iter()
+        .position(|item| *item == item_pubkey)
+        .ok_or(ErrorCode::ItemNotInInventory)?;
+
+    let now = Clock::get()?.unix_timestamp;
+
+    player_hero.items.remove(item_index);
+    player_hero.updated_at = now;
+
+    target_player_hero.items.push(item_pubkey);
+    target_player_hero.updated_at = now;
+
+    emit!(ItemEvent {
+        event_type: ItemEventType::ItemTransferred {
+            item_pubkey,
+            from_player_hero: player_hero.key(),
+            to_player_hero: target_player_hero.key(),
+        },
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(player_hero_id: String)]
+pub struct EquipItem<'info> {
+    #[account(
+        mut,
+        seeds = [PLAYER_HERO_SEED, player.key().as_ref(), player_hero_id.as_bytes()],
+        bump,
+        constraint = player_hero.items.contains(&item.key()) @ ErrorCode::ItemNotInInventory,
+        has_one = hero,
+        realloc = crate::player_hero_space!(
+            player_hero.name,
+            player_hero.items.len(),
+            player_hero.equipment.len() + 1 // Increment
+        ),
+        realloc::payer = player,
+        realloc::zero = false
+    )]
+    pub player_hero: Account<'info, PlayerHero>,
+
+    pub hero: Account<'info, Hero>,
+
+    pub item: Account<'info, Item>,
+
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn equip_item<'info>(
+    ctx: Context<'_, '_, 'info, 'info, EquipItem<'info>>,
+    _player_hero_id: String,
+    slot: EquipmentSlot,
+) -> Result<()> {
+    let item = &ctx.accounts.item;
+    let stat_caps = &ctx.accounts.hero.stat_caps;
+    let player_hero = &mut ctx.accounts.player_hero;
+
+    require!(item.slot == Some(slot), ErrorCode::InvalidEquipmentSlot);
+    require!(
+        player_hero.equipped_item(slot).is_none(),
+        ErrorCode::EquipmentSlotOccupied
+    );
+    require!(
+        player_hero.can_release_item(&item.key()),
+        ErrorCode::ItemIsEquipped
+    );
+
+    let mut equipped_items = load_equipped_items(player_hero, ctx.remaining_accounts)?;
+    require!(
+        effective_stats(player_hero, &equipped_items)
+            .capped(stat_caps)
+            .meets(&item.requirements),
+        ErrorCode::ItemRequirementsNotMet
+    );
+
+    player_hero.equipment.push(EquippedItem {
+        slot,
+        item: item.key(),
+    });
+    player_hero.updated_at = Clock::get()?.unix_timestamp;
+    equipped_items.push(item.clone().into_inner());
+
+    emit!(ItemEvent {
+        event_type: ItemEventType::ItemEquipped {
+            item_pubkey: item.key(),
+            player_hero_pubkey: player_hero.key(),
+            slot,
+            effective_stats: effective_stats(player_hero, &equipped_items).capped(stat_caps),
+        },
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(player_hero_id: String, slot: EquipmentSlot)]
+pub struct UnequipItem<'info> {
+    #[account(
+        mut,
+        seeds = [PLAYER_HERO_SEED, player.key().as_ref(), player_hero_id.as_bytes()],
+        bump,
+        constraint = player_hero.equipped_item(slot).is_some() @ ErrorCode::EquipmentSlotEmpty,
+        realloc = crate::player_hero_space!(
+            player_hero.name,
+            player_hero.items.len(),
+            player_hero.equipment.len() - 1 // Decrement
+        ),
+        realloc::payer = player,
+        realloc::zero = false
+    )]
+    pub player_hero: Account<'info, PlayerHero>,
+
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn unequip_item(
+    ctx: Context<UnequipItem>,
+    _player_hero_id: String,
+    slot: EquipmentSlot,
+) -> Result<()> {
+    let player_hero = &mut ctx.accounts.player_hero;
+
+    let equipped_index = player_hero
+        .equipment
+        .iter()
+        .position(|equipped| equipped.slot == slot)
+        .ok_or(ErrorCode::EquipmentSlotEmpty)?;
+
+    let equipped = player_hero.equipment.remove(equipped_index);
+    player_hero.updated_at = Clock::get()?.unix_timestamp;
+
+    emit!(ItemEvent {
+        event_type: ItemEventType::ItemUnequipped {
+            item_pubkey: equipped.item,
+            player_hero_pubkey: player_hero.key(),
+            slot,
+        },
+    });
+
+    Ok(())
+}
+
+// Equipped item accounts are expected in the remaining accounts
+pub fn load_equipped_items<'info>(
+    player_hero: &PlayerHero,
+    remaining_accounts: &'info [AccountInfo<'info>],
+) -> Result<Vec<Item>> {
+    player_hero
+        .equipment
+        .iter()
+        .map(|equipped| {
+            let item_info = remaining_accounts
+                .iter()
+                .find(|account_info| *account_info.key == equipped.item)
+                .ok_or(ErrorCode::EquippedItemNotProvided)?;
+            Ok(Account::<Item>::try_from(item_info)?.into_inner())
+        })
+        .collect()
+}
@@ -2,12 +2,18 @@ use anchor_lang::prelude::*;
 
 use crate::constants::*;
 use crate::errors::ErrorCode;
+use crate::events::*;
 use crate::state::*;
 
 #[derive(Accounts)]
-#[instruction(realm_id: String)]
+#[instruction(realm_id: String, allowlist_proof: Vec<[u8; 32]>, spawn_point_id: Option<String>)]
 pub struct StartJourney<'info> {
-    #[account(seeds = [REALM_SEED, realm_id.as_bytes()], bump)]
+    #[account(
+        mut,
+        seeds = [REALM_SEED, realm_id.as_bytes()],
+        bump,
+        constraint = realm.is_active() @ ErrorCode::RealmClosing
+    )]
     pub realm: Account<'info, Realm>,
 
     #[account(
@@ -22,23 +28,476 @@ pub struct StartJourney<'info> {
     #[account(mut)]
     pub player: Signer<'info>,
 
+    // Spawn point chosen by the player
+    #[account(
+        seeds = [
+            SPAWN_POINT_SEED,
+            realm_id.as_bytes(),
+            spawn_point_id.as_deref().unwrap_or_default().as_bytes()
+        ],
+        bump,
+        has_one = realm
+    )]
+    pub spawn_point: Option<Account<'info, RealmSpawnPoint>>,
+
+    // Hero the player starts with, checked against the spawn point requirements
+    #[account(has_one = player)]
+    pub player_hero: Option<Account<'info, PlayerHero>>,
+    pub hero: Option<Account<'info, Hero>>,
+
     pub system_program: Program<'info, System>,
 }
 
-pub fn start_journey(ctx: Context<StartJourney>, _realm_id: String) -> Result<()> {
-    let realm = &ctx.accounts.realm;
+// Invite-only realms expect a Merkle proof of the player in the realm allowlist.
+// Without a chosen spawn point, all the realm spawn points are expected in the remaining accounts
+pub fn start_journey<'info>(
+    ctx: Context<'_, '_, 'info, 'info, StartJourney<'info>>,
+    _realm_id: String,
+    allowlist_proof: Vec<[u8; 32]>,
+    spawn_point_id: Option<String>,
+) -> Result<()> {
+    let realm = &mut ctx.accounts.realm;
+    let player = ctx.accounts.player.key;
 
     require!(
-        realm.starting_dimension.is_some(),
-        ErrorCode::RealmHasNoStartingDimension
+        allowlist_proof.len() <= MAX_ALLOWLIST_PROOF_LEN,
+        ErrorCode::AllowlistProofTooLong
     );
+    require!(
+        realm.is_invited(player, &allowlist_proof),
+        ErrorCode::PlayerNotInvited
+    );
+
+    let level = ctx
+        .accounts
+        .player_hero
+        .as_ref()
+        .map_or(0, |player_hero| player_hero.level);
+    let tags = match (&ctx.accounts.player_hero, &ctx.accounts.hero) {
+        (Some(player_hero), Some(hero)) if player_hero.hero == hero.key() => hero.tags.as_slice(),
+        _ => &[],
+    };
+
+    let (spawn_point, dimension, position) = if spawn_point_id.is_some() {
+        let spawn_point = ctx
+            .accounts
+            .spawn_point
+            .as_ref()
+            .ok_or(ErrorCode::SpawnPointNotProvided)?;
+        require!(
+            spawn_point.allows(realm, level, tags),
+            ErrorCode::SpawnRequirementsNotMet
+        );
+        (
+            Some(spawn_point.key()),
+            spawn_point.dimension,
+            spawn_point.settings.position,
+        )
+    } else if realm.spawn_points_count > 0 {
+        let spawn_points = load_spawn_points(realm, ctx.remaining_accounts)?;
+
+        let spawn_point = RealmSpawnPoint::pick(&spawn_points, realm, player, level, tags)
+            .ok_or(ErrorCode::NoEligibleSpawnPoint)?;
+        (
+            Some(spawn_point.key()),
+            spawn_point.dimension,
+            spawn_point.settings.position,
+        )
+    } else {
+        let dimension = realm
+            .starting_dimension
+            .ok_or(ErrorCode::RealmHasNoStartingDimension)?;
+        (None, dimension, realm.starting_position)
+    };
+
+    realm.journeys_count = realm
+        .journeys_count
+        .checked_add(1)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
 
     let journey = &mut ctx.accounts.journey;
     journey.realm = realm.key();
     journey.started_at = Clock::get()?.unix_timestamp;
-    journey.player = *ctx.accounts.player.key;
-    journey.dimension = realm.starting_dimension.unwrap();
-    journey.position = realm.starting_position;
+    journey.nonce = realm.journeys_count;
+    journey.player = *player;
+    journey.dimension = dimension;
+    journey.position = position;
+
+    emit!(JourneyEvent {
+        journey_pubkey: journey.key(),
+        realm_pubkey: journey.realm,
+        player_pubkey: journey.player,
+        event_type: JourneyEventType::JourneyStarted {
+            dimension: journey.dimension,
+            position: journey.position,
+            spawn_point,
+        },
+    });
+
+    Ok(())
+}
+
+// All spawn points of the realm are expected in the remaining accounts, sorted by key
+fn load_spawn_points<'info>(
+    realm: &Account<Realm>,
+    remaining_accounts: &'info [AccountInfo<'info>],
+) -> Result<Vec<Account<'info, RealmSpawnPoint>>> {
+    let mut spawn_points: Vec<Account<RealmSpawnPoint>> = Vec::new();
+    for account_info in remaining_accounts.iter() {
+        if *account_info.owner != crate::ID
+            || spawn_points
+                .iter()
+                .any(|spawn_point| spawn_point.key() == *account_info.key)
+        {
+            continue;
+        }
+
+        let Ok(spawn_point) = Account::<RealmSpawnPoint>::try_from(account_info) else {
+            continue;
+        };
+        if spawn_point.realm == realm.key() {
+            spawn_points.push(spawn_point);
+        }
+    }
+    require!(
+        spawn_points.len() == realm.spawn_points_count as usize,
+        ErrorCode::SpawnPointNotProvided
+    );
+
+    spawn_points.sort_by_key(|spawn_point| spawn_point.key());
+    Ok(spawn_points)
+}
+
+#[derive(Accounts)]
+#[instruction(realm_id: String)]
+pub struct EndJourney<'info> {
+    #[account(
+        mut,
+        seeds = [JOURNEY_SEED, realm_id.as_bytes(), player.key().as_ref()],
+        bump,
+        close = player
+    )]
+    pub journey: Account<'info, Journey>,
+
+    #[account(mut)]
+    pub player: Signer<'info>,
+}
+
+pub fn end_journey(ctx: Context<EndJourney>, _realm_id: String) -> Result<()> {
+    let journey = &ctx.accounts.journey;
+
+    emit!(JourneyEvent {
+        journey_pubkey: journey.key(),
+        realm_pubkey: journey.realm,
+        player_pubkey: journey.player,
+        event_type: JourneyEventType::JourneyEnded {},
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(realm_id: String)]
+pub struct KickVoyager<'info> {
+    #[account(seeds = [REALM_SEED, realm_id.as_bytes()], bump)]
+    pub realm: Account<'info, Realm>,
+
+    #[account(
+        mut,
+        seeds = [JOURNEY_SEED, realm_id.as_bytes(), player.key().as_ref()],
+        bump,
+        has_one = realm,
+        close = player
+    )]
+    pub journey: Account<'info, Journey>,
+
+    #[account(mut)]
+    pub player: SystemAccount<'info>,
+
+    #[account(
+        seeds = [MASTER_SEED, realm.key().as_ref(), master.key().as_ref()],
+        bump,
+        constraint = master_membership.master.has_permission(
+            RealmPermissions::MODERATE_VOYAGERS
+        ) @ ErrorCode::UnauthorizedRealmMaster
+    )]
+    pub master_membership: Account<'info, RealmMembership>,
+
+    pub master: Signer<'info>,
+}
+
+pub fn kick_voyager(ctx: Context<KickVoyager>, _realm_id: String) -> Result<()> {
+    let journey = &ctx.accounts.journey;
+
+    emit!(JourneyEvent {
+        journey_pubkey: journey.key(),
+        realm_pubkey: journey.realm,
+        player_pubkey: journey.player,
+        event_type: JourneyEventType::VoyagerKicked {
+            master: ctx.accounts.master.key(),
+        },
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(realm_id: String)]
+pub struct MoveHero<'info> {
+    #[account(
+        seeds = [REALM_SEED, realm_id.as_bytes()],
+        bump,
+        constraint = realm.is_active() @ ErrorCode::RealmClosing
+    )]
+    pub realm: Account<'info, Realm>,
+
+    #[account(
+        mut,
+        seeds = [JOURNEY_SEED, realm_id.as_bytes(), player.key().as_ref()],
+        bump,
+        has_one = realm,
+        has_one = dimension,
+        constraint = journey.is_current(&realm) @ ErrorCode::StaleJourney
+    )]
+    pub journey: Account<'info, Journey>,
+
+    pub dimension: Account<'info, RealmDimension>,
+
+    #[account(seeds = [WALKABILITY_GRID_SEED, dimension.key().as_ref()], bump)]
+    pub grid: Option<Account<'info, WalkabilityGrid>>,
+
+    pub player: SystemAccount<'info>,
+
+    #[account(has_one = journey)]
+    pub session_key: Option<Account<'info, SessionKey>>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn move_hero(ctx: Context<MoveHero>, _realm_id: String, position: Position) -> Result<()> {
+    ctx.accounts.journey.require_authority(
+        ctx.accounts.authority.key,
+        ctx.accounts.session_key.as_deref(),
+        SessionScope::MOVE_HERO,
+        Clock::get()?.unix_timestamp,
+    )?;
+
+    require!(
+        ctx.accounts.dimension.contains(&position),
+        ErrorCode::PositionOutsideDimension
+    );
+
+    let journey = &mut ctx.accounts.journey;
+    require!(
+        journey.position.distance(&position) <= MAX_MOVE_DISTANCE,
+        ErrorCode::MoveDistanceTooLong
+    );
+
+    if ctx.accounts.dimension.has_walkability_grid {
+        let grid = ctx
+            .accounts
+            .grid
+            .as_ref()
+            .ok_or(ErrorCode::WalkabilityGridNotProvided)?;
+        require!(
+            grid.is_path_walkable(&journey.position, &position),
+            ErrorCode::PositionNotWalkable
+        );
+    }
+
+    let from = journey.position;
+    journey.position = position;
+
+    emit!(JourneyEvent {
+        journey_pubkey: journey.key(),
+        realm_pubkey: journey.realm,
+        player_pubkey: journey.player,
+        event_type: JourneyEventType::HeroMoved { from, to: position },
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(realm_id: String)]
+pub struct TravelThroughPortal<'info> {
+    #[account(
+        seeds = [REALM_SEED, realm_id.as_bytes()],
+        bump,
+        constraint = realm.is_active() @ ErrorCode::RealmClosing
+    )]
+    pub realm: Account<'info, Realm>,
+
+    #[account(
+        mut,
+        seeds = [JOURNEY_SEED, realm_id.as_bytes(), player.key().as_ref()],
+        bump,
+        has_one = realm,
+        has_one = dimension,
+        constraint = journey.is_current(&realm) @ ErrorCode::StaleJourney
+    )]
+    pub journey: Account<'info, Journey>,
+
+    pub dimension: Account<'info, RealmDimension>,
+
+    #[account(has_one = realm, has_one = dimension, has_one = target_dimension)]
+    pub portal: Account<'info, RealmPortal>,
+
+    pub target_dimension: Account<'info, RealmDimension>,
+
+    #[account(seeds = [WALKABILITY_GRID_SEED, target_dimension.key().as_ref()], bump)]
+    pub target_grid: Option<Account<'info, WalkabilityGrid>>,
+
+    pub player: SystemAccount<'info>,
+
+    #[account(has_one = journey)]
+    pub session_key: Option<Account<'info, SessionKey>>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn travel_through_portal(ctx: Context<TravelThroughPortal>, _realm_id: String) -> Result<()> {
+    ctx.accounts.journey.require_authority(
+        ctx.accounts.authority.key,
+        ctx.accounts.session_key.as_deref(),
+        SessionScope::TRAVEL_THROUGH_PORTAL,
+        Clock::get()?.unix_timestamp,
+    )?;
+
+    let portal = &ctx.accounts.portal;
+    let journey = &mut ctx.accounts.journey;
+
+    require!(
+        portal.area.contains(&journey.position),
+        ErrorCode::HeroNotInPortal
+    );
+
+    // The target dimension may have been shrunk or given a grid since the portal was added
+    let target_dimension = &ctx.accounts.target_dimension;
+    require!(
+        target_dimension.contains(&portal.target_position),
+        ErrorCode::PositionOutsideDimension
+    );
+    if target_dimension.has_walkability_grid {
+        let target_grid = ctx
+            .accounts
+            .target_grid
+            .as_ref()
+            .ok_or(ErrorCode::WalkabilityGridNotProvided)?;
+        require!(
+            target_grid.is_walkable(&portal.target_position),
+            ErrorCode::PositionNotWalkable
+        );
+    }
+
+    let from_dimension = journey.dimension;
+    journey.dimension = portal.target_dimension;
+    journey.position = portal.target_position;
+
+    emit!(JourneyEvent {
+        journey_pubkey: journey.key(),
+        realm_pubkey: journey.realm,
+        player_pubkey: journey.player,
+        event_type: JourneyEventType::HeroTraveled {
+            portal_pubkey: portal.key(),
+            from_dimension,
+            to_dimension: journey.dimension,
+            position: journey.position,
+        },
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(realm_id: String, authority: Pubkey)]
+pub struct CreateSessionKey<'info> {
+    #[account(
+        seeds = [REALM_SEED, realm_id.as_bytes()],
+        bump,
+        constraint = realm.is_active() @ ErrorCode::RealmClosing
+    )]
+    pub realm: Account<'info, Realm>,
+
+    #[account(
+        seeds = [JOURNEY_SEED, realm_id.as_bytes(), player.key().as_ref()],
+        bump,
+        has_one = realm,
+        constraint = journey.is_current(&realm) @ ErrorCode::StaleJourney
+    )]
+    pub journey: Account<'info, Journey>,
+
+    #[account(
+        init,
+        payer = player,
+        space = 8 + SessionKey::INIT_SPACE,
+        seeds = [SESSION_KEY_SEED, journey.key().as_ref(), authority.as_ref()],
+        bump
+    )]
+    pub session_key: Account<'info, SessionKey>,
+
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_session_key(
+    ctx: Context<CreateSessionKey>,
+    _realm_id: String,
+    authority: Pubkey,
+    scope: u8,
+    expires_at: i64,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    SessionKey::validate(scope, expires_at, now)?;
+
+    let journey = &ctx.accounts.journey;
+    let session_key = &mut ctx.accounts.session_key;
+    session_key.realm = journey.realm;
+    session_key.journey = journey.key();
+    session_key.player = journey.player;
+    session_key.authority = authority;
+    session_key.journey_started_at = journey.started_at;
+    session_key.journey_nonce = journey.nonce;
+    session_key.scope = scope;
+    session_key.created_at = now;
+    session_key.expires_at = expires_at;
+
+    emit!(JourneyEvent {
+        journey_pubkey: journey.key(),
+        realm_pubkey: journey.realm,
+        player_pubkey: journey.player,
+        event_type: JourneyEventType::SessionKeyCreated {
+            authority,
+            scope,
+            expires_at,
+        },
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RevokeSessionKey<'info> {
+    #[account(mut, has_one = player, close = player)]
+    pub session_key: Account<'info, SessionKey>,
+
+    #[account(mut)]
+    pub player: Signer<'info>,
+}
+
+pub fn revoke_session_key(ctx: Context<RevokeSessionKey>) -> Result<()> {
+    let session_key = &ctx.accounts.session_key;
+
+    emit!(JourneyEvent {
+        journey_pubkey: session_key.journey,
+        realm_pubkey: session_key.realm,
+        player_pubkey: session_key.player,
+        event_type: JourneyEventType::SessionKeyRevoked {
+            authority: session_key.authority,
+        },
+    });
 
     Ok(())
 }
@@ -1,4 +1,6 @@
 use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_lang::Discriminator;
 
 use crate::constants::*;
 use crate::errors::ErrorCode;
@@ -6,54 +8,282 @@ use crate::events::*;
 use crate::state::*;
 
 #[derive(Accounts)]
-#[instruction(realm_id: String, new_master_pubkey: Pubkey)]
+#[instruction(realm_id: String, new_master_pubkey: Pubkey, role: RealmMasterRole)]
 pub struct AddRealmMaster<'info> {
     #[account(
         mut,
         seeds = [REALM_SEED, realm_id.as_bytes()],
         bump,
-        realloc = crate::realm_space!(
-            realm.description,
-            realm.masters.len() + 1, // Increment
-            realm.dimensions.len()
-        ),
-        realloc::payer = master,
-        realloc::zero = false
+        constraint = realm.is_active() @ ErrorCode::RealmClosing,
     )]
     pub realm: Account<'info, Realm>,
 
+    // Masters can only assign roles within their own permissions
+    #[account(
+        seeds = [MASTER_SEED, realm.key().as_ref(), master.key().as_ref()],
+        bump,
+        constraint = master_membership.master.has_permission(RealmPermissions::MANAGE_MASTERS)
+            && master_membership.master.has_permission(role.permissions())
+            @ ErrorCode::UnauthorizedRealmMaster
+    )]
+    pub master_membership: Account<'info, RealmMembership>,
+
+    #[account(
+        init,
+        payer = master,
+        space = 8 + RealmMembership::INIT_SPACE,
+        seeds = [MASTER_SEED, realm.key().as_ref(), new_master_pubkey.as_ref()],
+        bump
+    )]
+    pub new_master_membership: Account<'info, RealmMembership>,
+
     #[account(
         mut,
-        constraint = realm.masters.iter().any(|m|
-            m.pubkey == master.key() && m.can_manage_realm_masters()
-        ) @ ErrorCode::UnauthorizedRealmMaster,
-        constraint = !realm.masters.iter().any(|m|
-            m.pubkey == new_master_pubkey
-        ) @ ErrorCode::DuplicateRealmMaster
+        constraint = role != RealmMasterRole::Owner @ ErrorCode::CantAssignOwnerRole
     )]
     pub master: Signer<'info>,
 
+    #[account(mut, has_one = realm, has_one = proposer, close = proposer)]
+    pub proposal: Option<Account<'info, RealmProposal>>,
+
+    #[account(mut)]
+    pub proposer: Option<SystemAccount<'info>>,
+
     pub system_program: Program<'info, System>,
 }
 
+// The approvers' memberships are expected in the remaining accounts under an approval policy
 pub fn add_realm_master(
     ctx: Context<AddRealmMaster>,
     _realm_id: String,
     new_master_pubkey: Pubkey,
+    role: RealmMasterRole,
 ) -> Result<()> {
     let realm = &mut ctx.accounts.realm;
+    realm.require_approval(
+        ctx.accounts.proposal.as_deref(),
+        &RealmProposalAction::AddMaster {
+            master: new_master_pubkey,
+            role: role.clone(),
+        },
+        &RealmMembership::load_approvers(&realm.key(), ctx.remaining_accounts),
+    )?;
 
-    let master = RealmMaster {
-        pubkey: new_master_pubkey,
-        role: RealmMasterRole::Admin,
-    };
+    let master = RealmMaster::new(new_master_pubkey, role);
+
+    let membership = &mut ctx.accounts.new_master_membership;
+    membership.realm = realm.key();
+    membership.master = master.clone();
+    membership.created_at = Clock::get()?.unix_timestamp;
+
+    realm.masters_count += 1;
+    realm.updated_at = membership.created_at;
+
+    if let Some(proposal) = &ctx.accounts.proposal {
+        emit!(RealmProposalEvent {
+            realm_pubkey: realm.key(),
+            proposal_pubkey: proposal.key(),
+            event_type: RealmProposalEventType::ProposalExecuted {},
+        });
+    }
+
+    emit!(RealmEvent {
+        realm_pubkey: realm.key(),
+        event_type: RealmEventType::RealmMasterAdded {
+            master: master.clone(),
+        },
+    });
 
-    realm.masters.push(master.clone());
+    emit!(RealmEvent {
+        realm_pubkey: realm.key(),
+        event_type: RealmEventType::RealmMasterRoleChanged {
+            master,
+            previous_role: None,
+        },
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(realm_id: String, master_pubkey: Pubkey, role: RealmMasterRole)]
+pub struct SetRealmMasterRole<'info> {
+    #[account(
+        mut,
+        seeds = [REALM_SEED, realm_id.as_bytes()],
+        bump,
+        constraint = realm.is_active() @ ErrorCode::RealmClosing,
+    )]
+    pub realm: Account<'info, Realm>,
+
+    // Masters can only assign roles within their own permissions
+    #[account(
+        seeds = [MASTER_SEED, realm.key().as_ref(), master.key().as_ref()],
+        bump,
+        constraint = master_membership.master.has_permission(RealmPermissions::MANAGE_MASTERS)
+            && master_membership.master.has_permission(role.permissions())
+            @ ErrorCode::UnauthorizedRealmMaster
+    )]
+    pub master_membership: Account<'info, RealmMembership>,
+
+    #[account(
+        mut,
+        seeds = [MASTER_SEED, realm.key().as_ref(), master_pubkey.as_ref()],
+        bump,
+        constraint = !target_membership.master.is_owner() @ ErrorCode::CantRemoveRealmOwner
+    )]
+    pub target_membership: Account<'info, RealmMembership>,
+
+    #[account(
+        mut,
+        constraint = role != RealmMasterRole::Owner @ ErrorCode::CantAssignOwnerRole
+    )]
+    pub master: Signer<'info>,
+
+    #[account(mut, has_one = realm, has_one = proposer, close = proposer)]
+    pub proposal: Option<Account<'info, RealmProposal>>,
+
+    #[account(mut)]
+    pub proposer: Option<SystemAccount<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// The approvers' memberships are expected in the remaining accounts under an approval policy
+pub fn set_realm_master_role(
+    ctx: Context<SetRealmMasterRole>,
+    _realm_id: String,
+    master_pubkey: Pubkey,
+    role: RealmMasterRole,
+) -> Result<()> {
+    let realm = &mut ctx.accounts.realm;
+    let approvers = RealmMembership::load_approvers(&realm.key(), ctx.remaining_accounts);
+    realm.require_approval(
+        ctx.accounts.proposal.as_deref(),
+        &RealmProposalAction::SetMasterRole {
+            master: master_pubkey,
+            role: role.clone(),
+        },
+        &approvers,
+    )?;
+
+    let master = &mut ctx.accounts.target_membership.master;
+    let previous_role = master.role.clone();
+    let was_approver = master.is_approver();
+    master.set_role(role);
+    let master = master.clone();
+    if was_approver && !master.is_approver() {
+        realm.require_approvers_left(&master.pubkey, &approvers)?;
+    }
     realm.updated_at = Clock::get()?.unix_timestamp;
 
+    if let Some(proposal) = &ctx.accounts.proposal {
+        emit!(RealmProposalEvent {
+            realm_pubkey: realm.key(),
+            proposal_pubkey: proposal.key(),
+            event_type: RealmProposalEventType::ProposalExecuted {},
+        });
+    }
+
     emit!(RealmEvent {
         realm_pubkey: realm.key(),
-        event_type: RealmEventType::RealmMasterAdded { master },
+        event_type: RealmEventType::RealmMasterRoleChanged {
+            master,
+            previous_role: Some(previous_role),
+        },
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(realm_id: String, master_pubkey: Pubkey, granted: u32, revoked: u32)]
+pub struct UpdateRealmMasterPermissions<'info> {
+    #[account(
+        mut,
+        seeds = [REALM_SEED, realm_id.as_bytes()],
+        bump,
+        constraint = realm.is_active() @ ErrorCode::RealmClosing,
+    )]
+    pub realm: Account<'info, Realm>,
+
+    // Masters can only grant permissions they have themselves
+    #[account(
+        seeds = [MASTER_SEED, realm.key().as_ref(), master.key().as_ref()],
+        bump,
+        constraint = master_membership.master.has_permission(RealmPermissions::MANAGE_MASTERS)
+            && master_membership.master.has_permission(granted)
+            @ ErrorCode::UnauthorizedRealmMaster
+    )]
+    pub master_membership: Account<'info, RealmMembership>,
+
+    #[account(
+        mut,
+        seeds = [MASTER_SEED, realm.key().as_ref(), master_pubkey.as_ref()],
+        bump,
+        constraint = !target_membership.master.is_owner() @ ErrorCode::CantChangeOwnerPermissions
+    )]
+    pub target_membership: Account<'info, RealmMembership>,
+
+    #[account(
+        mut,
+        constraint = (granted | revoked) & !RealmPermissions::ALL == 0 @ ErrorCode::InvalidRealmPermissions
+    )]
+    pub master: Signer<'info>,
+
+    #[account(mut, has_one = realm, has_one = proposer, close = proposer)]
+    pub proposal: Option<Account<'info, RealmProposal>>,
+
+    #[account(mut)]
+    pub proposer: Option<SystemAccount<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// The approvers' memberships are expected in the remaining accounts under an approval policy
+pub fn update_realm_master_permissions(
+    ctx: Context<UpdateRealmMasterPermissions>,
+    _realm_id: String,
+    master_pubkey: Pubkey,
+    granted: u32,
+    revoked: u32,
+) -> Result<()> {
+    let realm = &mut ctx.accounts.realm;
+    let approvers = RealmMembership::load_approvers(&realm.key(), ctx.remaining_accounts);
+    realm.require_approval(
+        ctx.accounts.proposal.as_deref(),
+        &RealmProposalAction::UpdateMasterPermissions {
+            master: master_pubkey,
+            granted,
+            revoked,
+        },
+        &approvers,
+    )?;
+
+    let master = &mut ctx.accounts.target_membership.master;
+    let previous_permissions = master.permissions;
+    let was_approver = master.is_approver();
+    master.permissions = (master.permissions | granted) & !revoked;
+    let master = master.clone();
+    if was_approver && !master.is_approver() {
+        realm.require_approvers_left(&master.pubkey, &approvers)?;
+    }
+    realm.updated_at = Clock::get()?.unix_timestamp;
+
+    if let Some(proposal) = &ctx.accounts.proposal {
+        emit!(RealmProposalEvent {
+            realm_pubkey: realm.key(),
+            proposal_pubkey: proposal.key(),
+            event_type: RealmProposalEventType::ProposalExecuted {},
+        });
+    }
+
+    emit!(RealmEvent {
+        realm_pubkey: realm.key(),
+        event_type: RealmEventType::RealmMasterPermissionsChanged {
+            master,
+            previous_permissions,
+        },
     });
 
     Ok(())
@@ -66,50 +296,92 @@ pub struct RemoveRealmMaster<'info> {
         mut,
         seeds = [REALM_SEED, realm_id.as_bytes()],
         bump,
-        realloc = crate::realm_space!(
-            realm.description,
-            realm.masters.len() - 1, // Decrement
-            realm.dimensions.len()
-        ),
-        realloc::payer = master,
-        realloc::zero = false,
     )]
     pub realm: Account<'info, Realm>,
 
+    #[account(
+        seeds = [MASTER_SEED, realm.key().as_ref(), master.key().as_ref()],
+        bump,
+        constraint = master_membership.master.has_permission(
+            RealmPermissions::MANAGE_MASTERS
+        ) @ ErrorCode::UnauthorizedRealmMaster
+    )]
+    pub master_membership: Account<'info, RealmMembership>,
+
+    #[account(
+        mut,
+        seeds = [MASTER_SEED, realm.key().as_ref(), master_pubkey.as_ref()],
+        bump,
+        constraint = !target_membership.master.is_owner() @ ErrorCode::CantRemoveRealmOwner,
+        close = master
+    )]
+    pub target_membership: Account<'info, RealmMembership>,
+
     #[account(
         mut,
-        constraint = realm.masters.iter().any(|m|
-            m.pubkey == master.key() && m.can_manage_realm_masters()
-        ) @ ErrorCode::UnauthorizedRealmMaster,
-        constraint = realm.masters.iter().any(|m|
-            m.pubkey == master_pubkey
-        ) @ ErrorCode::RealmMasterNotFound,
-        constraint = !realm.masters.contains(&RealmMaster {
-            pubkey: master_pubkey,
-            role: RealmMasterRole::Owner
-        }) @ ErrorCode::CantRemoveRealmOwner
+        seeds = [MASTER_SEED, realm.key().as_ref(), realm.owner.as_ref()],
+        bump
     )]
+    pub owner_membership: Account<'info, RealmMembership>,
+
+    #[account(mut)]
     pub master: Signer<'info>,
 
+    #[account(mut, has_one = realm, has_one = proposer, close = proposer)]
+    pub proposal: Option<Account<'info, RealmProposal>>,
+
+    #[account(mut)]
+    pub proposer: Option<SystemAccount<'info>>,
+
     pub system_program: Program<'info, System>,
 }
 
-pub fn remove_realm_master(
-    ctx: Context<RemoveRealmMaster>,
+// The dimensions owned by the removed master are expected in the remaining accounts and get
+// reassigned to the realm owner. The approvers' memberships are expected as well under an approval
+// policy, removing an approver requires enough of the others to be left for the threshold
+pub fn remove_realm_master<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RemoveRealmMaster<'info>>,
     _realm_id: String,
     master_pubkey: Pubkey,
 ) -> Result<()> {
     let realm = &mut ctx.accounts.realm;
+    let master = ctx.accounts.target_membership.master.clone();
+    let approvers = RealmMembership::load_approvers(&realm.key(), ctx.remaining_accounts);
 
-    let master_index = realm
-        .masters
-        .iter()
-        .position(|master| master.pubkey == master_pubkey)
-        .ok_or(ErrorCode::RealmMasterNotFound)?;
+    realm.require_approval(
+        ctx.accounts.proposal.as_deref(),
+        &RealmProposalAction::RemoveMaster {
+            master: master_pubkey,
+        },
+        &approvers,
+    )?;
+    if master.is_approver() {
+        realm.require_approvers_left(&master.pubkey, &approvers)?;
+    }
+    reassign_master_dimensions(
+        realm,
+        &ctx.accounts.target_membership,
+        &mut ctx.accounts.owner_membership,
+        ctx.remaining_accounts,
+    )?;
 
-    let master = realm.masters.swap_remove(master_index);
+    realm.masters_count = realm
+        .masters_count
+        .checked_sub(1)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    if realm.pending_owner == Some(master.pubkey) {
+        realm.pending_owner = None;
+    }
     realm.updated_at = Clock::get()?.unix_timestamp;
 
+    if let Some(proposal) = &ctx.accounts.proposal {
+        emit!(RealmProposalEvent {
+            realm_pubkey: realm.key(),
+            proposal_pubkey: proposal.key(),
+            event_type: RealmProposalEventType::ProposalExecuted {},
+        });
+    }
+
     emit!(RealmEvent {
         realm_pubkey: realm.key(),
         event_type: RealmEventType::RealmMasterRemoved { master },
@@ -120,7 +392,7 @@ pub fn remove_realm_master(
 
 #[derive(Accounts)]
 #[instruction(realm_id: String)]
-pub struct TransferRealmOwnership<'info> {
+pub struct ResignRealmMaster<'info> {
     #[account(
         mut,
         seeds = [REALM_SEED, realm_id.as_bytes()],
@@ -130,49 +402,613 @@ pub struct TransferRealmOwnership<'info> {
 
     #[account(
         mut,
-        constraint = realm.masters.iter().any(|m|
-            m.pubkey == master.key() && m.can_manage_realm_masters()
-        ) @ ErrorCode::UnauthorizedRealmMaster
+        seeds = [MASTER_SEED, realm.key().as_ref(), master.key().as_ref()],
+        bump,
+        constraint = !master_membership.master.is_owner() @ ErrorCode::CantRemoveRealmOwner,
+        close = master
+    )]
+    pub master_membership: Account<'info, RealmMembership>,
+
+    #[account(
+        mut,
+        seeds = [MASTER_SEED, realm.key().as_ref(), realm.owner.as_ref()],
+        bump
     )]
+    pub owner_membership: Account<'info, RealmMembership>,
+
+    #[account(mut)]
     pub master: Signer<'info>,
 
     pub system_program: Program<'info, System>,
 }
 
-pub fn transfer_realm_ownership(
-    ctx: Context<TransferRealmOwnership>,
+// The dimensions owned by the resigning master are expected in the remaining accounts and get
+// reassigned to the realm owner, the membership rent goes back to the master.
+// Resigning approvers also prove enough of the other approvers are left for the threshold
+pub fn resign_realm_master<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ResignRealmMaster<'info>>,
     _realm_id: String,
-    new_owner_pubkey: Pubkey,
 ) -> Result<()> {
     let realm = &mut ctx.accounts.realm;
 
-    let old_owner_index = realm
-        .masters
-        .iter()
-        .position(|master| master.pubkey == *ctx.accounts.master.key)
-        .ok_or(ErrorCode::UnauthorizedRealmMaster)?;
+    reassign_master_dimensions(
+        realm,
+        &ctx.accounts.master_membership,
+        &mut ctx.accounts.owner_membership,
+        ctx.remaining_accounts,
+    )?;
+
+    let master = ctx.accounts.master_membership.master.clone();
+    if master.is_approver() {
+        realm.require_approvers_left(
+            &master.pubkey,
+            &RealmMembership::load_approvers(&realm.key(), ctx.remaining_accounts),
+        )?;
+    }
+
+    realm.masters_count = realm
+        .masters_count
+        .checked_sub(1)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    if realm.pending_owner == Some(master.pubkey) {
+        realm.pending_owner = None;
+    }
+    realm.updated_at = Clock::get()?.unix_timestamp;
+
+    emit!(RealmEvent {
+        realm_pubkey: realm.key(),
+        event_type: RealmEventType::RealmMasterResigned { master },
+    });
+
+    Ok(())
+}
+
+// Hands the dimensions of a leaving master over to the realm owner,
+// every one of them counted by the membership has to be provided
+fn reassign_master_dimensions<'info>(
+    realm: &Account<'info, Realm>,
+    membership: &RealmMembership,
+    owner_membership: &mut Account<'info, RealmMembership>,
+    remaining_accounts: &'info [AccountInfo<'info>],
+) -> Result<()> {
+    let master_pubkey = membership.master.pubkey;
+    let mut reassigned: u32 = 0;
+
+    for account_info in remaining_accounts.iter() {
+        if *account_info.owner != crate::ID {
+            continue;
+        }
+
+        // Dimensions already reassigned no longer match, so duplicates are skipped
+        let Ok(mut dimension) = Account::<RealmDimension>::try_from(account_info) else {
+            continue;
+        };
+        if dimension.realm != realm.key() || dimension.owner != master_pubkey {
+            continue;
+        }
+
+        dimension.owner = realm.owner;
+        dimension.exit(&crate::ID)?;
+        reassigned = reassigned
+            .checked_add(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
 
-    let new_owner_index = realm
+        emit!(RealmDimensionEvent {
+            realm_pubkey: realm.key(),
+            dimension_pubkey: dimension.key(),
+            event_type: RealmDimensionEventType::DimensionOwnerChanged {
+                previous_owner: master_pubkey,
+                owner: realm.owner,
+            },
+        });
+    }
+    require!(
+        reassigned == membership.dimensions_count,
+        ErrorCode::DimensionNotProvided
+    );
+
+    owner_membership.dimensions_count = owner_membership
+        .dimensions_count
+        .checked_add(reassigned)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(realm_id: String)]
+pub struct MigrateRealmMasters<'info> {
+    /// CHECK: Deserialized manually, legacy realms don't match the current layout
+    #[account(
+        mut,
+        seeds = [REALM_SEED, realm_id.as_bytes()],
+        bump,
+        owner = crate::ID
+    )]
+    pub realm: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub master: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Membership PDAs of every legacy master and all the realm dimensions are expected in the
+// remaining accounts, the master pays for them and receives the rent freed from the realm
+pub fn migrate_realm_masters<'info>(
+    ctx: Context<'_, '_, 'info, 'info, MigrateRealmMasters<'info>>,
+    _realm_id: String,
+) -> Result<()> {
+    let realm_info = ctx.accounts.realm.to_account_info();
+    let master_info = ctx.accounts.master.to_account_info();
+
+    let legacy = {
+        let data = realm_info.try_borrow_data()?;
+        require!(
+            data.len() >= 8 && data[..8] == Realm::DISCRIMINATOR,
+            anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch
+        );
+
+        // Both layouts start the same way up to the masters, which migrated realms keep empty
+        let mut prefix = &data[8..];
+        RealmDescription::deserialize(&mut prefix)?;
+        <(i64, i64)>::deserialize(&mut prefix)?;
+        require!(
+            u32::deserialize(&mut prefix)? > 0,
+            ErrorCode::RealmAlreadyMigrated
+        );
+
+        LegacyRealm::deserialize(&mut &data[8..])?
+    };
+
+    // Legacy masters only had a role, their permissions are derived from it
+    let masters: Vec<RealmMaster> = legacy
         .masters
+        .into_iter()
+        .map(|master| RealmMaster::new(master.pubkey, master.role))
+        .collect();
+
+    require!(
+        masters
+            .iter()
+            .any(|m| m.pubkey == master_info.key()
+                && m.has_permission(RealmPermissions::MANAGE_MASTERS)),
+        ErrorCode::UnauthorizedRealmMaster
+    );
+
+    let owner = masters
         .iter()
-        .position(|master| master.pubkey == new_owner_pubkey)
+        .find(|master| master.is_owner())
+        .map(|master| master.pubkey)
         .ok_or(ErrorCode::RealmMasterNotFound)?;
 
-    // Current roles should be checked via constraints
-    realm.masters[old_owner_index].role = RealmMasterRole::Admin;
-    realm.masters[new_owner_index].role = RealmMasterRole::Owner;
+    let now = Clock::get()?.unix_timestamp;
+
+    // Legacy dimensions can't be loaded by the current layout, so they are migrated as well
+    let mut dimension_owners: Vec<Pubkey> = Vec::with_capacity(legacy.dimensions.len());
+    for dimension_pubkey in legacy.dimensions.iter() {
+        let dimension_info = ctx
+            .remaining_accounts
+            .iter()
+            .find(|account_info| account_info.key == dimension_pubkey)
+            .ok_or(ErrorCode::DimensionNotProvided)?;
+
+        dimension_owners.push(migrate_dimension(
+            dimension_info,
+            realm_info.key,
+            &master_info,
+            &ctx.accounts.system_program,
+        )?);
+    }
+
+    for master in masters.iter() {
+        let seeds = [MASTER_SEED, realm_info.key.as_ref(), master.pubkey.as_ref()];
+        let (membership_pubkey, bump) = Pubkey::find_program_address(&seeds, &crate::ID);
+
+        let membership_info = ctx
+            .remaining_accounts
+            .iter()
+            .find(|account_info| *account_info.key == membership_pubkey)
+            .ok_or(ErrorCode::RealmMasterNotProvided)?;
+
+        create_membership_account(
+            membership_info,
+            &master_info,
+            &ctx.accounts.system_program,
+            &[
+                MASTER_SEED,
+                realm_info.key.as_ref(),
+                master.pubkey.as_ref(),
+                &[bump],
+            ],
+        )?;
+
+        let membership = RealmMembership {
+            realm: realm_info.key(),
+            master: master.clone(),
+            dimensions_count: dimension_owners
+                .iter()
+                .filter(|owner| **owner == master.pubkey)
+                .count() as u32,
+            created_at: now,
+        };
+        membership.try_serialize(&mut &mut membership_info.try_borrow_mut_data()?[..])?;
+    }
+
+    let realm = Realm {
+        description: legacy.description,
+        created_at: legacy.created_at,
+        updated_at: now,
+        masters: vec![],
+        pending_owner: None,
+        approval_threshold: 0,
+        timelock: 0,
+        dimensions: legacy.dimensions,
+        starting_dimension: legacy.starting_dimension,
+        starting_position: legacy.starting_position,
+        owner,
+        masters_count: masters.len() as u32,
+        status: RealmStatus::Active,
+        visibility: RealmVisibility::Public,
+        spawn_points_count: 0,
+        journeys_count: 0,
+    };
+
+    let space = crate::realm_space!(realm.description, 0, realm.dimensions.len());
+    resize_account(
+        &realm_info,
+        &master_info,
+        &ctx.accounts.system_program,
+        space,
+    )?;
+    realm.try_serialize(&mut &mut realm_info.try_borrow_mut_data()?[..])?;
+
+    emit!(RealmEvent {
+        realm_pubkey: realm_info.key(),
+        event_type: RealmEventType::RealmMastersMigrated {
+            masters_count: realm.masters_count,
+        },
+    });
+
+    Ok(())
+}
+
+fn migrate_dimension<'info>(
+    dimension_info: &AccountInfo<'info>,
+    realm_pubkey: &Pubkey,
+    payer: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<Pubkey> {
+    require_keys_eq!(
+        *dimension_info.owner,
+        crate::ID,
+        anchor_lang::error::ErrorCode::AccountOwnedByWrongProgram
+    );
+
+    let legacy = {
+        let data = dimension_info.try_borrow_data()?;
+        require!(
+            data.len() >= 8 && data[..8] == RealmDimension::DISCRIMINATOR,
+            anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch
+        );
+        LegacyRealmDimension::deserialize(&mut &data[8..])?
+    };
+    require_keys_eq!(
+        legacy.realm,
+        *realm_pubkey,
+        anchor_lang::error::ErrorCode::ConstraintHasOne
+    );
+
+    // Legacy areas were never checked for overlaps, ids follow their order
+    let areas: Vec<RealmDimensionArea> = legacy
+        .areas
+        .into_iter()
+        .zip(0..)
+        .map(|(area, id)| RealmDimensionArea {
+            id,
+            name: area.name,
+            area: area.area,
+            tileset: area.tileset,
+            tilemap: area.tilemap,
+        })
+        .collect();
+    let dimension = RealmDimension {
+        realm: legacy.realm,
+        owner: legacy.owner,
+        name: legacy.name,
+        areas,
+        allow_overlapping_areas: true,
+        has_walkability_grid: false,
+        portals_count: 0,
+        incoming_portals_count: 0,
+    };
+
+    let space = crate::realm_dimension_space!(dimension.name, &dimension.areas);
+    resize_account(dimension_info, payer, system_program, space)?;
+    dimension.try_serialize(&mut &mut dimension_info.try_borrow_mut_data()?[..])?;
+    Ok(dimension.owner)
+}
+
+// Resizes a migrated account to its new layout, keeping it rent exempt
+fn resize_account<'info>(
+    account_info: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    space: usize,
+) -> Result<()> {
+    let rent = Rent::get()?.minimum_balance(space);
+    let lamports = account_info.lamports();
+    if rent > lamports {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                system_program::Transfer {
+                    from: payer.clone(),
+                    to: account_info.clone(),
+                },
+            ),
+            rent - lamports,
+        )?;
+    } else {
+        **account_info.try_borrow_mut_lamports()? -= lamports - rent;
+        **payer.try_borrow_mut_lamports()? += lamports - rent;
+    }
+    account_info.realloc(space, false)?;
+    Ok(())
+}
+
+// Creates a membership PDA the way Anchor's init does, so lamports sent to the
+// address beforehand can't block the migration
+fn create_membership_account<'info>(
+    membership_info: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    signer_seeds: &[&[u8]],
+) -> Result<()> {
+    let space = 8 + RealmMembership::INIT_SPACE;
+    let rent = Rent::get()?.minimum_balance(space);
+    let lamports = membership_info.lamports();
+    if rent > lamports {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                system_program::Transfer {
+                    from: payer.clone(),
+                    to: membership_info.clone(),
+                },
+            ),
+            rent - lamports,
+        )?;
+    }
+
+    system_program::allocate(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            system_program::Allocate {
+                account_to_allocate: membership_info.clone(),
+            },
+            &[signer_seeds],
+        ),
+        space as u64,
+    )?;
+
+    system_program::assign(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            system_program::Assign {
+                account_to_assign: membership_info.clone(),
+            },
+            &[signer_seeds],
+        ),
+        &crate::ID,
+    )?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(realm_id: String, new_owner_pubkey: Pubkey)]
+pub struct ProposeRealmOwner<'info> {
+    #[account(
+        mut,
+        seeds = [REALM_SEED, realm_id.as_bytes()],
+        bump,
+        constraint = realm.is_active() @ ErrorCode::RealmClosing,
+    )]
+    pub realm: Account<'info, Realm>,
+
+    #[account(
+        seeds = [MASTER_SEED, realm.key().as_ref(), master.key().as_ref()],
+        bump,
+        constraint = master_membership.master.is_owner() @ ErrorCode::UnauthorizedRealmMaster
+    )]
+    pub master_membership: Account<'info, RealmMembership>,
+
+    // Only existing masters can become owners
+    #[account(
+        seeds = [MASTER_SEED, realm.key().as_ref(), new_owner_pubkey.as_ref()],
+        bump,
+    )]
+    pub new_owner_membership: Account<'info, RealmMembership>,
+
+    #[account(mut)]
+    pub master: Signer<'info>,
+
+    #[account(mut, has_one = realm, has_one = proposer, close = proposer)]
+    pub proposal: Option<Account<'info, RealmProposal>>,
+
+    #[account(mut)]
+    pub proposer: Option<SystemAccount<'info>>,
+
+    #[account(mut, has_one = realm, close = master)]
+    pub queued_action: Option<Account<'info, RealmQueuedAction>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// The approvers' memberships are expected in the remaining accounts under an approval policy
+pub fn propose_realm_owner(
+    ctx: Context<ProposeRealmOwner>,
+    _realm_id: String,
+    new_owner_pubkey: Pubkey,
+) -> Result<()> {
+    let realm = &mut ctx.accounts.realm;
+    let now = Clock::get()?.unix_timestamp;
+    let action = RealmTimelockAction::TransferOwnership {
+        new_owner: new_owner_pubkey,
+    };
+
+    realm.require_approval(
+        ctx.accounts.proposal.as_deref(),
+        &RealmProposalAction::TransferOwnership {
+            new_owner: new_owner_pubkey,
+        },
+        &RealmMembership::load_approvers(&realm.key(), ctx.remaining_accounts),
+    )?;
+    realm.require_timelock(ctx.accounts.queued_action.as_deref(), &action, now)?;
+
+    if let Some(proposal) = &ctx.accounts.proposal {
+        emit!(RealmProposalEvent {
+            realm_pubkey: realm.key(),
+            proposal_pubkey: proposal.key(),
+            event_type: RealmProposalEventType::ProposalExecuted {},
+        });
+    }
+
+    if let Some(queued_action) = &ctx.accounts.queued_action {
+        emit!(RealmEvent {
+            realm_pubkey: realm.key(),
+            event_type: RealmEventType::RealmActionExecuted {
+                queued_action: queued_action.key(),
+                action,
+            },
+        });
+    }
+
+    let owner = ctx.accounts.master_membership.master.clone();
+
+    realm.pending_owner = Some(new_owner_pubkey);
+    realm.updated_at = now;
+
+    emit!(RealmEvent {
+        realm_pubkey: realm.key(),
+        event_type: RealmEventType::RealmOwnershipProposed {
+            owner,
+            pending_owner: new_owner_pubkey,
+        },
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(realm_id: String)]
+pub struct AcceptRealmOwnership<'info> {
+    #[account(
+        mut,
+        seeds = [REALM_SEED, realm_id.as_bytes()],
+        bump,
+    )]
+    pub realm: Account<'info, Realm>,
+
+    #[account(
+        mut,
+        seeds = [MASTER_SEED, realm.key().as_ref(), realm.owner.as_ref()],
+        bump,
+    )]
+    pub owner_membership: Account<'info, RealmMembership>,
+
+    #[account(
+        mut,
+        seeds = [MASTER_SEED, realm.key().as_ref(), new_owner.key().as_ref()],
+        bump,
+    )]
+    pub new_owner_membership: Account<'info, RealmMembership>,
+
+    #[account(
+        mut,
+        constraint = realm.pending_owner.is_some() @ ErrorCode::NoPendingRealmOwner,
+        constraint = realm.pending_owner == Some(new_owner.key()) @ ErrorCode::NotPendingRealmOwner
+    )]
+    pub new_owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn accept_realm_ownership(ctx: Context<AcceptRealmOwnership>, _realm_id: String) -> Result<()> {
+    let realm = &mut ctx.accounts.realm;
+
+    ctx.accounts
+        .owner_membership
+        .master
+        .set_role(RealmMasterRole::Admin);
+    ctx.accounts
+        .new_owner_membership
+        .master
+        .set_role(RealmMasterRole::Owner);
+
+    realm.owner = *ctx.accounts.new_owner.key;
+    realm.pending_owner = None;
     realm.updated_at = Clock::get()?.unix_timestamp;
 
-    let old_owner = realm.masters[old_owner_index].clone();
-    let new_owner = realm.masters[new_owner_index].clone();
+    let old_owner = ctx.accounts.owner_membership.master.clone();
+    let new_owner = ctx.accounts.new_owner_membership.master.clone();
 
     emit!(RealmEvent {
         realm_pubkey: realm.key(),
         event_type: RealmEventType::RealmOwnershipTransferred {
-            old_owner: old_owner,
-            new_owner: new_owner,
+            old_owner,
+            new_owner,
         },
     });
 
     Ok(())
 }
+
+#[derive(Accounts)]
+#[instruction(realm_id: String)]
+pub struct CancelRealmOwnershipProposal<'info> {
+    #[account(
+        mut,
+        seeds = [REALM_SEED, realm_id.as_bytes()],
+        bump,
+    )]
+    pub realm: Account<'info, Realm>,
+
+    #[account(
+        seeds = [MASTER_SEED, realm.key().as_ref(), master.key().as_ref()],
+        bump,
+    )]
+    pub master_membership: Account<'info, RealmMembership>,
+
+    // Either the owner withdraws the proposal or the pending owner declines it
+    #[account(
+        mut,
+        constraint = realm.pending_owner.is_some() @ ErrorCode::NoPendingRealmOwner,
+        constraint = realm.pending_owner == Some(master.key())
+            || master_membership.master.is_owner()
+            @ ErrorCode::UnauthorizedRealmMaster
+    )]
+    pub master: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn cancel_realm_ownership_proposal(
+    ctx: Context<CancelRealmOwnershipProposal>,
+    _realm_id: String,
+) -> Result<()> {
+    let realm = &mut ctx.accounts.realm;
+
+    let pending_owner = realm
+        .pending_owner
+        .take()
+        .ok_or(ErrorCode::NoPendingRealmOwner)?;
+    realm.updated_at = Clock::get()?.unix_timestamp;
+
+    emit!(RealmEvent {
+        realm_pubkey: realm.key(),
+        event_type: RealmEventType::RealmOwnershipProposalCancelled { pending_owner },
+    });
+
+    Ok(())
+}
@@ -2,12 +2,24 @@
 
 pub mod dimensions;
 pub mod heroes;
+pub mod items;
 pub mod journeys;
 pub mod masters;
+pub mod portals;
+pub mod proposals;
 pub mod realms;
+pub mod spawn_points;
+pub mod timelocks;
+pub mod walkability;
 
 pub use dimensions::*;
 pub use heroes::*;
+pub use items::*;
 pub use journeys::*;
 pub use masters::*;
+pub use portals::*;
+pub use proposals::*;
 pub use realms::*;
+pub use spawn_points::*;
+pub use timelocks::*;
+pub use walkability::*;
@@ -0,0 +1,285 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+#[instruction(realm_id: String, dimension_id: String, portal_id: String)]
+pub struct AddRealmPortal<'info> {
+    #[account(
+        seeds = [REALM_SEED, realm_id.as_bytes()],
+        bump,
+        constraint = realm.is_active() @ ErrorCode::RealmClosing
+    )]
+    pub realm: Account<'info, Realm>,
+
+    // Saved before the dimension, which wins for portals within the same dimension
+    #[account(mut, has_one = realm)]
+    pub target_dimension: Account<'info, RealmDimension>,
+
+    #[account(
+        mut,
+        seeds = [DIMENSION_SEED, realm_id.as_bytes(), dimension_id.as_bytes()],
+        bump,
+    )]
+    pub dimension: Account<'info, RealmDimension>,
+
+    #[account(
+        init,
+        payer = master,
+        space = 8 + RealmPortal::INIT_SPACE,
+        seeds = [PORTAL_SEED, realm_id.as_bytes(), dimension_id.as_bytes(), portal_id.as_bytes()],
+        bump
+    )]
+    pub portal: Account<'info, RealmPortal>,
+
+    #[account(
+        seeds = [MASTER_SEED, realm.key().as_ref(), master.key().as_ref()],
+        bump,
+        constraint = master_membership.master.can_manage_realm_dimension(
+            &dimension
+        ) @ ErrorCode::UnauthorizedRealmMaster
+    )]
+    pub master_membership: Account<'info, RealmMembership>,
+
+    #[account(mut)]
+    pub master: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn add_realm_portal(
+    ctx: Context<AddRealmPortal>,
+    _realm_id: String,
+    _dimension_id: String,
+    _portal_id: String,
+    area: Rect,
+    target_position: Position,
+) -> Result<()> {
+    area.validate()?;
+    require!(
+        ctx.accounts.dimension.encloses(&area),
+        ErrorCode::PortalOutsideDimension
+    );
+
+    let target_dimension = &mut ctx.accounts.target_dimension;
+    require!(
+        target_dimension.contains(&target_position),
+        ErrorCode::PositionOutsideDimension
+    );
+    if target_dimension.key() != ctx.accounts.dimension.key() {
+        target_dimension.incoming_portals_count = target_dimension
+            .incoming_portals_count
+            .checked_add(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+    }
+
+    let dimension = &mut ctx.accounts.dimension;
+    dimension.portals_count = dimension
+        .portals_count
+        .checked_add(1)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+    let portal = &mut ctx.accounts.portal;
+    portal.realm = ctx.accounts.realm.key();
+    portal.dimension = ctx.accounts.dimension.key();
+    portal.area = area;
+    portal.target_dimension = target_dimension.key();
+    portal.target_position = target_position;
+    portal.creator = ctx.accounts.master.key();
+
+    emit!(RealmDimensionEvent {
+        realm_pubkey: portal.realm,
+        dimension_pubkey: portal.dimension,
+        event_type: RealmDimensionEventType::PortalAdded {
+            portal_pubkey: portal.key(),
+            target_dimension: portal.target_dimension,
+        },
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(realm_id: String, dimension_id: String, portal_id: String)]
+pub struct RemoveRealmPortal<'info> {
+    #[account(
+        seeds = [REALM_SEED, realm_id.as_bytes()],
+        bump,
+        constraint = realm.is_active() @ ErrorCode::RealmClosing
+    )]
+    pub realm: Account<'info, Realm>,
+
+    // Saved before the dimension, which wins for portals within the same dimension
+    #[account(mut)]
+    pub target_dimension: Account<'info, RealmDimension>,
+
+    #[account(
+        mut,
+        seeds = [DIMENSION_SEED, realm_id.as_bytes(), dimension_id.as_bytes()],
+        bump,
+    )]
+    pub dimension: Account<'info, RealmDimension>,
+
+    #[account(
+        mut,
+        seeds = [PORTAL_SEED, realm_id.as_bytes(), dimension_id.as_bytes(), portal_id.as_bytes()],
+        bump,
+        has_one = target_dimension,
+        has_one = creator,
+        close = creator
+    )]
+    pub portal: Account<'info, RealmPortal>,
+
+    #[account(mut)]
+    pub creator: SystemAccount<'info>,
+
+    #[account(
+        seeds = [MASTER_SEED, realm.key().as_ref(), master.key().as_ref()],
+        bump,
+        constraint = master_membership.master.can_manage_realm_dimension(
+            &dimension
+        ) @ ErrorCode::UnauthorizedRealmMaster
+    )]
+    pub master_membership: Account<'info, RealmMembership>,
+
+    #[account(mut)]
+    pub master: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn remove_realm_portal(
+    ctx: Context<RemoveRealmPortal>,
+    _realm_id: String,
+    _dimension_id: String,
+    _portal_id: String,
+) -> Result<()> {
+    let target_dimension = &mut ctx.accounts.target_dimension;
+    if target_dimension.key() != ctx.accounts.dimension.key() {
+        target_dimension.incoming_portals_count = target_dimension
+            .incoming_portals_count
+            .checked_sub(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+    }
+
+    let dimension = &mut ctx.accounts.dimension;
+    dimension.portals_count = dimension
+        .portals_count
+        .checked_sub(1)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+    emit!(RealmDimensionEvent {
+        realm_pubkey: ctx.accounts.realm.key(),
+        dimension_pubkey: ctx.accounts.dimension.key(),
+        event_type: RealmDimensionEventType::PortalRemoved {
+            portal_pubkey: ctx.accounts.portal.key(),
+        },
+    });
+
+    Ok(())
+}
+
+// Closes the portals leading out of the dimension, refunding the rent to their creators
+// who are expected in the remaining accounts unless they are the master
+pub fn close_dimension_portals<'info>(
+    dimension: &Account<'info, RealmDimension>,
+    master: &Signer<'info>,
+    remaining_accounts: &'info [AccountInfo<'info>],
+) -> Result<()> {
+    let mut closed_portals = 0;
+    for account_info in remaining_accounts.iter() {
+        // Closed accounts are handed back to the system program, so duplicates are skipped
+        if *account_info.owner != crate::ID {
+            continue;
+        }
+
+        let Ok(portal) = Account::<RealmPortal>::try_from(account_info) else {
+            continue;
+        };
+        if portal.dimension != dimension.key() {
+            continue;
+        }
+
+        close_portal(portal, master, remaining_accounts)?;
+        closed_portals += 1;
+    }
+
+    require!(
+        closed_portals == dimension.portals_count,
+        ErrorCode::PortalNotProvided
+    );
+    Ok(())
+}
+
+// Closes the portals leading into the dimension from other ones, their source dimensions
+// and creators are expected in the remaining accounts as well
+pub fn close_incoming_portals<'info>(
+    dimension: &Account<'info, RealmDimension>,
+    master: &Signer<'info>,
+    remaining_accounts: &'info [AccountInfo<'info>],
+) -> Result<()> {
+    let mut closed_portals = 0;
+    for account_info in remaining_accounts.iter() {
+        if *account_info.owner != crate::ID {
+            continue;
+        }
+
+        let Ok(portal) = Account::<RealmPortal>::try_from(account_info) else {
+            continue;
+        };
+        if portal.target_dimension != dimension.key() || portal.dimension == dimension.key() {
+            continue;
+        }
+
+        let source_info = remaining_accounts
+            .iter()
+            .find(|account_info| *account_info.key == portal.dimension)
+            .ok_or(ErrorCode::DimensionNotProvided)?;
+        let mut source = Account::<RealmDimension>::try_from(source_info)?;
+        source.portals_count = source
+            .portals_count
+            .checked_sub(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        source.exit(&crate::ID)?;
+
+        close_portal(portal, master, remaining_accounts)?;
+        closed_portals += 1;
+    }
+
+    require!(
+        closed_portals == dimension.incoming_portals_count,
+        ErrorCode::PortalNotProvided
+    );
+    Ok(())
+}
+
+// Refunds the portal rent to its creator, expected in the remaining accounts unless it is the master
+fn close_portal<'info>(
+    portal: Account<'info, RealmPortal>,
+    master: &Signer<'info>,
+    remaining_accounts: &'info [AccountInfo<'info>],
+) -> Result<()> {
+    let creator_info = if portal.creator == master.key() {
+        master.to_account_info()
+    } else {
+        remaining_accounts
+            .iter()
+            .find(|account_info| *account_info.key == portal.creator)
+            .ok_or(ErrorCode::PortalCreatorNotProvided)?
+            .clone()
+    };
+
+    let (realm_pubkey, dimension_pubkey, portal_pubkey) =
+        (portal.realm, portal.dimension, portal.key());
+    portal.close(creator_info)?;
+
+    emit!(RealmDimensionEvent {
+        realm_pubkey,
+        dimension_pubkey,
+        event_type: RealmDimensionEventType::PortalRemoved { portal_pubkey },
+    });
+    Ok(())
+}
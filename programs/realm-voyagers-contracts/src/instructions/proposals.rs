@@ -0,0 +1,233 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+#[instruction(realm_id: String, proposal_id: String)]
+pub struct CreateRealmProposal<'info> {
+    #[account(
+        seeds = [REALM_SEED, realm_id.as_bytes()],
+        bump,
+        constraint = realm.is_active() @ ErrorCode::RealmClosing
+    )]
+    pub realm: Account<'info, Realm>,
+
+    #[account(
+        init,
+        payer = master,
+        space = 8 + RealmProposal::INIT_SPACE,
+        seeds = [PROPOSAL_SEED, realm_id.as_bytes(), proposal_id.as_bytes()],
+        bump
+    )]
+    pub proposal: Account<'info, RealmProposal>,
+
+    #[account(
+        seeds = [MASTER_SEED, realm.key().as_ref(), master.key().as_ref()],
+        bump,
+        constraint = master_membership.master.is_approver() @ ErrorCode::UnauthorizedRealmMaster
+    )]
+    pub master_membership: Account<'info, RealmMembership>,
+
+    #[account(mut)]
+    pub master: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_realm_proposal(
+    ctx: Context<CreateRealmProposal>,
+    _realm_id: String,
+    _proposal_id: String,
+    action: RealmProposalAction,
+) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.realm = ctx.accounts.realm.key();
+    proposal.proposer = *ctx.accounts.master.key;
+    proposal.action = action.clone();
+    proposal.approvals = vec![proposal.proposer]; // Proposer approves by default
+    proposal.rejections = vec![];
+    proposal.created_at = Clock::get()?.unix_timestamp;
+
+    emit!(RealmProposalEvent {
+        realm_pubkey: proposal.realm,
+        proposal_pubkey: proposal.key(),
+        event_type: RealmProposalEventType::ProposalCreated { action },
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(realm_id: String, proposal_id: String)]
+pub struct VoteRealmProposal<'info> {
+    #[account(seeds = [REALM_SEED, realm_id.as_bytes()], bump)]
+    pub realm: Account<'info, Realm>,
+
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, realm_id.as_bytes(), proposal_id.as_bytes()],
+        bump,
+        has_one = realm
+    )]
+    pub proposal: Account<'info, RealmProposal>,
+
+    #[account(
+        seeds = [MASTER_SEED, realm.key().as_ref(), master.key().as_ref()],
+        bump,
+        constraint = master_membership.master.is_approver() @ ErrorCode::UnauthorizedRealmMaster
+    )]
+    pub master_membership: Account<'info, RealmMembership>,
+
+    #[account(
+        constraint = !proposal.has_voted(&master.key()) @ ErrorCode::AlreadyVoted
+    )]
+    pub master: Signer<'info>,
+}
+
+pub fn approve_realm_proposal(
+    ctx: Context<VoteRealmProposal>,
+    _realm_id: String,
+    _proposal_id: String,
+) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    require!(
+        proposal.approvals.len() < MAX_PROPOSAL_VOTES,
+        ErrorCode::TooManyVotes
+    );
+    proposal.approvals.push(*ctx.accounts.master.key);
+
+    emit!(RealmProposalEvent {
+        realm_pubkey: proposal.realm,
+        proposal_pubkey: proposal.key(),
+        event_type: RealmProposalEventType::ProposalApproved {
+            master: *ctx.accounts.master.key,
+            approvals: proposal.approvals.len() as u8,
+        },
+    });
+
+    Ok(())
+}
+
+pub fn reject_realm_proposal(
+    ctx: Context<VoteRealmProposal>,
+    _realm_id: String,
+    _proposal_id: String,
+) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    require!(
+        proposal.rejections.len() < MAX_PROPOSAL_VOTES,
+        ErrorCode::TooManyVotes
+    );
+    proposal.rejections.push(*ctx.accounts.master.key);
+
+    emit!(RealmProposalEvent {
+        realm_pubkey: proposal.realm,
+        proposal_pubkey: proposal.key(),
+        event_type: RealmProposalEventType::ProposalRejected {
+            master: *ctx.accounts.master.key,
+            rejections: proposal.rejections.len() as u8,
+        },
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(realm_id: String, proposal_id: String)]
+pub struct CancelRealmProposal<'info> {
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, realm_id.as_bytes(), proposal_id.as_bytes()],
+        bump,
+        has_one = proposer @ ErrorCode::UnauthorizedRealmMaster,
+        close = proposer
+    )]
+    pub proposal: Account<'info, RealmProposal>,
+
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+}
+
+pub fn cancel_realm_proposal(
+    ctx: Context<CancelRealmProposal>,
+    _realm_id: String,
+    _proposal_id: String,
+) -> Result<()> {
+    let proposal = &ctx.accounts.proposal;
+
+    emit!(RealmProposalEvent {
+        realm_pubkey: proposal.realm,
+        proposal_pubkey: proposal.key(),
+        event_type: RealmProposalEventType::ProposalCancelled {},
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(realm_id: String, threshold: u8)]
+pub struct SetRealmApprovalThreshold<'info> {
+    #[account(
+        mut,
+        seeds = [REALM_SEED, realm_id.as_bytes()],
+        bump,
+        constraint = realm.is_active() @ ErrorCode::RealmClosing,
+    )]
+    pub realm: Account<'info, Realm>,
+
+    #[account(
+        seeds = [MASTER_SEED, realm.key().as_ref(), master.key().as_ref()],
+        bump,
+        constraint = master_membership.master.has_permission(
+            RealmPermissions::MANAGE_MASTERS
+        ) @ ErrorCode::UnauthorizedRealmMaster
+    )]
+    pub master_membership: Account<'info, RealmMembership>,
+
+    #[account(mut)]
+    pub master: Signer<'info>,
+
+    #[account(mut, has_one = realm, has_one = proposer, close = proposer)]
+    pub proposal: Option<Account<'info, RealmProposal>>,
+
+    #[account(mut)]
+    pub proposer: Option<SystemAccount<'info>>,
+}
+
+// Memberships of the approvers backing the threshold are expected in the remaining accounts
+pub fn set_realm_approval_threshold<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SetRealmApprovalThreshold<'info>>,
+    _realm_id: String,
+    threshold: u8,
+) -> Result<()> {
+    let realm = &mut ctx.accounts.realm;
+    let approvers = RealmMembership::load_approvers(&realm.key(), ctx.remaining_accounts);
+
+    realm.require_approval(
+        ctx.accounts.proposal.as_deref(),
+        &RealmProposalAction::SetApprovalThreshold { threshold },
+        &approvers,
+    )?;
+    realm.validate_approval_threshold(threshold, approvers.len())?;
+
+    realm.approval_threshold = threshold;
+    realm.updated_at = Clock::get()?.unix_timestamp;
+
+    if let Some(proposal) = &ctx.accounts.proposal {
+        emit!(RealmProposalEvent {
+            realm_pubkey: realm.key(),
+            proposal_pubkey: proposal.key(),
+            event_type: RealmProposalEventType::ProposalExecuted {},
+        });
+    }
+
+    emit!(RealmEvent {
+        realm_pubkey: realm.key(),
+        event_type: RealmEventType::RealmApprovalThresholdChanged { threshold },
+    });
+
+    Ok(())
+}
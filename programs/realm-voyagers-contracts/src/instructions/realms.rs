@@ -3,6 +3,8 @@ use anchor_lang::prelude::*;
 use crate::constants::*;
 use crate::errors::ErrorCode;
 use crate::events::*;
+use crate::instructions::portals::close_dimension_portals;
+use crate::instructions::walkability::close_dimension_walkability_grid;
 use crate::state::*;
 
 #[derive(Accounts)]
@@ -11,12 +13,21 @@ pub struct CreateRealm<'info> {
     #[account(
         init,
         payer = master,
-        space = crate::realm_space!(description, 1, 0),
+        space = crate::realm_space!(description, 0, 0),
         seeds = [REALM_SEED, realm_id.as_bytes()],
         bump
     )]
     pub realm: Account<'info, Realm>,
 
+    #[account(
+        init,
+        payer = master,
+        space = 8 + RealmMembership::INIT_SPACE,
+        seeds = [MASTER_SEED, realm.key().as_ref(), master.key().as_ref()],
+        bump
+    )]
+    pub master_membership: Account<'info, RealmMembership>,
+
     #[account(mut)]
     pub master: Signer<'info>,
 
@@ -34,10 +45,13 @@ pub fn create_realm(
     realm.description = description.clone();
     realm.created_at = Clock::get()?.unix_timestamp;
     realm.updated_at = realm.created_at;
-    realm.masters.push(RealmMaster {
-        pubkey: *ctx.accounts.master.key,
-        role: RealmMasterRole::Owner,
-    });
+    realm.owner = *ctx.accounts.master.key;
+    realm.masters_count = 1;
+
+    let membership = &mut ctx.accounts.master_membership;
+    membership.realm = realm.key();
+    membership.master = RealmMaster::new(realm.owner, RealmMasterRole::Owner);
+    membership.created_at = realm.created_at;
 
     emit!(RealmEvent {
         realm_pubkey: realm.key(),
@@ -54,6 +68,7 @@ pub struct UpdateRealmDescription<'info> {
         mut,
         seeds = [REALM_SEED, realm_id.as_bytes()],
         bump,
+        constraint = realm.is_active() @ ErrorCode::RealmClosing,
         realloc = crate::realm_space!(
             description,
             realm.masters.len(),
@@ -65,11 +80,15 @@ pub struct UpdateRealmDescription<'info> {
     pub realm: Account<'info, Realm>,
 
     #[account(
-        mut,
-        constraint = realm.masters.iter().any(|m|
-            m.pubkey == master.key() && m.can_update_realm()
+        seeds = [MASTER_SEED, realm.key().as_ref(), master.key().as_ref()],
+        bump,
+        constraint = master_membership.master.has_permission(
+            RealmPermissions::UPDATE_REALM
         ) @ ErrorCode::UnauthorizedRealmMaster
     )]
+    pub master_membership: Account<'info, RealmMembership>,
+
+    #[account(mut)]
     pub master: Signer<'info>,
 
     pub system_program: Program<'info, System>,
@@ -94,6 +113,47 @@ pub fn update_realm_description(
     Ok(())
 }
 
+#[derive(Accounts)]
+#[instruction(realm_id: String)]
+pub struct SetRealmVisibility<'info> {
+    #[account(
+        mut,
+        seeds = [REALM_SEED, realm_id.as_bytes()],
+        bump,
+        constraint = realm.is_active() @ ErrorCode::RealmClosing
+    )]
+    pub realm: Account<'info, Realm>,
+
+    #[account(
+        seeds = [MASTER_SEED, realm.key().as_ref(), master.key().as_ref()],
+        bump,
+        constraint = master_membership.master.has_permission(
+            RealmPermissions::UPDATE_REALM
+        ) @ ErrorCode::UnauthorizedRealmMaster
+    )]
+    pub master_membership: Account<'info, RealmMembership>,
+
+    pub master: Signer<'info>,
+}
+
+// Invite-only allowlists are replaced by setting the visibility again with a new root
+pub fn set_realm_visibility(
+    ctx: Context<SetRealmVisibility>,
+    _realm_id: String,
+    visibility: RealmVisibility,
+) -> Result<()> {
+    let realm = &mut ctx.accounts.realm;
+    realm.visibility = visibility;
+    realm.updated_at = Clock::get()?.unix_timestamp;
+
+    emit!(RealmEvent {
+        realm_pubkey: realm.key(),
+        event_type: RealmEventType::RealmVisibilityChanged { visibility },
+    });
+
+    Ok(())
+}
+
 #[derive(Accounts)]
 #[instruction(realm_id: String)]
 pub struct DeleteRealm<'info> {
@@ -107,19 +167,52 @@ pub struct DeleteRealm<'info> {
 
     #[account(
         mut,
-        constraint = realm.masters.iter().any(|m|
-            m.pubkey == master.key() && m.can_delete_realm()
-        ) @ ErrorCode::UnauthorizedRealmMaster
+        seeds = [MASTER_SEED, realm.key().as_ref(), master.key().as_ref()],
+        bump,
+        constraint = master_membership.master.has_permission(
+            RealmPermissions::DELETE_REALM
+        ) @ ErrorCode::UnauthorizedRealmMaster,
+        close = master
     )]
+    pub master_membership: Account<'info, RealmMembership>,
+
+    #[account(mut)]
     pub master: Signer<'info>,
 
+    #[account(mut, has_one = realm, has_one = proposer, close = proposer)]
+    pub proposal: Option<Account<'info, RealmProposal>>,
+
+    #[account(mut)]
+    pub proposer: Option<SystemAccount<'info>>,
+
+    #[account(mut, has_one = realm, close = master)]
+    pub queued_action: Option<Account<'info, RealmQueuedAction>>,
+
     pub system_program: Program<'info, System>,
 }
 
-pub fn delete_realm(ctx: Context<DeleteRealm>, _realm_id: String) -> Result<()> {
+// Dimensions with their portals, walkability grids and owners, the spawn points and the memberships
+// of the other masters are expected in the remaining accounts, along with the approvers' memberships
+// under an approval policy.
+// Journeys are left to their players, who close them with end_journey
+pub fn delete_realm<'info>(
+    ctx: Context<'_, '_, 'info, 'info, DeleteRealm<'info>>,
+    _realm_id: String,
+) -> Result<()> {
     let realm = &mut ctx.accounts.realm;
 
-    // Iterate over and close all dimensions linked to the realm
+    realm.require_approval(
+        ctx.accounts.proposal.as_deref(),
+        &RealmProposalAction::DeleteRealm,
+        &RealmMembership::load_approvers(&realm.key(), ctx.remaining_accounts),
+    )?;
+    realm.require_timelock(
+        ctx.accounts.queued_action.as_deref(),
+        &RealmTimelockAction::DeleteRealm,
+        Clock::get()?.unix_timestamp,
+    )?;
+
+    // Close all dimensions linked to the realm, refunding the rent to their owners
     for dimension_pubkey in realm.dimensions.iter() {
         let dimension_info = ctx
             .remaining_accounts
@@ -127,17 +220,367 @@ pub fn delete_realm(ctx: Context<DeleteRealm>, _realm_id: String) -> Result<()>
             .find(|account_info| account_info.key == dimension_pubkey)
             .ok_or(ErrorCode::DimensionNotProvided)?;
 
-        // TODO: transfer to dimension owner
-
-        **ctx.accounts.master.lamports.borrow_mut() += dimension_info.lamports();
-        **dimension_info.lamports.borrow_mut() = 0;
-        dimension_info.try_borrow_mut_data()?.fill(0);
+        close_realm_dimension(
+            realm.key(),
+            dimension_info,
+            &ctx.accounts.master,
+            ctx.remaining_accounts,
+        )?;
     }
 
+    // Close memberships so they can't outlive the realm
+    let closed_memberships = 1 + close_realm_memberships(
+        realm.key(),
+        ctx.accounts.master_membership.key(),
+        &ctx.accounts.master,
+        ctx.remaining_accounts,
+    )?;
+    require!(
+        closed_memberships == realm.masters_count,
+        ErrorCode::RealmMasterNotProvided
+    );
+
+    // Spawn points are keyed by the realm id, so they would come back with a recreated realm
+    let closed_spawn_points =
+        close_realm_spawn_points(realm.key(), &ctx.accounts.master, ctx.remaining_accounts)?;
+    require!(
+        closed_spawn_points == realm.spawn_points_count,
+        ErrorCode::SpawnPointNotProvided
+    );
+
+    emit_deletion_executed(
+        realm.key(),
+        ctx.accounts.proposal.as_ref(),
+        ctx.accounts.queued_action.as_ref(),
+    );
+
+    emit!(RealmEvent {
+        realm_pubkey: realm.key(),
+        event_type: RealmEventType::RealmDeleted {},
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(realm_id: String)]
+pub struct BeginRealmTeardown<'info> {
+    #[account(
+        mut,
+        seeds = [REALM_SEED, realm_id.as_bytes()],
+        bump,
+        constraint = realm.is_active() @ ErrorCode::RealmClosing
+    )]
+    pub realm: Account<'info, Realm>,
+
+    #[account(
+        seeds = [MASTER_SEED, realm.key().as_ref(), master.key().as_ref()],
+        bump,
+        constraint = master_membership.master.has_permission(
+            RealmPermissions::DELETE_REALM
+        ) @ ErrorCode::UnauthorizedRealmMaster
+    )]
+    pub master_membership: Account<'info, RealmMembership>,
+
+    #[account(mut)]
+    pub master: Signer<'info>,
+
+    #[account(mut, has_one = realm, has_one = proposer, close = proposer)]
+    pub proposal: Option<Account<'info, RealmProposal>>,
+
+    #[account(mut)]
+    pub proposer: Option<SystemAccount<'info>>,
+
+    #[account(mut, has_one = realm, close = master)]
+    pub queued_action: Option<Account<'info, RealmQueuedAction>>,
+}
+
+// Teardown spreads the deletion of large realms over several transactions,
+// the approvers' memberships are expected in the remaining accounts under an approval policy
+pub fn begin_realm_teardown(ctx: Context<BeginRealmTeardown>, _realm_id: String) -> Result<()> {
+    let realm = &mut ctx.accounts.realm;
+    let now = Clock::get()?.unix_timestamp;
+
+    realm.require_approval(
+        ctx.accounts.proposal.as_deref(),
+        &RealmProposalAction::DeleteRealm,
+        &RealmMembership::load_approvers(&realm.key(), ctx.remaining_accounts),
+    )?;
+    realm.require_timelock(
+        ctx.accounts.queued_action.as_deref(),
+        &RealmTimelockAction::DeleteRealm,
+        now,
+    )?;
+
+    realm.status = RealmStatus::Closing;
+    realm.updated_at = now;
+
+    // The master's own membership is closed last, by finish_realm_teardown
+    let masters_left = realm
+        .masters_count
+        .checked_sub(1)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+    emit_deletion_executed(
+        realm.key(),
+        ctx.accounts.proposal.as_ref(),
+        ctx.accounts.queued_action.as_ref(),
+    );
+
     emit!(RealmEvent {
         realm_pubkey: realm.key(),
+        event_type: RealmEventType::RealmTeardownStarted {
+            dimensions_left: realm.dimensions.len() as u32,
+            masters_left,
+            spawn_points_left: realm.spawn_points_count,
+        },
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(realm_id: String)]
+pub struct ContinueRealmTeardown<'info> {
+    #[account(
+        mut,
+        seeds = [REALM_SEED, realm_id.as_bytes()],
+        bump,
+        constraint = !realm.is_active() @ ErrorCode::RealmNotClosing
+    )]
+    pub realm: Account<'info, Realm>,
+
+    #[account(
+        seeds = [MASTER_SEED, realm.key().as_ref(), master.key().as_ref()],
+        bump,
+        constraint = master_membership.master.has_permission(
+            RealmPermissions::DELETE_REALM
+        ) @ ErrorCode::UnauthorizedRealmMaster
+    )]
+    pub master_membership: Account<'info, RealmMembership>,
+
+    #[account(mut)]
+    pub master: Signer<'info>,
+}
+
+// A batch of dimensions with their portals, walkability grids and owners, spawn points and memberships
+// of other masters is expected in the remaining accounts. Anything of the realm left out is closed in a later batch
+pub fn continue_realm_teardown<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ContinueRealmTeardown<'info>>,
+    _realm_id: String,
+) -> Result<()> {
+    let realm = &mut ctx.accounts.realm;
+    let realm_pubkey = realm.key();
+
+    let mut remaining_dimensions = Vec::with_capacity(realm.dimensions.len());
+    for dimension_pubkey in realm.dimensions.iter() {
+        match ctx
+            .remaining_accounts
+            .iter()
+            .find(|account_info| account_info.key == dimension_pubkey)
+        {
+            Some(dimension_info) => close_realm_dimension(
+                realm_pubkey,
+                dimension_info,
+                &ctx.accounts.master,
+                ctx.remaining_accounts,
+            )?,
+            None => remaining_dimensions.push(*dimension_pubkey),
+        }
+    }
+    let dimensions_closed = (realm.dimensions.len() - remaining_dimensions.len()) as u32;
+    realm.dimensions = remaining_dimensions;
+
+    let masters_closed = close_realm_memberships(
+        realm_pubkey,
+        ctx.accounts.master_membership.key(),
+        &ctx.accounts.master,
+        ctx.remaining_accounts,
+    )?;
+    realm.masters_count = realm
+        .masters_count
+        .checked_sub(masters_closed)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    let masters_left = realm
+        .masters_count
+        .checked_sub(1)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+    let spawn_points_closed =
+        close_realm_spawn_points(realm_pubkey, &ctx.accounts.master, ctx.remaining_accounts)?;
+    realm.spawn_points_count = realm
+        .spawn_points_count
+        .checked_sub(spawn_points_closed)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    realm.updated_at = Clock::get()?.unix_timestamp;
+
+    emit!(RealmEvent {
+        realm_pubkey,
+        event_type: RealmEventType::RealmTeardownProgress {
+            dimensions_closed,
+            masters_closed,
+            spawn_points_closed,
+            dimensions_left: realm.dimensions.len() as u32,
+            masters_left,
+            spawn_points_left: realm.spawn_points_count,
+        },
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(realm_id: String)]
+pub struct FinishRealmTeardown<'info> {
+    #[account(
+        mut,
+        seeds = [REALM_SEED, realm_id.as_bytes()],
+        bump,
+        constraint = !realm.is_active() @ ErrorCode::RealmNotClosing,
+        constraint = realm.dimensions.is_empty()
+            && realm.masters_count == 1
+            && realm.spawn_points_count == 0 @ ErrorCode::RealmTeardownIncomplete,
+        close = master
+    )]
+    pub realm: Account<'info, Realm>,
+
+    #[account(
+        mut,
+        seeds = [MASTER_SEED, realm.key().as_ref(), master.key().as_ref()],
+        bump,
+        constraint = master_membership.master.has_permission(
+            RealmPermissions::DELETE_REALM
+        ) @ ErrorCode::UnauthorizedRealmMaster,
+        close = master
+    )]
+    pub master_membership: Account<'info, RealmMembership>,
+
+    #[account(mut)]
+    pub master: Signer<'info>,
+}
+
+pub fn finish_realm_teardown(ctx: Context<FinishRealmTeardown>, _realm_id: String) -> Result<()> {
+    emit!(RealmEvent {
+        realm_pubkey: ctx.accounts.realm.key(),
         event_type: RealmEventType::RealmDeleted {},
     });
 
     Ok(())
 }
+
+// Closes a dimension of the realm with its portals and walkability grid,
+// refunding the rent to its owner who is expected in the remaining accounts unless it is the master
+fn close_realm_dimension<'info>(
+    realm_pubkey: Pubkey,
+    dimension_info: &'info AccountInfo<'info>,
+    master: &Signer<'info>,
+    remaining_accounts: &'info [AccountInfo<'info>],
+) -> Result<()> {
+    let dimension = Account::<RealmDimension>::try_from(dimension_info)?;
+    close_dimension_portals(&dimension, master, remaining_accounts)?;
+
+    let owner_info = if dimension.owner == master.key() {
+        master.to_account_info()
+    } else {
+        remaining_accounts
+            .iter()
+            .find(|account_info| *account_info.key == dimension.owner)
+            .ok_or(ErrorCode::DimensionOwnerNotProvided)?
+            .clone()
+    };
+
+    close_dimension_walkability_grid(&dimension, owner_info.clone(), remaining_accounts)?;
+    dimension.close(owner_info)?;
+
+    emit!(RealmDimensionEvent {
+        realm_pubkey,
+        dimension_pubkey: *dimension_info.key,
+        event_type: RealmDimensionEventType::DimensionRemoved {},
+    });
+
+    Ok(())
+}
+
+// Closes the memberships of the realm found in the remaining accounts, except the master's own
+fn close_realm_memberships<'info>(
+    realm_pubkey: Pubkey,
+    master_membership: Pubkey,
+    master: &Signer<'info>,
+    remaining_accounts: &'info [AccountInfo<'info>],
+) -> Result<u32> {
+    let mut closed_memberships = 0;
+    for account_info in remaining_accounts.iter() {
+        // Closed accounts are handed back to the system program, so duplicates are skipped
+        if *account_info.key == master_membership || *account_info.owner != crate::ID {
+            continue;
+        }
+
+        let Ok(membership) = Account::<RealmMembership>::try_from(account_info) else {
+            continue;
+        };
+        if membership.realm != realm_pubkey {
+            continue;
+        }
+
+        membership.close(master.to_account_info())?;
+        closed_memberships += 1;
+    }
+    Ok(closed_memberships)
+}
+
+// Closes the spawn points of the realm found in the remaining accounts
+fn close_realm_spawn_points<'info>(
+    realm_pubkey: Pubkey,
+    master: &Signer<'info>,
+    remaining_accounts: &'info [AccountInfo<'info>],
+) -> Result<u32> {
+    let mut closed_spawn_points = 0;
+    for account_info in remaining_accounts.iter() {
+        // Closed accounts are handed back to the system program, so duplicates are skipped
+        if *account_info.owner != crate::ID {
+            continue;
+        }
+
+        let Ok(spawn_point) = Account::<RealmSpawnPoint>::try_from(account_info) else {
+            continue;
+        };
+        if spawn_point.realm != realm_pubkey {
+            continue;
+        }
+
+        spawn_point.close(master.to_account_info())?;
+        closed_spawn_points += 1;
+
+        emit!(RealmEvent {
+            realm_pubkey,
+            event_type: RealmEventType::SpawnPointRemoved {
+                spawn_point: *account_info.key,
+            },
+        });
+    }
+    Ok(closed_spawn_points)
+}
+
+fn emit_deletion_executed(
+    realm_pubkey: Pubkey,
+    proposal: Option<&Account<RealmProposal>>,
+    queued_action: Option<&Account<RealmQueuedAction>>,
+) {
+    if let Some(proposal) = proposal {
+        emit!(RealmProposalEvent {
+            realm_pubkey,
+            proposal_pubkey: proposal.key(),
+            event_type: RealmProposalEventType::ProposalExecuted {},
+        });
+    }
+
+    if let Some(queued_action) = queued_action {
+        emit!(RealmEvent {
+            realm_pubkey,
+            event_type: RealmEventType::RealmActionExecuted {
+                queued_action: queued_action.key(),
+                action: RealmTimelockAction::DeleteRealm,
+            },
+        });
+    }
+}
@@ -0,0 +1,198 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+#[instruction(realm_id: String, spawn_point_id: String, dimension_id: String)]
+pub struct AddRealmSpawnPoint<'info> {
+    #[account(
+        mut,
+        seeds = [REALM_SEED, realm_id.as_bytes()],
+        bump,
+        constraint = realm.is_active() @ ErrorCode::RealmClosing
+    )]
+    pub realm: Account<'info, Realm>,
+
+    #[account(
+        seeds = [DIMENSION_SEED, realm_id.as_bytes(), dimension_id.as_bytes()],
+        bump,
+    )]
+    pub dimension: Account<'info, RealmDimension>,
+
+    #[account(
+        init,
+        payer = master,
+        space = 8 + RealmSpawnPoint::INIT_SPACE,
+        seeds = [SPAWN_POINT_SEED, realm_id.as_bytes(), spawn_point_id.as_bytes()],
+        bump
+    )]
+    pub spawn_point: Account<'info, RealmSpawnPoint>,
+
+    #[account(
+        seeds = [MASTER_SEED, realm.key().as_ref(), master.key().as_ref()],
+        bump,
+        constraint = master_membership.master.has_permission(
+            RealmPermissions::SET_STARTING_POINT
+        ) @ ErrorCode::UnauthorizedRealmMaster
+    )]
+    pub master_membership: Account<'info, RealmMembership>,
+
+    #[account(mut)]
+    pub master: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn add_realm_spawn_point(
+    ctx: Context<AddRealmSpawnPoint>,
+    _realm_id: String,
+    _spawn_point_id: String,
+    _dimension_id: String,
+    settings: SpawnPointSettings,
+) -> Result<()> {
+    settings.validate(&ctx.accounts.dimension)?;
+
+    let realm = &mut ctx.accounts.realm;
+    require!(
+        realm.spawn_points_count < MAX_SPAWN_POINTS,
+        ErrorCode::TooManySpawnPoints
+    );
+    realm.spawn_points_count += 1;
+    realm.updated_at = Clock::get()?.unix_timestamp;
+
+    let spawn_point = &mut ctx.accounts.spawn_point;
+    spawn_point.realm = realm.key();
+    spawn_point.dimension = ctx.accounts.dimension.key();
+    spawn_point.settings = settings.clone();
+
+    emit!(RealmEvent {
+        realm_pubkey: realm.key(),
+        event_type: RealmEventType::SpawnPointAdded {
+            spawn_point: spawn_point.key(),
+            dimension: spawn_point.dimension,
+            settings,
+        },
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(realm_id: String, spawn_point_id: String, dimension_id: String)]
+pub struct UpdateRealmSpawnPoint<'info> {
+    #[account(
+        seeds = [REALM_SEED, realm_id.as_bytes()],
+        bump,
+        constraint = realm.is_active() @ ErrorCode::RealmClosing
+    )]
+    pub realm: Account<'info, Realm>,
+
+    #[account(
+        seeds = [DIMENSION_SEED, realm_id.as_bytes(), dimension_id.as_bytes()],
+        bump,
+    )]
+    pub dimension: Account<'info, RealmDimension>,
+
+    #[account(
+        mut,
+        seeds = [SPAWN_POINT_SEED, realm_id.as_bytes(), spawn_point_id.as_bytes()],
+        bump,
+        has_one = realm
+    )]
+    pub spawn_point: Account<'info, RealmSpawnPoint>,
+
+    #[account(
+        seeds = [MASTER_SEED, realm.key().as_ref(), master.key().as_ref()],
+        bump,
+        constraint = master_membership.master.has_permission(
+            RealmPermissions::SET_STARTING_POINT
+        ) @ ErrorCode::UnauthorizedRealmMaster
+    )]
+    pub master_membership: Account<'info, RealmMembership>,
+
+    pub master: Signer<'info>,
+}
+
+// Spawn points may be moved to another dimension of the realm
+pub fn update_realm_spawn_point(
+    ctx: Context<UpdateRealmSpawnPoint>,
+    _realm_id: String,
+    _spawn_point_id: String,
+    _dimension_id: String,
+    settings: SpawnPointSettings,
+) -> Result<()> {
+    settings.validate(&ctx.accounts.dimension)?;
+
+    let spawn_point = &mut ctx.accounts.spawn_point;
+    spawn_point.dimension = ctx.accounts.dimension.key();
+    spawn_point.settings = settings.clone();
+
+    emit!(RealmEvent {
+        realm_pubkey: spawn_point.realm,
+        event_type: RealmEventType::SpawnPointUpdated {
+            spawn_point: spawn_point.key(),
+            dimension: spawn_point.dimension,
+            settings,
+        },
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(realm_id: String, spawn_point_id: String)]
+pub struct RemoveRealmSpawnPoint<'info> {
+    #[account(
+        mut,
+        seeds = [REALM_SEED, realm_id.as_bytes()],
+        bump,
+        constraint = realm.is_active() @ ErrorCode::RealmClosing
+    )]
+    pub realm: Account<'info, Realm>,
+
+    #[account(
+        mut,
+        seeds = [SPAWN_POINT_SEED, realm_id.as_bytes(), spawn_point_id.as_bytes()],
+        bump,
+        has_one = realm,
+        close = master
+    )]
+    pub spawn_point: Account<'info, RealmSpawnPoint>,
+
+    #[account(
+        seeds = [MASTER_SEED, realm.key().as_ref(), master.key().as_ref()],
+        bump,
+        constraint = master_membership.master.has_permission(
+            RealmPermissions::SET_STARTING_POINT
+        ) @ ErrorCode::UnauthorizedRealmMaster
+    )]
+    pub master_membership: Account<'info, RealmMembership>,
+
+    #[account(mut)]
+    pub master: Signer<'info>,
+}
+
+pub fn remove_realm_spawn_point(
+    ctx: Context<RemoveRealmSpawnPoint>,
+    _realm_id: String,
+    _spawn_point_id: String,
+) -> Result<()> {
+    let realm = &mut ctx.accounts.realm;
+    realm.spawn_points_count = realm
+        .spawn_points_count
+        .checked_sub(1)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    realm.updated_at = Clock::get()?.unix_timestamp;
+
+    emit!(RealmEvent {
+        realm_pubkey: realm.key(),
+        event_type: RealmEventType::SpawnPointRemoved {
+            spawn_point: ctx.accounts.spawn_point.key(),
+        },
+    });
+
+    Ok(())
+}
@@ -0,0 +1,191 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+#[instruction(realm_id: String, timelock: i64)]
+pub struct SetRealmTimelock<'info> {
+    #[account(
+        mut,
+        seeds = [REALM_SEED, realm_id.as_bytes()],
+        bump,
+        constraint = realm.is_active() @ ErrorCode::RealmClosing,
+    )]
+    pub realm: Account<'info, Realm>,
+
+    #[account(
+        seeds = [MASTER_SEED, realm.key().as_ref(), master.key().as_ref()],
+        bump,
+        constraint = master_membership.master.has_permission(
+            RealmPermissions::MANAGE_MASTERS
+        ) @ ErrorCode::UnauthorizedRealmMaster
+    )]
+    pub master_membership: Account<'info, RealmMembership>,
+
+    #[account(mut)]
+    pub master: Signer<'info>,
+
+    #[account(mut, has_one = realm, close = master)]
+    pub queued_action: Option<Account<'info, RealmQueuedAction>>,
+}
+
+pub fn set_realm_timelock(
+    ctx: Context<SetRealmTimelock>,
+    _realm_id: String,
+    timelock: i64,
+) -> Result<()> {
+    Realm::validate_timelock(timelock)?;
+
+    let realm = &mut ctx.accounts.realm;
+    let now = Clock::get()?.unix_timestamp;
+    let action = RealmTimelockAction::SetTimelock { timelock };
+
+    // Raising the timelock only gives players more warning, lowering it must wait
+    if timelock < realm.timelock {
+        realm.require_timelock(ctx.accounts.queued_action.as_deref(), &action, now)?;
+    } else if let Some(queued_action) = &ctx.accounts.queued_action {
+        queued_action.validate_for(realm, &action)?;
+    }
+
+    realm.timelock = timelock;
+    realm.updated_at = now;
+
+    if let Some(queued_action) = &ctx.accounts.queued_action {
+        emit!(RealmEvent {
+            realm_pubkey: realm.key(),
+            event_type: RealmEventType::RealmActionExecuted {
+                queued_action: queued_action.key(),
+                action,
+            },
+        });
+    }
+
+    emit!(RealmEvent {
+        realm_pubkey: realm.key(),
+        event_type: RealmEventType::RealmTimelockChanged { timelock },
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(realm_id: String, action_id: String, action: RealmTimelockAction)]
+pub struct QueueRealmAction<'info> {
+    #[account(
+        seeds = [REALM_SEED, realm_id.as_bytes()],
+        bump,
+        constraint = realm.is_active() @ ErrorCode::RealmClosing
+    )]
+    pub realm: Account<'info, Realm>,
+
+    #[account(
+        init,
+        payer = master,
+        space = 8 + RealmQueuedAction::INIT_SPACE,
+        seeds = [QUEUED_ACTION_SEED, realm_id.as_bytes(), action_id.as_bytes()],
+        bump
+    )]
+    pub queued_action: Account<'info, RealmQueuedAction>,
+
+    #[account(
+        seeds = [MASTER_SEED, realm.key().as_ref(), master.key().as_ref()],
+        bump,
+        constraint = master_membership.master.can_queue_action(
+            &action
+        ) @ ErrorCode::UnauthorizedRealmMaster
+    )]
+    pub master_membership: Account<'info, RealmMembership>,
+
+    #[account(mut)]
+    pub master: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn queue_realm_action(
+    ctx: Context<QueueRealmAction>,
+    _realm_id: String,
+    _action_id: String,
+    action: RealmTimelockAction,
+) -> Result<()> {
+    if let RealmTimelockAction::SetTimelock { timelock } = action {
+        Realm::validate_timelock(timelock)?;
+    }
+
+    let queued_action = &mut ctx.accounts.queued_action;
+    queued_action.realm = ctx.accounts.realm.key();
+    queued_action.queued_by = *ctx.accounts.master.key;
+    queued_action.action = action.clone();
+    queued_action.queued_at = Clock::get()?.unix_timestamp;
+    queued_action.execute_after = queued_action
+        .queued_at
+        .checked_add(ctx.accounts.realm.timelock)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+    emit!(RealmEvent {
+        realm_pubkey: queued_action.realm,
+        event_type: RealmEventType::RealmActionQueued {
+            queued_action: queued_action.key(),
+            action,
+            execute_after: queued_action.execute_after,
+        },
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(realm_id: String, action_id: String)]
+pub struct CancelRealmAction<'info> {
+    #[account(seeds = [REALM_SEED, realm_id.as_bytes()], bump)]
+    pub realm: Account<'info, Realm>,
+
+    #[account(
+        mut,
+        seeds = [QUEUED_ACTION_SEED, realm_id.as_bytes(), action_id.as_bytes()],
+        bump,
+        has_one = realm,
+        has_one = queued_by,
+        close = queued_by
+    )]
+    pub queued_action: Account<'info, RealmQueuedAction>,
+
+    #[account(mut)]
+    pub queued_by: SystemAccount<'info>,
+
+    #[account(
+        seeds = [MASTER_SEED, realm.key().as_ref(), master.key().as_ref()],
+        bump,
+    )]
+    pub master_membership: Option<Account<'info, RealmMembership>>,
+
+    // Either the master who queued the action or anyone allowed to queue it
+    #[account(
+        constraint = queued_action.queued_by == master.key()
+            || master_membership.as_ref().is_some_and(|membership|
+                membership.master.can_queue_action(&queued_action.action)
+            ) @ ErrorCode::UnauthorizedRealmMaster
+    )]
+    pub master: Signer<'info>,
+}
+
+pub fn cancel_realm_action(
+    ctx: Context<CancelRealmAction>,
+    _realm_id: String,
+    _action_id: String,
+) -> Result<()> {
+    let queued_action = &ctx.accounts.queued_action;
+
+    emit!(RealmEvent {
+        realm_pubkey: queued_action.realm,
+        event_type: RealmEventType::RealmActionCancelled {
+            queued_action: queued_action.key(),
+            action: queued_action.action.clone(),
+        },
+    });
+
+    Ok(())
+}
@@ -0,0 +1,227 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+#[instruction(realm_id: String, dimension_id: String, origin: Position, width: u32, height: u32)]
+pub struct CreateWalkabilityGrid<'info> {
+    #[account(
+        seeds = [REALM_SEED, realm_id.as_bytes()],
+        bump,
+        constraint = realm.is_active() @ ErrorCode::RealmClosing
+    )]
+    pub realm: Account<'info, Realm>,
+
+    #[account(
+        mut,
+        seeds = [DIMENSION_SEED, realm_id.as_bytes(), dimension_id.as_bytes()],
+        bump,
+    )]
+    pub dimension: Account<'info, RealmDimension>,
+
+    #[account(
+        init,
+        payer = master,
+        space = crate::walkability_grid_space!(width, height),
+        seeds = [WALKABILITY_GRID_SEED, dimension.key().as_ref()],
+        bump
+    )]
+    pub grid: Account<'info, WalkabilityGrid>,
+
+    #[account(
+        seeds = [MASTER_SEED, realm.key().as_ref(), master.key().as_ref()],
+        bump,
+        constraint = master_membership.master.can_manage_realm_dimension(
+            &dimension
+        ) @ ErrorCode::UnauthorizedRealmMaster
+    )]
+    pub master_membership: Account<'info, RealmMembership>,
+
+    #[account(mut)]
+    pub master: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Cells start walkable, walls are uploaded afterwards in chunks
+pub fn create_walkability_grid(
+    ctx: Context<CreateWalkabilityGrid>,
+    _realm_id: String,
+    _dimension_id: String,
+    origin: Position,
+    width: u32,
+    height: u32,
+) -> Result<()> {
+    WalkabilityGrid::validate(&origin, width, height)?;
+
+    let grid = &mut ctx.accounts.grid;
+    grid.realm = ctx.accounts.realm.key();
+    grid.dimension = ctx.accounts.dimension.key();
+    grid.origin = origin;
+    grid.width = width;
+    grid.height = height;
+    grid.blocked = vec![0; WalkabilityGrid::bitmap_len(width, height)];
+
+    ctx.accounts.dimension.has_walkability_grid = true;
+
+    emit!(RealmDimensionEvent {
+        realm_pubkey: grid.realm,
+        dimension_pubkey: grid.dimension,
+        event_type: RealmDimensionEventType::WalkabilityGridCreated {
+            grid_pubkey: grid.key(),
+            origin,
+            width,
+            height,
+        },
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(realm_id: String, dimension_id: String)]
+pub struct UploadWalkabilityChunk<'info> {
+    #[account(
+        seeds = [REALM_SEED, realm_id.as_bytes()],
+        bump,
+        constraint = realm.is_active() @ ErrorCode::RealmClosing
+    )]
+    pub realm: Account<'info, Realm>,
+
+    #[account(
+        seeds = [DIMENSION_SEED, realm_id.as_bytes(), dimension_id.as_bytes()],
+        bump,
+    )]
+    pub dimension: Account<'info, RealmDimension>,
+
+    #[account(
+        mut,
+        seeds = [WALKABILITY_GRID_SEED, dimension.key().as_ref()],
+        bump,
+    )]
+    pub grid: Account<'info, WalkabilityGrid>,
+
+    #[account(
+        seeds = [MASTER_SEED, realm.key().as_ref(), master.key().as_ref()],
+        bump,
+        constraint = master_membership.master.can_manage_realm_dimension(
+            &dimension
+        ) @ ErrorCode::UnauthorizedRealmMaster
+    )]
+    pub master_membership: Account<'info, RealmMembership>,
+
+    pub master: Signer<'info>,
+}
+
+// Chunks overwrite the bitmap bytes starting at the offset
+pub fn upload_walkability_chunk(
+    ctx: Context<UploadWalkabilityChunk>,
+    _realm_id: String,
+    _dimension_id: String,
+    offset: u32,
+    chunk: Vec<u8>,
+) -> Result<()> {
+    let grid = &mut ctx.accounts.grid;
+    grid.write_chunk(offset, &chunk)?;
+
+    emit!(RealmDimensionEvent {
+        realm_pubkey: grid.realm,
+        dimension_pubkey: grid.dimension,
+        event_type: RealmDimensionEventType::WalkabilityChunkUploaded {
+            grid_pubkey: grid.key(),
+            offset,
+            len: chunk.len() as u32,
+        },
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(realm_id: String, dimension_id: String)]
+pub struct RemoveWalkabilityGrid<'info> {
+    #[account(seeds = [REALM_SEED, realm_id.as_bytes()], bump)]
+    pub realm: Account<'info, Realm>,
+
+    #[account(
+        mut,
+        seeds = [DIMENSION_SEED, realm_id.as_bytes(), dimension_id.as_bytes()],
+        bump,
+    )]
+    pub dimension: Account<'info, RealmDimension>,
+
+    #[account(
+        mut,
+        seeds = [WALKABILITY_GRID_SEED, dimension.key().as_ref()],
+        bump,
+        close = owner
+    )]
+    pub grid: Account<'info, WalkabilityGrid>,
+
+    #[account(
+        seeds = [MASTER_SEED, realm.key().as_ref(), master.key().as_ref()],
+        bump,
+        constraint = master_membership.master.can_manage_realm_dimension(
+            &dimension
+        ) @ ErrorCode::UnauthorizedRealmMaster
+    )]
+    pub master_membership: Account<'info, RealmMembership>,
+
+    #[account(mut)]
+    pub master: Signer<'info>,
+
+    #[account(mut, address = dimension.owner)]
+    pub owner: SystemAccount<'info>,
+}
+
+pub fn remove_walkability_grid(
+    ctx: Context<RemoveWalkabilityGrid>,
+    _realm_id: String,
+    _dimension_id: String,
+) -> Result<()> {
+    ctx.accounts.dimension.has_walkability_grid = false;
+
+    emit!(RealmDimensionEvent {
+        realm_pubkey: ctx.accounts.realm.key(),
+        dimension_pubkey: ctx.accounts.dimension.key(),
+        event_type: RealmDimensionEventType::WalkabilityGridRemoved {
+            grid_pubkey: ctx.accounts.grid.key(),
+        },
+    });
+
+    Ok(())
+}
+
+// Closes the walkability grid of the dimension, expected in the remaining accounts when it has one,
+// refunding the rent to the dimension owner
+pub fn close_dimension_walkability_grid<'info>(
+    dimension: &Account<'info, RealmDimension>,
+    owner_info: AccountInfo<'info>,
+    remaining_accounts: &'info [AccountInfo<'info>],
+) -> Result<()> {
+    if !dimension.has_walkability_grid {
+        return Ok(());
+    }
+
+    let (grid_pubkey, _) = Pubkey::find_program_address(
+        &[WALKABILITY_GRID_SEED, dimension.key().as_ref()],
+        &crate::ID,
+    );
+    let grid_info = remaining_accounts
+        .iter()
+        .find(|account_info| *account_info.key == grid_pubkey)
+        .ok_or(ErrorCode::WalkabilityGridNotProvided)?;
+
+    Account::<WalkabilityGrid>::try_from(grid_info)?.close(owner_info)?;
+
+    emit!(RealmDimensionEvent {
+        realm_pubkey: dimension.realm,
+        dimension_pubkey: dimension.key(),
+        event_type: RealmDimensionEventType::WalkabilityGridRemoved { grid_pubkey },
+    });
+
+    Ok(())
+}
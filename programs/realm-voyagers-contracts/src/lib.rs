@@ -31,33 +31,178 @@ pub mod realm_voyagers {
         realms::update_realm_description(ctx, realm_id, description)
     }
 
-    pub fn delete_realm(ctx: Context<DeleteRealm>, realm_id: String) -> Result<()> {
+    pub fn set_realm_visibility(
+        ctx: Context<SetRealmVisibility>,
+        realm_id: String,
+        visibility: state::RealmVisibility,
+    ) -> Result<()> {
+        realms::set_realm_visibility(ctx, realm_id, visibility)
+    }
+
+    pub fn delete_realm<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DeleteRealm<'info>>,
+        realm_id: String,
+    ) -> Result<()> {
         realms::delete_realm(ctx, realm_id)
     }
 
+    pub fn begin_realm_teardown(ctx: Context<BeginRealmTeardown>, realm_id: String) -> Result<()> {
+        realms::begin_realm_teardown(ctx, realm_id)
+    }
+
+    pub fn continue_realm_teardown<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ContinueRealmTeardown<'info>>,
+        realm_id: String,
+    ) -> Result<()> {
+        realms::continue_realm_teardown(ctx, realm_id)
+    }
+
+    pub fn finish_realm_teardown(
+        ctx: Context<FinishRealmTeardown>,
+        realm_id: String,
+    ) -> Result<()> {
+        realms::finish_realm_teardown(ctx, realm_id)
+    }
+
+    // Realm Proposals
+    pub fn set_realm_approval_threshold<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SetRealmApprovalThreshold<'info>>,
+        realm_id: String,
+        threshold: u8,
+    ) -> Result<()> {
+        proposals::set_realm_approval_threshold(ctx, realm_id, threshold)
+    }
+
+    pub fn create_realm_proposal(
+        ctx: Context<CreateRealmProposal>,
+        realm_id: String,
+        proposal_id: String,
+        action: state::RealmProposalAction,
+    ) -> Result<()> {
+        proposals::create_realm_proposal(ctx, realm_id, proposal_id, action)
+    }
+
+    pub fn approve_realm_proposal(
+        ctx: Context<VoteRealmProposal>,
+        realm_id: String,
+        proposal_id: String,
+    ) -> Result<()> {
+        proposals::approve_realm_proposal(ctx, realm_id, proposal_id)
+    }
+
+    pub fn reject_realm_proposal(
+        ctx: Context<VoteRealmProposal>,
+        realm_id: String,
+        proposal_id: String,
+    ) -> Result<()> {
+        proposals::reject_realm_proposal(ctx, realm_id, proposal_id)
+    }
+
+    pub fn cancel_realm_proposal(
+        ctx: Context<CancelRealmProposal>,
+        realm_id: String,
+        proposal_id: String,
+    ) -> Result<()> {
+        proposals::cancel_realm_proposal(ctx, realm_id, proposal_id)
+    }
+
+    // Realm Timelocks
+    pub fn set_realm_timelock(
+        ctx: Context<SetRealmTimelock>,
+        realm_id: String,
+        timelock: i64,
+    ) -> Result<()> {
+        timelocks::set_realm_timelock(ctx, realm_id, timelock)
+    }
+
+    pub fn queue_realm_action(
+        ctx: Context<QueueRealmAction>,
+        realm_id: String,
+        action_id: String,
+        action: state::RealmTimelockAction,
+    ) -> Result<()> {
+        timelocks::queue_realm_action(ctx, realm_id, action_id, action)
+    }
+
+    pub fn cancel_realm_action(
+        ctx: Context<CancelRealmAction>,
+        realm_id: String,
+        action_id: String,
+    ) -> Result<()> {
+        timelocks::cancel_realm_action(ctx, realm_id, action_id)
+    }
+
     // Realm Masters
     pub fn add_realm_master(
         ctx: Context<AddRealmMaster>,
         realm_id: String,
         new_master_pubkey: Pubkey,
+        role: state::RealmMasterRole,
+    ) -> Result<()> {
+        masters::add_realm_master(ctx, realm_id, new_master_pubkey, role)
+    }
+
+    pub fn set_realm_master_role(
+        ctx: Context<SetRealmMasterRole>,
+        realm_id: String,
+        master_pubkey: Pubkey,
+        role: state::RealmMasterRole,
     ) -> Result<()> {
-        masters::add_realm_master(ctx, realm_id, new_master_pubkey)
+        masters::set_realm_master_role(ctx, realm_id, master_pubkey, role)
     }
 
-    pub fn remove_realm_master(
-        ctx: Context<RemoveRealmMaster>,
+    pub fn update_realm_master_permissions(
+        ctx: Context<UpdateRealmMasterPermissions>,
+        realm_id: String,
+        master_pubkey: Pubkey,
+        granted: u32,
+        revoked: u32,
+    ) -> Result<()> {
+        masters::update_realm_master_permissions(ctx, realm_id, master_pubkey, granted, revoked)
+    }
+
+    pub fn remove_realm_master<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RemoveRealmMaster<'info>>,
         realm_id: String,
         master_pubkey: Pubkey,
     ) -> Result<()> {
         masters::remove_realm_master(ctx, realm_id, master_pubkey)
     }
 
-    pub fn transfer_realm_ownership(
-        ctx: Context<TransferRealmOwnership>,
+    pub fn resign_realm_master<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ResignRealmMaster<'info>>,
+        realm_id: String,
+    ) -> Result<()> {
+        masters::resign_realm_master(ctx, realm_id)
+    }
+
+    pub fn migrate_realm_masters<'info>(
+        ctx: Context<'_, '_, 'info, 'info, MigrateRealmMasters<'info>>,
+        realm_id: String,
+    ) -> Result<()> {
+        masters::migrate_realm_masters(ctx, realm_id)
+    }
+
+    pub fn propose_realm_owner(
+        ctx: Context<ProposeRealmOwner>,
         realm_id: String,
         new_owner_pubkey: Pubkey,
     ) -> Result<()> {
-        masters::transfer_realm_ownership(ctx, realm_id, new_owner_pubkey)
+        masters::propose_realm_owner(ctx, realm_id, new_owner_pubkey)
+    }
+
+    pub fn accept_realm_ownership(
+        ctx: Context<AcceptRealmOwnership>,
+        realm_id: String,
+    ) -> Result<()> {
+        masters::accept_realm_ownership(ctx, realm_id)
+    }
+
+    pub fn cancel_realm_ownership_proposal(
+        ctx: Context<CancelRealmOwnershipProposal>,
+        realm_id: String,
+    ) -> Result<()> {
+        masters::cancel_realm_ownership_proposal(ctx, realm_id)
     }
 
     // Realm Dimensions
@@ -67,8 +212,16 @@ pub mod realm_voyagers {
         dimension_id: String,
         name: String,
         areas: Vec<state::RealmDimensionArea>,
+        allow_overlapping_areas: bool,
     ) -> Result<()> {
-        dimensions::add_realm_dimension(ctx, realm_id, dimension_id, name, areas)
+        dimensions::add_realm_dimension(
+            ctx,
+            realm_id,
+            dimension_id,
+            name,
+            areas,
+            allow_overlapping_areas,
+        )
     }
 
     pub fn update_realm_dimension(
@@ -77,12 +230,47 @@ pub mod realm_voyagers {
         dimension_id: String,
         name: String,
         areas: Vec<state::RealmDimensionArea>,
+        allow_overlapping_areas: bool,
+    ) -> Result<()> {
+        dimensions::update_realm_dimension(
+            ctx,
+            realm_id,
+            dimension_id,
+            name,
+            areas,
+            allow_overlapping_areas,
+        )
+    }
+
+    pub fn add_dimension_area(
+        ctx: Context<AddDimensionArea>,
+        realm_id: String,
+        dimension_id: String,
+        area: state::RealmDimensionArea,
     ) -> Result<()> {
-        dimensions::update_realm_dimension(ctx, realm_id, dimension_id, name, areas)
+        dimensions::add_dimension_area(ctx, realm_id, dimension_id, area)
     }
 
-    pub fn remove_realm_dimension(
-        ctx: Context<RemoveRealmDimension>,
+    pub fn update_dimension_area(
+        ctx: Context<UpdateDimensionArea>,
+        realm_id: String,
+        dimension_id: String,
+        area: state::RealmDimensionArea,
+    ) -> Result<()> {
+        dimensions::update_dimension_area(ctx, realm_id, dimension_id, area)
+    }
+
+    pub fn remove_dimension_area(
+        ctx: Context<RemoveDimensionArea>,
+        realm_id: String,
+        dimension_id: String,
+        area_id: u32,
+    ) -> Result<()> {
+        dimensions::remove_dimension_area(ctx, realm_id, dimension_id, area_id)
+    }
+
+    pub fn remove_realm_dimension<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RemoveRealmDimension<'info>>,
         realm_id: String,
         dimension_id: String,
     ) -> Result<()> {
@@ -98,13 +286,107 @@ pub mod realm_voyagers {
         dimensions::set_realm_starting_point(ctx, realm_id, dimension_id, position)
     }
 
+    // Walkability Grids
+    pub fn create_walkability_grid(
+        ctx: Context<CreateWalkabilityGrid>,
+        realm_id: String,
+        dimension_id: String,
+        origin: state::Position,
+        width: u32,
+        height: u32,
+    ) -> Result<()> {
+        walkability::create_walkability_grid(ctx, realm_id, dimension_id, origin, width, height)
+    }
+
+    pub fn upload_walkability_chunk(
+        ctx: Context<UploadWalkabilityChunk>,
+        realm_id: String,
+        dimension_id: String,
+        offset: u32,
+        chunk: Vec<u8>,
+    ) -> Result<()> {
+        walkability::upload_walkability_chunk(ctx, realm_id, dimension_id, offset, chunk)
+    }
+
+    pub fn remove_walkability_grid(
+        ctx: Context<RemoveWalkabilityGrid>,
+        realm_id: String,
+        dimension_id: String,
+    ) -> Result<()> {
+        walkability::remove_walkability_grid(ctx, realm_id, dimension_id)
+    }
+
+    // Realm Spawn Points
+    pub fn add_realm_spawn_point(
+        ctx: Context<AddRealmSpawnPoint>,
+        realm_id: String,
+        spawn_point_id: String,
+        dimension_id: String,
+        settings: state::SpawnPointSettings,
+    ) -> Result<()> {
+        spawn_points::add_realm_spawn_point(ctx, realm_id, spawn_point_id, dimension_id, settings)
+    }
+
+    pub fn update_realm_spawn_point(
+        ctx: Context<UpdateRealmSpawnPoint>,
+        realm_id: String,
+        spawn_point_id: String,
+        dimension_id: String,
+        settings: state::SpawnPointSettings,
+    ) -> Result<()> {
+        spawn_points::update_realm_spawn_point(
+            ctx,
+            realm_id,
+            spawn_point_id,
+            dimension_id,
+            settings,
+        )
+    }
+
+    pub fn remove_realm_spawn_point(
+        ctx: Context<RemoveRealmSpawnPoint>,
+        realm_id: String,
+        spawn_point_id: String,
+    ) -> Result<()> {
+        spawn_points::remove_realm_spawn_point(ctx, realm_id, spawn_point_id)
+    }
+
+    // Realm Portals
+    pub fn add_realm_portal(
+        ctx: Context<AddRealmPortal>,
+        realm_id: String,
+        dimension_id: String,
+        portal_id: String,
+        area: state::Rect,
+        target_position: state::Position,
+    ) -> Result<()> {
+        portals::add_realm_portal(
+            ctx,
+            realm_id,
+            dimension_id,
+            portal_id,
+            area,
+            target_position,
+        )
+    }
+
+    pub fn remove_realm_portal(
+        ctx: Context<RemoveRealmPortal>,
+        realm_id: String,
+        dimension_id: String,
+        portal_id: String,
+    ) -> Result<()> {
+        portals::remove_realm_portal(ctx, realm_id, dimension_id, portal_id)
+    }
+
     // Heroes
     pub fn create_hero(
         ctx: Context<CreateHero>,
         hero_id: String,
         description: state::HeroDescription,
+        tags: Vec<state::HeroTag>,
     ) -> Result<()> {
-        heroes::create_hero(ctx, hero_id, description)
+        heroes::create_hero(ctx, hero_id, description, tags)
     }
 
     pub fn update_hero_description(
@@ -115,8 +397,151 @@ pub mod realm_voyagers {
         heroes::update_hero_description(ctx, hero_id, description)
     }
 
+    pub fn mint_player_hero(
+        ctx: Context<MintPlayerHero>,
+        player_hero_id: String,
+        name: Option<String>,
+    ) -> Result<()> {
+        heroes::mint_player_hero(ctx, player_hero_id, name)
+    }
+
+    pub fn set_hero_xp_curve(
+        ctx: Context<SetHeroXpCurve>,
+        hero_id: String,
+        xp_curve: state::XpCurve,
+    ) -> Result<()> {
+        heroes::set_hero_xp_curve(ctx, hero_id, xp_curve)
+    }
+
+    pub fn set_hero_stat_caps(
+        ctx: Context<SetHeroStatCaps>,
+        hero_id: String,
+        stat_caps: state::HeroStats,
+    ) -> Result<()> {
+        heroes::set_hero_stat_caps(ctx, hero_id, stat_caps)
+    }
+
+    pub fn set_hero_trusted_realms(
+        ctx: Context<SetHeroTrustedRealms>,
+        hero_id: String,
+        trusted_realms: Vec<Pubkey>,
+    ) -> Result<()> {
+        heroes::set_hero_trusted_realms(ctx, hero_id, trusted_realms)
+    }
+
+    pub fn grant_xp(ctx: Context<GrantXp>, amount: u64) -> Result<()> {
+        heroes::grant_xp(ctx, amount)
+    }
+
+    pub fn level_up(
+        ctx: Context<LevelUp>,
+        player_hero_id: String,
+        allocation: state::HeroStats,
+    ) -> Result<()> {
+        heroes::level_up(ctx, player_hero_id, allocation)
+    }
+
+    // Items
+    pub fn create_item(
+        ctx: Context<CreateItem>,
+        item_id: String,
+        description: state::ItemDescription,
+        rarity: state::ItemRarity,
+        slot: Option<state::EquipmentSlot>,
+        modifiers: state::HeroStats,
+        requirements: state::HeroStats,
+    ) -> Result<()> {
+        items::create_item(
+            ctx,
+            item_id,
+            description,
+            rarity,
+            slot,
+            modifiers,
+            requirements,
+        )
+    }
+
+    pub fn grant_item(ctx: Context<GrantItem>) -> Result<()> {
+        items::grant_item(ctx)
+    }
+
+    pub fn drop_item(
+        ctx: Context<DropItem>,
+        player_hero_id: String,
+        item_pubkey: Pubkey,
+    ) -> Result<()> {
+        items::drop_item(ctx, player_hero_id, item_pubkey)
+    }
+
+    pub fn transfer_item(
+        ctx: Context<TransferItem>,
+        player_hero_id: String,
+        item_pubkey: Pubkey,
+    ) -> Result<()> {
+        items::transfer_item(ctx, player_hero_id, item_pubkey)
+    }
+
+    pub fn equip_item<'info>(
+        ctx: Context<'_, '_, 'info, 'info, EquipItem<'info>>,
+        player_hero_id: String,
+        slot: state::EquipmentSlot,
+    ) -> Result<()> {
+        items::equip_item(ctx, player_hero_id, slot)
+    }
+
+    pub fn unequip_item(
+        ctx: Context<UnequipItem>,
+        player_hero_id: String,
+        slot: state::EquipmentSlot,
+    ) -> Result<()> {
+        items::unequip_item(ctx, player_hero_id, slot)
+    }
+
     // Journey
-    pub fn start_journey(ctx: Context<StartJourney>, realm_id: String) -> Result<()> {
-        journeys::start_journey(ctx, realm_id)
+    pub fn start_journey<'info>(
+        ctx: Context<'_, '_, 'info, 'info, StartJourney<'info>>,
+        realm_id: String,
+        allowlist_proof: Vec<[u8; 32]>,
+        spawn_point_id: Option<String>,
+    ) -> Result<()> {
+        journeys::start_journey(ctx, realm_id, allowlist_proof, spawn_point_id)
+    }
+
+    pub fn end_journey(ctx: Context<EndJourney>, realm_id: String) -> Result<()> {
+        journeys::end_journey(ctx, realm_id)
+    }
+
+    pub fn kick_voyager(ctx: Context<KickVoyager>, realm_id: String) -> Result<()> {
+        journeys::kick_voyager(ctx, realm_id)
+    }
+
+    pub fn move_hero(
+        ctx: Context<MoveHero>,
+        realm_id: String,
+        position: state::Position,
+    ) -> Result<()> {
+        journeys::move_hero(ctx, realm_id, position)
+    }
+
+    pub fn travel_through_portal(
+        ctx: Context<TravelThroughPortal>,
+        realm_id: String,
+    ) -> Result<()> {
+        journeys::travel_through_portal(ctx, realm_id)
+    }
+
+    pub fn create_session_key(
+        ctx: Context<CreateSessionKey>,
+        realm_id: String,
+        authority: Pubkey,
+        scope: u8,
+        expires_at: i64,
+    ) -> Result<()> {
+        journeys::create_session_key(ctx, realm_id, authority, scope, expires_at)
+    }
+
+    pub fn revoke_session_key(ctx: Context<RevokeSessionKey>) -> Result<()> {
+        journeys::revoke_session_key(ctx)
     }
 }
@@ -1,32 +1,30 @@
 use anchor_lang::prelude::*;
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+use crate::constants::*;
+use crate::errors::ErrorCode;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, InitSpace)]
 pub struct Position {
     pub x: i32,
     pub y: i32,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, InitSpace)]
 pub struct Rect {
     pub top_left: Position,
     pub bottom_right: Position,
 }
 
 impl Position {
-    pub fn distance_squared(&self, other: &Position) -> i32 {
-        let dx = self.x - other.x;
-        let dy = self.y - other.y;
+    // Widened to i64 so distant coordinates can't overflow
+    pub fn distance_squared(&self, other: &Position) -> i64 {
+        let dx = self.x as i64 - other.x as i64;
+        let dy = self.y as i64 - other.y as i64;
         dx * dx + dy * dy
     }
 
-    pub fn distance(&self, other: &Position) -> i32 {
-        (self.distance_squared(other) as f64).sqrt() as i32
-    }
-}
-
-impl Default for Position {
-    fn default() -> Self {
-        Self { x: 0, y: 0 }
+    pub fn distance(&self, other: &Position) -> i64 {
+        (self.distance_squared(other) as f64).sqrt() as i64
     }
 }
 
@@ -45,16 +43,24 @@ impl Rect {
             && self.bottom_right.y >= other.top_left.y
     }
 
-    pub fn area(&self) -> i32 {
-        (self.bottom_right.x - self.top_left.x) * (self.bottom_right.y - self.top_left.y)
+    pub fn encloses(&self, other: &Rect) -> bool {
+        self.contains(&other.top_left) && self.contains(&other.bottom_right)
     }
-}
 
-impl Default for Rect {
-    fn default() -> Self {
-        Self {
-            top_left: Position::default(),
-            bottom_right: Position::default(),
-        }
+    pub fn validate(&self) -> Result<()> {
+        let in_bounds = |position: &Position| {
+            (-MAX_COORDINATE..=MAX_COORDINATE).contains(&position.x)
+                && (-MAX_COORDINATE..=MAX_COORDINATE).contains(&position.y)
+        };
+        require!(
+            in_bounds(&self.top_left) && in_bounds(&self.bottom_right),
+            ErrorCode::AreaOutOfBounds
+        );
+        // Corners are inclusive, so a single row or column of cells is still an area
+        require!(
+            self.top_left.x <= self.bottom_right.x && self.top_left.y <= self.bottom_right.y,
+            ErrorCode::InvertedArea
+        );
+        Ok(())
     }
 }
@@ -1,7 +1,11 @@
 use anchor_lang::prelude::*;
 
+use crate::constants::*;
+use crate::errors::ErrorCode;
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
 pub struct RealmDimensionArea {
+    pub id: u32, // Stable within the dimension
     pub name: String,
 
     pub area: crate::state::Rect,
@@ -18,6 +22,82 @@ pub struct RealmDimension {
     pub name: String,
 
     pub areas: Vec<RealmDimensionArea>,
+    pub allow_overlapping_areas: bool,
+    pub has_walkability_grid: bool, // Movement is checked against its WalkabilityGrid
+    pub portals_count: u32,         // Portals leading out of the dimension
+    pub incoming_portals_count: u32, // Portals leading into the dimension from other ones
+}
+
+// Dimension area layout from before areas had ids
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyRealmDimensionArea {
+    pub name: String,
+
+    pub area: crate::state::Rect,
+
+    pub tileset: String,
+    pub tilemap: String,
+}
+
+// Dimension layout deployed before masters were moved into RealmMembership PDAs,
+// legacy dimensions are rewritten along with their realm by migrate_realm_masters
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyRealmDimension {
+    pub realm: Pubkey,
+    pub owner: Pubkey,
+
+    pub name: String,
+
+    pub areas: Vec<LegacyRealmDimensionArea>,
+}
+
+impl RealmDimensionArea {
+    pub fn validate(&self) -> Result<()> {
+        require!(self.name.len() <= MAX_NAME_LEN, ErrorCode::NameTooLong);
+        require!(
+            self.tileset.len() <= MAX_RESOURCE_PATH_LEN
+                && self.tilemap.len() <= MAX_RESOURCE_PATH_LEN,
+            ErrorCode::ResourcePathTooLong
+        );
+        self.area.validate()
+    }
+}
+
+impl RealmDimension {
+    pub fn validate_areas(areas: &[RealmDimensionArea], allow_overlapping: bool) -> Result<()> {
+        for (i, area) in areas.iter().enumerate() {
+            area.validate()?;
+            require!(
+                !areas[..i].iter().any(|other| other.id == area.id),
+                ErrorCode::DuplicateAreaId
+            );
+
+            // Rects are inclusive, so areas sharing an edge overlap as well
+            require!(
+                allow_overlapping
+                    || !areas[..i]
+                        .iter()
+                        .any(|other| other.area.intersects(&area.area)),
+                ErrorCode::OverlappingAreas
+            );
+        }
+        Ok(())
+    }
+
+    pub fn area_index(&self, area_id: u32) -> Result<usize> {
+        self.areas
+            .iter()
+            .position(|area| area.id == area_id)
+            .ok_or(error!(ErrorCode::AreaNotFound))
+    }
+
+    pub fn contains(&self, position: &crate::state::Position) -> bool {
+        self.areas.iter().any(|area| area.area.contains(position))
+    }
+
+    pub fn encloses(&self, rect: &crate::state::Rect) -> bool {
+        self.areas.iter().any(|area| area.area.encloses(rect))
+    }
 }
 
 #[macro_export]
@@ -27,8 +107,9 @@ macro_rules! realm_dimension_space {
         let mut total_area_size = 0;
         for area in $areas {
             let area: &RealmDimensionArea = &area;
+            total_area_size += 4;                                           // id: u32
             total_area_size += 4 + area.name.len();                         // name: String (4 bytes prefix + content)
-            total_area_size += std::mem::size_of::<crate::state::Rect>();   // area: Rect (fixed size)
+            total_area_size += std::mem::size_of::<$crate::state::Rect>();   // area: Rect (fixed size)
             total_area_size += 4 + area.tileset.len();                      // tileset: String (4 bytes prefix + content)
             total_area_size += 4 + area.tilemap.len();                      // tilemap: String (4 bytes prefix + content)
         }
@@ -37,6 +118,10 @@ macro_rules! realm_dimension_space {
         32 +                                                                // realm pubkey
         32 +                                                                // owner pubkey
         4 + $name.len() +                                                   // name: String (4 bytes prefix + content)
-        4 + total_area_size                                                 // areas: Vec<RealmDimensionArea> (4 bytes prefix + content)
+        4 + total_area_size +                                               // areas: Vec<RealmDimensionArea> (4 bytes prefix + content)
+        1 +                                                                 // allow_overlapping_areas: bool
+        1 +                                                                 // has_walkability_grid: bool
+        4 +                                                                 // portals_count: u32
+        4                                                                   // incoming_portals_count: u32
     }};
 }
@@ -1,6 +1,10 @@
 use anchor_lang::prelude::*;
 
+use crate::constants::*;
+use crate::errors::ErrorCode;
 use crate::state::hero_stats::*;
+use crate::state::item::*;
+use crate::state::{Journey, Realm, RealmMembership};
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
 pub struct HeroDescription {
@@ -9,16 +13,33 @@ pub struct HeroDescription {
     pub lore: String,     // Background of the hero
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct XpCurve {
+    pub base_xp: u64,               // Experience required to reach level 2
+    pub xp_growth: u64,             // Additional experience required for each next level
+    pub stat_points_per_level: u64, // Stat points to spend on every level up
+}
+
 #[account]
 pub struct Hero {
     pub master: Pubkey, // Owner of the hero class
     pub description: HeroDescription,
-    pub base_stats: HeroStats, // Base stats for the hero class
+    pub tags: Vec<HeroTag>,          // Tags the hero class is composed of
+    pub base_stats: HeroStats,       // Base stats for the hero class
+    pub xp_curve: XpCurve,           // Leveling rules for the hero instances
+    pub stat_caps: HeroStats,        // Maximum values of the hero instances stats
+    pub trusted_realms: Vec<Pubkey>, // Realms whose masters can grant experience
 
     pub created_at: i64,
     pub updated_at: i64,
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct EquippedItem {
+    pub slot: EquipmentSlot,
+    pub item: Pubkey,
+}
+
 #[account]
 pub struct PlayerHero {
     pub player: Pubkey, // Owner of the hero instance
@@ -28,7 +49,9 @@ pub struct PlayerHero {
     pub level: u64, // Level
     pub xp: u64,    // Experience points
     pub stats: HeroStats,
-    pub items: Vec<Pubkey>, // Inventory of items (stored as item PDAs or unique IDs)
+    pub stat_points: u64,             // Unspent stat points
+    pub items: Vec<Pubkey>,           // Inventory of items (stored as item PDAs or unique IDs)
+    pub equipment: Vec<EquippedItem>, // Items from the inventory worn in slots
 
     pub created_at: i64,
     pub updated_at: i64,
@@ -36,13 +59,17 @@ pub struct PlayerHero {
 
 #[macro_export]
 macro_rules! hero_space {
-    ($description:expr) => {
+    ($description:expr, $tags_count:expr, $trusted_realms_count:expr) => {
         8 +                                 // discriminator
         32 +                                // master: pubkey
         4 + $description.name.len() +       // name: String prefix + content
         4 + $description.graphics.len() +   // graphics: String prefix + content
         4 + $description.lore.len() +       // lore: String prefix + content
+        4 + $tags_count +                   // tags: Vec<HeroTag> (4 bytes prefix + 1 byte per tag)
         std::mem::size_of::<HeroStats>() +  // base_stats: HeroStats
+        std::mem::size_of::<XpCurve>() +    // xp_curve: XpCurve
+        std::mem::size_of::<HeroStats>() +  // stat_caps: HeroStats
+        4 + $trusted_realms_count * 32 +    // trusted_realms: Vec<Pubkey> (4 bytes prefix + pubkeys)
         8 +                                 // created_at: i64
         8                                   // updated_at: i64
     };
@@ -50,7 +77,7 @@ macro_rules! hero_space {
 
 #[macro_export]
 macro_rules! player_hero_space {
-    ($name:expr, $items_count:expr) => {
+    ($name:expr, $items_count:expr, $equipment_count:expr) => {
         8 +                                 // discriminator
         32 +                                // player: pubkey
         32 +                                // hero: pubkey
@@ -58,8 +85,105 @@ macro_rules! player_hero_space {
         8 +                                 // level: u64
         8 +                                 // xp: u64
         std::mem::size_of::<HeroStats>() +  // stats: HeroStats (8 stats of u64)
+        8 +                                 // stat_points: u64
         4 + $items_count * 32 +             // items: Vec<Pubkey> (4 bytes prefix + items as pubkeys)
+        4 + $equipment_count * (1 + 32) +   // equipment: Vec<EquippedItem> (4 bytes prefix + slot & pubkey)
         8 +                                 // created_at: i64
         8                                   // updated_at: i64
     };
 }
+
+impl Hero {
+    pub fn validate_tags(tags: &[HeroTag]) -> Result<()> {
+        require!(tags.len() <= MAX_HERO_TAGS, ErrorCode::TooManyHeroTags);
+        require!(
+            !tags
+                .iter()
+                .enumerate()
+                .any(|(i, tag)| tags[..i].contains(tag)),
+            ErrorCode::DuplicateHeroTag
+        );
+        Ok(())
+    }
+
+    pub fn validate_trusted_realms(trusted_realms: &[Pubkey]) -> Result<()> {
+        require!(
+            trusted_realms.len() <= MAX_TRUSTED_REALMS,
+            ErrorCode::TooManyTrustedRealms
+        );
+        Ok(())
+    }
+
+    // Hero class masters reward any player hero of the class, masters of trusted realms
+    // only the voyagers currently journeying in their realm
+    pub fn can_reward(
+        &self,
+        authority: &Pubkey,
+        player_hero: &PlayerHero,
+        realm_master: Option<(
+            &Account<Realm>,
+            &Account<Journey>,
+            &Account<RealmMembership>,
+        )>,
+        permission: u32,
+    ) -> Result<bool> {
+        if self.master == *authority {
+            return Ok(true);
+        }
+
+        let Some((realm, journey, membership)) = realm_master else {
+            return Ok(false);
+        };
+        if journey.realm != realm.key() {
+            return Ok(false);
+        }
+        require!(journey.is_current(realm), ErrorCode::StaleJourney);
+
+        Ok(self.trusted_realms.contains(&realm.key())
+            && membership.realm == realm.key()
+            && journey.player == player_hero.player
+            && membership.master.pubkey == *authority
+            && membership.master.has_permission(permission))
+    }
+}
+
+impl Default for XpCurve {
+    fn default() -> Self {
+        Self {
+            base_xp: DEFAULT_BASE_XP,
+            xp_growth: DEFAULT_XP_GROWTH,
+            stat_points_per_level: DEFAULT_STAT_POINTS_PER_LEVEL,
+        }
+    }
+}
+
+impl XpCurve {
+    pub fn validate(&self) -> Result<()> {
+        require!(self.base_xp > 0, ErrorCode::InvalidXpCurve);
+        Ok(())
+    }
+
+    pub fn xp_for_next_level(&self, level: u64) -> Result<u64> {
+        level
+            .checked_sub(1)
+            .and_then(|levels| levels.checked_mul(self.xp_growth))
+            .and_then(|growth| growth.checked_add(self.base_xp))
+            .ok_or(error!(ErrorCode::ArithmeticOverflow))
+    }
+}
+
+impl PlayerHero {
+    pub fn equipped_item(&self, slot: EquipmentSlot) -> Option<Pubkey> {
+        self.equipment
+            .iter()
+            .find(|equipped| equipped.slot == slot)
+            .map(|equipped| equipped.item)
+    }
+
+    // An item can leave the inventory only if there is an unequipped copy of it
+    pub fn can_release_item(&self, item: &Pubkey) -> bool {
+        let carried = self.items.iter().filter(|i| *i == item).count();
+        let equipped = self.equipment.iter().filter(|e| e.item == *item).count();
+        carried > equipped
+    }
+}
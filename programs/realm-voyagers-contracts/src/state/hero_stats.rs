@@ -1,5 +1,9 @@
 use anchor_lang::prelude::*;
 
+use crate::errors::ErrorCode;
+use crate::state::hero::PlayerHero;
+use crate::state::item::Item;
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
 pub struct HeroStats {
     pub strength: u64,     // Melee damage and physical power
@@ -12,15 +16,15 @@ pub struct HeroStats {
     pub willpower: u64,    // Mental resilience and focus
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug, InitSpace)]
 pub enum HeroTag {
     Mage,
     Archer,
     Knight,
 }
 
-impl HeroStats {
-    pub fn default() -> Self {
+impl Default for HeroStats {
+    fn default() -> Self {
         Self {
             strength: 1,
             agility: 1,
@@ -32,7 +36,9 @@ impl HeroStats {
             willpower: 1,
         }
     }
+}
 
+impl HeroStats {
     pub fn zero() -> Self {
         Self {
             strength: 0,
@@ -46,6 +52,97 @@ impl HeroStats {
         }
     }
 
+    pub fn total(&self) -> Option<u64> {
+        self.strength
+            .checked_add(self.agility)?
+            .checked_add(self.dexterity)?
+            .checked_add(self.constitution)?
+            .checked_add(self.intelligence)?
+            .checked_add(self.wisdom)?
+            .checked_add(self.charisma)?
+            .checked_add(self.willpower)
+    }
+
+    pub fn uniform(value: u64) -> Self {
+        Self {
+            strength: value,
+            agility: value,
+            dexterity: value,
+            constitution: value,
+            intelligence: value,
+            wisdom: value,
+            charisma: value,
+            willpower: value,
+        }
+    }
+
+    fn try_combine(&self, other: &Self, f: impl Fn(u64, u64) -> Option<u64>) -> Option<Self> {
+        Some(Self {
+            strength: f(self.strength, other.strength)?,
+            agility: f(self.agility, other.agility)?,
+            dexterity: f(self.dexterity, other.dexterity)?,
+            constitution: f(self.constitution, other.constitution)?,
+            intelligence: f(self.intelligence, other.intelligence)?,
+            wisdom: f(self.wisdom, other.wisdom)?,
+            charisma: f(self.charisma, other.charisma)?,
+            willpower: f(self.willpower, other.willpower)?,
+        })
+    }
+
+    fn combine(&self, other: &Self, f: impl Fn(u64, u64) -> u64) -> Self {
+        Self {
+            strength: f(self.strength, other.strength),
+            agility: f(self.agility, other.agility),
+            dexterity: f(self.dexterity, other.dexterity),
+            constitution: f(self.constitution, other.constitution),
+            intelligence: f(self.intelligence, other.intelligence),
+            wisdom: f(self.wisdom, other.wisdom),
+            charisma: f(self.charisma, other.charisma),
+            willpower: f(self.willpower, other.willpower),
+        }
+    }
+
+    pub fn checked_add(&self, other: &Self) -> Option<Self> {
+        self.try_combine(other, u64::checked_add)
+    }
+
+    pub fn saturating_add(&self, other: &Self) -> Self {
+        self.combine(other, u64::saturating_add)
+    }
+
+    pub fn checked_sub(&self, other: &Self) -> Option<Self> {
+        self.try_combine(other, u64::checked_sub)
+    }
+
+    pub fn saturating_sub(&self, other: &Self) -> Self {
+        self.combine(other, u64::saturating_sub)
+    }
+
+    // Every stat clamped to the corresponding cap
+    pub fn capped(&self, caps: &Self) -> Self {
+        self.combine(caps, u64::min)
+    }
+
+    // Checked addition which keeps every raised stat within its cap, untouched stats
+    // may stay above a cap lowered after they were allocated
+    pub fn checked_add_capped(&self, other: &Self, caps: &Self) -> Result<Self> {
+        let stats = self.checked_add(other).ok_or(ErrorCode::StatOverflow)?;
+        let raised_caps = caps.combine(other, |cap, raise| if raise == 0 { u64::MAX } else { cap });
+        require!(raised_caps.meets(&stats), ErrorCode::StatOverflow);
+        Ok(stats)
+    }
+
+    pub fn meets(&self, requirements: &Self) -> bool {
+        self.strength >= requirements.strength
+            && self.agility >= requirements.agility
+            && self.dexterity >= requirements.dexterity
+            && self.constitution >= requirements.constitution
+            && self.intelligence >= requirements.intelligence
+            && self.wisdom >= requirements.wisdom
+            && self.charisma >= requirements.charisma
+            && self.willpower >= requirements.willpower
+    }
+
     pub fn with_strength(mut self, value: u64) -> Self {
         self.strength = value;
         self
@@ -87,24 +184,13 @@ impl HeroStats {
     }
 }
 
-impl std::ops::Add for HeroStats {
-    type Output = Self;
-
-    fn add(self, other: Self) -> Self {
-        Self {
-            strength: self.strength + other.strength,
-            agility: self.agility + other.agility,
-            dexterity: self.dexterity + other.dexterity,
-            constitution: self.constitution + other.constitution,
-            intelligence: self.intelligence + other.intelligence,
-            wisdom: self.wisdom + other.wisdom,
-            charisma: self.charisma + other.charisma,
-            willpower: self.willpower + other.willpower,
-        }
+impl HeroTag {
+    pub fn compose_stats(tags: &[HeroTag]) -> HeroStats {
+        tags.iter().fold(HeroStats::zero(), |stats, tag| {
+            stats.saturating_add(&tag.base_stats())
+        })
     }
-}
 
-impl HeroTag {
     pub fn base_stats(&self) -> HeroStats {
         match self {
             HeroTag::Mage => HeroStats::default()
@@ -122,3 +208,12 @@ impl HeroTag {
         }
     }
 }
+
+// Hero stats with the modifiers of the equipped items applied
+pub fn effective_stats(player_hero: &PlayerHero, equipped_items: &[Item]) -> HeroStats {
+    equipped_items
+        .iter()
+        .fold(player_hero.stats.clone(), |stats, item| {
+            stats.saturating_add(&item.modifiers)
+        })
+}
@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::state::hero_stats::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct ItemDescription {
+    pub name: String,     // Name of the item class
+    pub graphics: String, // URL or IPFS hash for the item graphics
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub enum ItemRarity {
+    Common,
+    Uncommon,
+    Rare,
+    Epic,
+    Legendary,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum EquipmentSlot {
+    Head,
+    Body,
+    Hands,
+    Legs,
+    Feet,
+    MainHand,
+    OffHand,
+    Trinket,
+}
+
+#[account]
+pub struct Item {
+    pub master: Pubkey, // Owner of the item class
+    pub description: ItemDescription,
+    pub rarity: ItemRarity,
+    pub slot: Option<EquipmentSlot>, // Slot the item can be equipped into
+    pub modifiers: HeroStats,        // Stats added to the hero wearing the item
+    pub requirements: HeroStats,     // Minimal effective stats to equip the item
+
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+#[macro_export]
+macro_rules! item_space {
+    ($description:expr) => {
+        8 +                                 // discriminator
+        32 +                                // master: pubkey
+        4 + $description.name.len() +       // name: String prefix + content
+        4 + $description.graphics.len() +   // graphics: String prefix + content
+        1 +                                 // rarity: ItemRarity
+        1 + 1 +                             // slot: Option<EquipmentSlot>
+        std::mem::size_of::<HeroStats>() +  // modifiers: HeroStats
+        std::mem::size_of::<HeroStats>() +  // requirements: HeroStats
+        8 +                                 // created_at: i64
+        8                                   // updated_at: i64
+    };
+}
+
+impl ItemDescription {
+    pub fn validate(&self) -> Result<()> {
+        require!(self.name.len() <= MAX_NAME_LEN, ErrorCode::NameTooLong);
+        require!(
+            self.graphics.len() <= MAX_RESOURCE_PATH_LEN,
+            ErrorCode::ResourcePathTooLong
+        );
+        Ok(())
+    }
+}
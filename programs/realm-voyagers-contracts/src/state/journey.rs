@@ -1,12 +1,22 @@
 use anchor_lang::prelude::*;
 
+use crate::state::*;
+
 #[derive(InitSpace)]
 #[account]
 pub struct Journey {
     pub realm: Pubkey,
     pub player: Pubkey,
     pub started_at: i64,
+    pub nonce: u64, // Tells apart journeys restarted within the same second
 
     pub dimension: Pubkey,
     pub position: crate::state::Position,
 }
+
+impl Journey {
+    // Journeys outlive deleted realms and can't be resumed in a realm recreated under the same id
+    pub fn is_current(&self, realm: &Realm) -> bool {
+        self.started_at >= realm.created_at
+    }
+}
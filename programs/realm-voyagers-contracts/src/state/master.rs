@@ -7,38 +7,101 @@ pub enum RealmMasterRole {
     Curator = 2,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct RealmPermissions;
+
+impl RealmPermissions {
+    pub const UPDATE_REALM: u32 = 1 << 0;
+    pub const DELETE_REALM: u32 = 1 << 1;
+    pub const MANAGE_MASTERS: u32 = 1 << 2;
+    pub const ADD_DIMENSIONS: u32 = 1 << 3;
+    pub const MANAGE_OWN_DIMENSIONS: u32 = 1 << 4;
+    pub const MANAGE_ALL_DIMENSIONS: u32 = 1 << 5;
+    pub const SET_STARTING_POINT: u32 = 1 << 6;
+    pub const MODERATE_VOYAGERS: u32 = 1 << 7;
+    pub const GRANT_XP: u32 = 1 << 8;
+    pub const APPROVE_PROPOSALS: u32 = 1 << 9;
+    pub const GRANT_ITEMS: u32 = 1 << 10;
+
+    pub const ALL: u32 = Self::UPDATE_REALM
+        | Self::DELETE_REALM
+        | Self::MANAGE_MASTERS
+        | Self::ADD_DIMENSIONS
+        | Self::MANAGE_OWN_DIMENSIONS
+        | Self::MANAGE_ALL_DIMENSIONS
+        | Self::SET_STARTING_POINT
+        | Self::MODERATE_VOYAGERS
+        | Self::GRANT_XP
+        | Self::APPROVE_PROPOSALS
+        | Self::GRANT_ITEMS;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
 pub struct RealmMaster {
     pub pubkey: Pubkey,
     pub role: RealmMasterRole,
+    pub permissions: u32, // Bitmask of RealmPermissions
 }
 
-impl RealmMaster {
-    pub fn can_update_realm(&self) -> bool {
-        matches!(self.role, RealmMasterRole::Owner | RealmMasterRole::Admin)
+// Realm master layout from before permissions were stored per master
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct LegacyRealmMaster {
+    pub pubkey: Pubkey,
+    pub role: RealmMasterRole,
+}
+
+// Membership of a master in a realm, one PDA per master
+#[derive(InitSpace)]
+#[account]
+pub struct RealmMembership {
+    pub realm: Pubkey,
+    pub master: RealmMaster,
+    pub dimensions_count: u32, // Dimensions owned by the master
+    pub created_at: i64,
+}
+
+impl RealmMasterRole {
+    // Permissions every role starts with
+    pub fn permissions(&self) -> u32 {
+        match self {
+            RealmMasterRole::Owner => RealmPermissions::ALL,
+            RealmMasterRole::Admin => {
+                RealmPermissions::UPDATE_REALM
+                    | RealmPermissions::ADD_DIMENSIONS
+                    | RealmPermissions::MANAGE_OWN_DIMENSIONS
+                    | RealmPermissions::MODERATE_VOYAGERS
+                    | RealmPermissions::GRANT_XP
+                    | RealmPermissions::GRANT_ITEMS
+            }
+            RealmMasterRole::Curator => 0,
+        }
     }
+}
 
-    pub fn can_delete_realm(&self) -> bool {
-        matches!(self.role, RealmMasterRole::Owner)
+impl RealmMaster {
+    pub fn new(pubkey: Pubkey, role: RealmMasterRole) -> Self {
+        Self {
+            pubkey,
+            permissions: role.permissions(),
+            role,
+        }
     }
 
-    pub fn can_manage_realm_masters(&self) -> bool {
-        matches!(self.role, RealmMasterRole::Owner)
+    pub fn set_role(&mut self, role: RealmMasterRole) {
+        self.permissions = role.permissions();
+        self.role = role;
     }
 
-    pub fn can_add_realm_dimension(&self) -> bool {
-        matches!(self.role, RealmMasterRole::Owner | RealmMasterRole::Admin)
+    pub fn is_owner(&self) -> bool {
+        self.role == RealmMasterRole::Owner
     }
 
-    pub fn can_manage_realm_dimension(&self, dimension: &crate::state::RealmDimension) -> bool {
-        match self.role {
-            RealmMasterRole::Owner => true,
-            RealmMasterRole::Admin => dimension.owner == self.pubkey,
-            RealmMasterRole::Curator => false,
-        }
+    pub fn has_permission(&self, permission: u32) -> bool {
+        self.permissions & permission == permission
     }
 
-    pub fn can_set_realm_starting_point(&self) -> bool {
-        matches!(self.role, RealmMasterRole::Owner)
+    pub fn can_manage_realm_dimension(&self, dimension: &crate::state::RealmDimension) -> bool {
+        self.has_permission(RealmPermissions::MANAGE_ALL_DIMENSIONS)
+            || (self.has_permission(RealmPermissions::MANAGE_OWN_DIMENSIONS)
+                && dimension.owner == self.pubkey)
     }
 }
@@ -2,14 +2,30 @@ pub mod common;
 pub mod dimension;
 pub mod hero;
 pub mod hero_stats;
+pub mod item;
 pub mod journey;
 pub mod master;
+pub mod portal;
+pub mod proposal;
 pub mod realm;
+pub mod session;
+pub mod spawn_point;
+pub mod timelock;
+pub mod visibility;
+pub mod walkability;
 
 pub use common::*;
 pub use dimension::*;
 pub use hero::*;
 pub use hero_stats::*;
+pub use item::*;
 pub use journey::*;
 pub use master::*;
+pub use portal::*;
+pub use proposal::*;
 pub use realm::*;
+pub use session::*;
+pub use spawn_point::*;
+pub use timelock::*;
+pub use visibility::*;
+pub use walkability::*;
@@ -0,0 +1,14 @@
+use anchor_lang::prelude::*;
+
+#[derive(InitSpace)]
+#[account]
+pub struct RealmPortal {
+    pub realm: Pubkey,
+    pub dimension: Pubkey, // Source dimension
+    pub area: crate::state::Rect,
+
+    pub target_dimension: Pubkey,
+    pub target_position: crate::state::Position,
+
+    pub creator: Pubkey, // Refunded when the portal is closed
+}
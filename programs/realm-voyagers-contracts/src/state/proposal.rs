@@ -0,0 +1,169 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::state::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub enum RealmProposalAction {
+    DeleteRealm,
+    TransferOwnership {
+        new_owner: Pubkey,
+    },
+    SetApprovalThreshold {
+        threshold: u8,
+    },
+    AddMaster {
+        master: Pubkey,
+        role: RealmMasterRole,
+    },
+    SetMasterRole {
+        master: Pubkey,
+        role: RealmMasterRole,
+    },
+    UpdateMasterPermissions {
+        master: Pubkey,
+        granted: u32,
+        revoked: u32,
+    },
+    RemoveMaster {
+        master: Pubkey,
+    },
+}
+
+#[derive(InitSpace)]
+#[account]
+pub struct RealmProposal {
+    pub realm: Pubkey,
+    pub proposer: Pubkey,
+    pub action: RealmProposalAction,
+
+    #[max_len(MAX_PROPOSAL_VOTES)]
+    pub approvals: Vec<Pubkey>,
+    #[max_len(MAX_PROPOSAL_VOTES)]
+    pub rejections: Vec<Pubkey>,
+
+    pub created_at: i64,
+}
+
+impl RealmMaster {
+    pub fn is_approver(&self) -> bool {
+        self.has_permission(RealmPermissions::APPROVE_PROPOSALS)
+    }
+}
+
+impl RealmMembership {
+    // Collects the masters of the realm who can currently approve from their memberships,
+    // any other account is skipped so approvers can be mixed with unrelated accounts
+    pub fn load_approvers(realm: &Pubkey, accounts: &[AccountInfo]) -> Vec<Pubkey> {
+        let mut approvers: Vec<Pubkey> = vec![];
+        for account_info in accounts.iter() {
+            if *account_info.owner != crate::ID {
+                continue;
+            }
+
+            let Ok(data) = account_info.try_borrow_data() else {
+                continue;
+            };
+            let Ok(membership) = RealmMembership::try_deserialize(&mut &data[..]) else {
+                continue;
+            };
+
+            if membership.realm == *realm
+                && membership.master.is_approver()
+                && !approvers.contains(&membership.master.pubkey)
+            {
+                approvers.push(membership.master.pubkey);
+            }
+        }
+        approvers
+    }
+}
+
+impl Realm {
+    // Approvers are proven by their membership accounts
+    pub fn validate_approval_threshold(&self, threshold: u8, approvers_count: usize) -> Result<()> {
+        require!(
+            threshold as usize <= approvers_count.min(MAX_PROPOSAL_VOTES),
+            ErrorCode::InvalidApprovalThreshold
+        );
+        Ok(())
+    }
+
+    // An approver leaving must not lock the realm out of reaching its threshold
+    pub fn require_approvers_left(&self, leaving: &Pubkey, approvers: &[Pubkey]) -> Result<()> {
+        let approvers_left = approvers
+            .iter()
+            .filter(|approver| *approver != leaving)
+            .count();
+        require!(
+            approvers_left >= self.approval_threshold as usize,
+            ErrorCode::ApprovalThresholdUnreachable
+        );
+        Ok(())
+    }
+
+    // Actions are executable directly unless the realm has an approval policy,
+    // votes then only count for the approvers loaded from their memberships
+    pub fn require_approval(
+        &self,
+        proposal: Option<&RealmProposal>,
+        action: &RealmProposalAction,
+        approvers: &[Pubkey],
+    ) -> Result<()> {
+        if let Some(proposal) = proposal {
+            proposal.validate_for(self, action)?;
+        }
+        if self.approval_threshold == 0 {
+            return Ok(());
+        }
+
+        let proposal = proposal.ok_or(ErrorCode::ProposalRequired)?;
+        require!(
+            !proposal.is_rejected(approvers),
+            ErrorCode::ProposalRejected
+        );
+        require!(
+            proposal.is_approved(self, approvers),
+            ErrorCode::ProposalNotApproved
+        );
+        Ok(())
+    }
+}
+
+impl RealmProposal {
+    // Proposals outlive deleted realms and can't be executed in a realm recreated under the same id
+    pub fn is_current(&self, realm: &Realm) -> bool {
+        self.created_at >= realm.created_at
+    }
+
+    // A supplied proposal is closed on execution, so it has to match even when not required
+    pub fn validate_for(&self, realm: &Realm, action: &RealmProposalAction) -> Result<()> {
+        require!(self.is_current(realm), ErrorCode::StaleProposal);
+        require!(self.action == *action, ErrorCode::ProposalActionMismatch);
+        Ok(())
+    }
+
+    // Only votes of masters who can still approve are counted
+    pub fn approvals_count(&self, approvers: &[Pubkey]) -> usize {
+        self.approvals
+            .iter()
+            .filter(|pubkey| approvers.contains(pubkey))
+            .count()
+    }
+
+    pub fn is_approved(&self, realm: &Realm, approvers: &[Pubkey]) -> bool {
+        self.approvals_count(approvers) >= realm.approval_threshold as usize
+    }
+
+    // A single rejection vetoes the proposal, it has to be cancelled and proposed again
+    pub fn is_rejected(&self, approvers: &[Pubkey]) -> bool {
+        self.rejections
+            .iter()
+            .any(|pubkey| approvers.contains(pubkey))
+    }
+
+    pub fn has_voted(&self, pubkey: &Pubkey) -> bool {
+        self.approvals.contains(pubkey) || self.rejections.contains(pubkey)
+    }
+}
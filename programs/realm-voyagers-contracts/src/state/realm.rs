@@ -10,6 +10,13 @@ pub struct RealmDescription {
     pub logo: String,
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RealmStatus {
+    #[default]
+    Active,
+    Closing, // Teardown started, dimensions are being closed in batches
+}
+
 #[account]
 pub struct Realm {
     pub description: RealmDescription,
@@ -17,7 +24,33 @@ pub struct Realm {
     pub created_at: i64,
     pub updated_at: i64,
 
-    pub masters: Vec<crate::state::RealmMaster>,
+    pub masters: Vec<crate::state::RealmMaster>, // Legacy, moved out into RealmMembership PDAs
+    pub pending_owner: Option<Pubkey>,           // Proposed owner, awaiting acceptance
+    pub approval_threshold: u8, // Approvals required for destructive actions, 0 disables
+    pub timelock: i64,          // Delay in seconds before sensitive actions, 0 disables
+    pub dimensions: Vec<Pubkey>,
+
+    pub starting_dimension: Option<Pubkey>,
+    pub starting_position: crate::state::Position,
+
+    // Legacy realms are rewritten into this layout by migrate_realm_masters
+    pub owner: Pubkey,
+    pub masters_count: u32,
+    pub status: RealmStatus,
+    pub visibility: crate::state::RealmVisibility,
+    pub spawn_points_count: u32,
+    pub journeys_count: u64, // Nonce of the last started journey
+}
+
+// Realm layout deployed before masters were moved into RealmMembership PDAs
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyRealm {
+    pub description: RealmDescription,
+
+    pub created_at: i64,
+    pub updated_at: i64,
+
+    pub masters: Vec<crate::state::LegacyRealmMaster>,
     pub dimensions: Vec<Pubkey>,
 
     pub starting_dimension: Option<Pubkey>,
@@ -34,13 +67,43 @@ macro_rules! realm_space {
         4 + $description.logo.len() +                                               // String prefix + content
         8 +                                                                         // i64 timestamp
         8 +                                                                         // i64 timestamp
-        4 + $masters_count * std::mem::size_of::<crate::state::RealmMaster>() +     // Vec prefix + content
+        4 + $masters_count * std::mem::size_of::<$crate::state::RealmMaster>() +     // Vec prefix + content
+        1 + std::mem::size_of::<Pubkey>() +                                         // Option + Pubkey
+        1 +                                                                         // u8 threshold
+        8 +                                                                         // i64 timelock
         4 + $dimension_count * std::mem::size_of::<Pubkey>() +                      // Vec prefix + content
         1 + std::mem::size_of::<Pubkey>() +                                         // Option + Pubkey
-        std::mem::size_of::<crate::state::Position>()                               // Position
+        std::mem::size_of::<$crate::state::Position>() +                             // Position
+        std::mem::size_of::<Pubkey>() +                                             // Pubkey owner
+        4 +                                                                         // u32 masters count
+        1 +                                                                         // RealmStatus
+        1 + 32 +                                                                    // RealmVisibility + allowlist root
+        4 +                                                                         // u32 spawn points count
+        8                                                                           // u64 journeys count
     };
 }
 
+impl Realm {
+    pub fn is_active(&self) -> bool {
+        self.status == RealmStatus::Active
+    }
+
+    // Edits of the starting dimension must keep the starting position within its areas
+    pub fn validate_starting_point(
+        &self,
+        dimension_pubkey: &Pubkey,
+        dimension: &crate::state::RealmDimension,
+    ) -> Result<()> {
+        if self.starting_dimension == Some(*dimension_pubkey) {
+            require!(
+                dimension.contains(&self.starting_position),
+                ErrorCode::StartingPointOutsideDimension
+            );
+        }
+        Ok(())
+    }
+}
+
 impl RealmDescription {
     pub fn validate(&self) -> Result<()> {
         require!(self.name.len() <= MAX_NAME_LEN, ErrorCode::NameTooLong);
@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::state::*;
+
+pub struct SessionScope;
+
+impl SessionScope {
+    pub const MOVE_HERO: u8 = 1 << 0;
+    pub const TRAVEL_THROUGH_PORTAL: u8 = 1 << 1;
+
+    pub const ALL: u8 = Self::MOVE_HERO | Self::TRAVEL_THROUGH_PORTAL;
+}
+
+#[derive(InitSpace)]
+#[account]
+pub struct SessionKey {
+    pub realm: Pubkey,
+    pub journey: Pubkey,
+    pub player: Pubkey,
+    pub authority: Pubkey,       // Delegated signer
+    pub journey_started_at: i64, // Keys of an ended journey don't carry over to the next one
+    pub journey_nonce: u64,
+
+    pub scope: u8, // Bitmask of SessionScope
+    pub created_at: i64,
+    pub expires_at: i64,
+}
+
+impl SessionKey {
+    pub fn validate(scope: u8, expires_at: i64, now: i64) -> Result<()> {
+        require!(
+            scope != 0 && scope & !SessionScope::ALL == 0,
+            ErrorCode::InvalidSessionScope
+        );
+        require!(
+            expires_at > now && expires_at - now <= MAX_SESSION_DURATION,
+            ErrorCode::InvalidSessionExpiry
+        );
+        Ok(())
+    }
+
+    pub fn is_expired(&self, now: i64) -> bool {
+        now >= self.expires_at
+    }
+
+    pub fn allows(&self, scope: u8) -> bool {
+        self.scope & scope == scope
+    }
+}
+
+impl Journey {
+    // Journey actions are signed by the player or one of their session keys
+    pub fn require_authority(
+        &self,
+        authority: &Pubkey,
+        session_key: Option<&SessionKey>,
+        scope: u8,
+        now: i64,
+    ) -> Result<()> {
+        if *authority == self.player {
+            return Ok(());
+        }
+
+        let session_key = session_key.ok_or(ErrorCode::UnauthorizedJourneyAuthority)?;
+        require!(
+            session_key.authority == *authority && session_key.player == self.player,
+            ErrorCode::UnauthorizedJourneyAuthority
+        );
+        require!(
+            session_key.journey_started_at == self.started_at
+                && session_key.journey_nonce == self.nonce,
+            ErrorCode::StaleSessionKey
+        );
+        require!(!session_key.is_expired(now), ErrorCode::SessionKeyExpired);
+        require!(session_key.allows(scope), ErrorCode::SessionScopeNotAllowed);
+        Ok(())
+    }
+}
@@ -0,0 +1,86 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::state::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub struct SpawnPointSettings {
+    #[max_len(MAX_NAME_LEN)]
+    pub name: String,
+    pub position: Position,
+
+    pub weight: u32, // Odds of being picked for players without a choice, 0 for explicit choice only
+
+    // Requirements of the player hero, none when zeroed
+    pub min_level: u64,
+    pub hero_tag: Option<HeroTag>,
+}
+
+#[derive(InitSpace)]
+#[account]
+pub struct RealmSpawnPoint {
+    pub realm: Pubkey,
+    pub dimension: Pubkey,
+    pub settings: SpawnPointSettings,
+}
+
+impl SpawnPointSettings {
+    pub fn validate(&self, dimension: &RealmDimension) -> Result<()> {
+        require!(self.name.len() <= MAX_NAME_LEN, ErrorCode::NameTooLong);
+        require!(
+            dimension.contains(&self.position),
+            ErrorCode::PositionOutsideDimension
+        );
+        Ok(())
+    }
+
+    pub fn allows(&self, level: u64, tags: &[HeroTag]) -> bool {
+        let has_tag = match &self.hero_tag {
+            Some(hero_tag) => tags.contains(hero_tag),
+            None => true,
+        };
+        level >= self.min_level && has_tag
+    }
+}
+
+impl RealmSpawnPoint {
+    // Spawn points left in removed dimensions can't be used anymore
+    pub fn allows(&self, realm: &Realm, level: u64, tags: &[HeroTag]) -> bool {
+        realm.dimensions.contains(&self.dimension) && self.settings.allows(level, tags)
+    }
+
+    // Weighted pick among the allowed spawn points, stable for a given player.
+    // Spawn points are expected sorted by key so the pick doesn't depend on their order
+    pub fn pick<'a, 'info>(
+        spawn_points: &'a [Account<'info, RealmSpawnPoint>],
+        realm: &Account<Realm>,
+        player: &Pubkey,
+        level: u64,
+        tags: &[HeroTag],
+    ) -> Option<&'a Account<'info, RealmSpawnPoint>> {
+        let mut allowed = spawn_points
+            .iter()
+            .filter(|spawn_point| spawn_point.settings.weight > 0)
+            .filter(|spawn_point| spawn_point.allows(realm, level, tags));
+        let total_weight: u64 = allowed
+            .clone()
+            .map(|spawn_point| spawn_point.settings.weight as u64)
+            .sum();
+        if total_weight == 0 {
+            return None;
+        }
+
+        let seed = hashv(&[realm.key().as_ref(), player.as_ref()]).to_bytes();
+        let mut roll = u64::from_le_bytes(seed[..8].try_into().unwrap()) % total_weight;
+        allowed.find(|spawn_point| {
+            let weight = spawn_point.settings.weight as u64;
+            if roll < weight {
+                return true;
+            }
+            roll -= weight;
+            false
+        })
+    }
+}
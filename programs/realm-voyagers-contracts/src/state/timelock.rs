@@ -0,0 +1,91 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::state::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub enum RealmTimelockAction {
+    DeleteRealm,
+    RemoveDimension { dimension: Pubkey },
+    TransferOwnership { new_owner: Pubkey },
+    SetTimelock { timelock: i64 },
+}
+
+#[derive(InitSpace)]
+#[account]
+pub struct RealmQueuedAction {
+    pub realm: Pubkey,
+    pub queued_by: Pubkey,
+    pub action: RealmTimelockAction,
+
+    pub queued_at: i64,
+    pub execute_after: i64,
+}
+
+impl RealmTimelockAction {
+    pub fn permission(&self) -> u32 {
+        match self {
+            RealmTimelockAction::DeleteRealm => RealmPermissions::DELETE_REALM,
+            RealmTimelockAction::RemoveDimension { .. } => {
+                RealmPermissions::MANAGE_OWN_DIMENSIONS | RealmPermissions::MANAGE_ALL_DIMENSIONS
+            }
+            RealmTimelockAction::TransferOwnership { .. } => RealmPermissions::MANAGE_MASTERS,
+            RealmTimelockAction::SetTimelock { .. } => RealmPermissions::MANAGE_MASTERS,
+        }
+    }
+}
+
+impl RealmQueuedAction {
+    // Queued actions outlive deleted realms and can't be executed in a realm recreated under the same id
+    pub fn is_current(&self, realm: &Realm) -> bool {
+        self.queued_at >= realm.created_at
+    }
+
+    // A supplied queued action is closed on execution, so it has to match even when not required
+    pub fn validate_for(&self, realm: &Realm, action: &RealmTimelockAction) -> Result<()> {
+        require!(self.is_current(realm), ErrorCode::StaleQueuedAction);
+        require!(self.action == *action, ErrorCode::QueuedActionMismatch);
+        Ok(())
+    }
+}
+
+impl RealmMaster {
+    // Masters holding any of the action permissions may queue it
+    pub fn can_queue_action(&self, action: &RealmTimelockAction) -> bool {
+        self.permissions & action.permission() != 0
+    }
+}
+
+impl Realm {
+    pub fn validate_timelock(timelock: i64) -> Result<()> {
+        require!(
+            (0..=MAX_TIMELOCK).contains(&timelock),
+            ErrorCode::InvalidTimelock
+        );
+        Ok(())
+    }
+
+    // Actions are executable directly unless the realm has a timelock
+    pub fn require_timelock(
+        &self,
+        queued_action: Option<&RealmQueuedAction>,
+        action: &RealmTimelockAction,
+        now: i64,
+    ) -> Result<()> {
+        if let Some(queued_action) = queued_action {
+            queued_action.validate_for(self, action)?;
+        }
+        if self.timelock == 0 {
+            return Ok(());
+        }
+
+        let queued_action = queued_action.ok_or(ErrorCode::TimelockRequired)?;
+        // A timelock raised after queueing also delays already queued actions
+        let execute_after = queued_action
+            .execute_after
+            .max(queued_action.queued_at.saturating_add(self.timelock));
+        require!(now >= execute_after, ErrorCode::TimelockNotElapsed);
+        Ok(())
+    }
+}
@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+use crate::state::*;
+
+const ALLOWLIST_LEAF_PREFIX: &[u8] = &[0];
+const ALLOWLIST_NODE_PREFIX: &[u8] = &[1];
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RealmVisibility {
+    #[default]
+    Public,
+    Unlisted, // Joinable by anyone, but not meant to be shown in realm listings
+    InviteOnly {
+        allowlist_root: [u8; 32],
+    }, // Merkle root of the invited players
+}
+
+impl Realm {
+    // Invited players prove their membership of the allowlist Merkle tree
+    pub fn is_invited(&self, player: &Pubkey, proof: &[[u8; 32]]) -> bool {
+        let RealmVisibility::InviteOnly { allowlist_root } = self.visibility else {
+            return true;
+        };
+
+        let leaf = hashv(&[ALLOWLIST_LEAF_PREFIX, player.as_ref()]).to_bytes();
+        let root = proof.iter().fold(leaf, |node, sibling| {
+            // Pairs are hashed in sorted order so proofs don't need to carry positions
+            let (left, right) = if node <= *sibling {
+                (node, *sibling)
+            } else {
+                (*sibling, node)
+            };
+            hashv(&[ALLOWLIST_NODE_PREFIX, &left, &right]).to_bytes()
+        });
+        root == allowlist_root
+    }
+}
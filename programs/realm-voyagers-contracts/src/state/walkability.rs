@@ -0,0 +1,101 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::state::*;
+
+#[account]
+pub struct WalkabilityGrid {
+    pub realm: Pubkey,
+    pub dimension: Pubkey,
+
+    pub origin: Position, // Position of the first cell
+    pub width: u32,
+    pub height: u32,
+
+    pub blocked: Vec<u8>, // Row-major bitmap of the cells, set bits are walls
+}
+
+#[macro_export]
+macro_rules! walkability_grid_space {
+    ($width:expr, $height:expr) => {
+        8 +                                                         // discriminator
+        32 +                                                        // realm pubkey
+        32 +                                                        // dimension pubkey
+        std::mem::size_of::<$crate::state::Position>() +             // origin: Position
+        4 +                                                         // width: u32
+        4 +                                                         // height: u32
+        4 + $crate::state::WalkabilityGrid::bitmap_len($width, $height) // blocked: Vec<u8> (4 bytes prefix + content)
+    };
+}
+
+impl WalkabilityGrid {
+    pub fn bitmap_len(width: u32, height: u32) -> usize {
+        (width as usize * height as usize).div_ceil(8)
+    }
+
+    pub fn validate(origin: &Position, width: u32, height: u32) -> Result<()> {
+        require!(
+            width > 0 && height > 0 && width as u64 * height as u64 <= MAX_WALKABILITY_GRID_CELLS,
+            ErrorCode::InvalidWalkabilityGridSize
+        );
+        let bounds = -MAX_COORDINATE..=MAX_COORDINATE;
+        require!(
+            bounds.contains(&origin.x)
+                && bounds.contains(&origin.y)
+                && bounds.contains(&(origin.x + width as i32 - 1))
+                && bounds.contains(&(origin.y + height as i32 - 1)),
+            ErrorCode::AreaOutOfBounds
+        );
+        Ok(())
+    }
+
+    pub fn write_chunk(&mut self, offset: u32, chunk: &[u8]) -> Result<()> {
+        let start = offset as usize;
+        let end = start
+            .checked_add(chunk.len())
+            .filter(|end| *end <= self.blocked.len())
+            .ok_or(ErrorCode::WalkabilityChunkOutOfBounds)?;
+        self.blocked[start..end].copy_from_slice(chunk);
+        Ok(())
+    }
+
+    // Cells outside of the grid are left to the dimension areas
+    pub fn is_walkable(&self, position: &Position) -> bool {
+        let x = position.x as i64 - self.origin.x as i64;
+        let y = position.y as i64 - self.origin.y as i64;
+        if x < 0 || y < 0 || x >= self.width as i64 || y >= self.height as i64 {
+            return true;
+        }
+
+        let index = (y * self.width as i64 + x) as usize;
+        self.blocked[index / 8] & (1 << (index % 8)) == 0
+    }
+
+    // Walks every cell of the straight line between both positions, so walls can't be jumped over
+    pub fn is_path_walkable(&self, from: &Position, to: &Position) -> bool {
+        let (dx, dy) = ((to.x - from.x).abs(), -(to.y - from.y).abs());
+        let (sx, sy) = ((to.x - from.x).signum(), (to.y - from.y).signum());
+        let mut error = dx + dy;
+        let mut position = *from;
+
+        loop {
+            if !self.is_walkable(&position) {
+                return false;
+            }
+            if position == *to {
+                return true;
+            }
+
+            let doubled_error = 2 * error;
+            if doubled_error >= dy {
+                error += dy;
+                position.x += sx;
+            }
+            if doubled_error <= dx {
+                error += dx;
+                position.y += sy;
+            }
+        }
+    }
+}
@@ -0,0 +1,200 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { expect } from "chai";
+
+import { RealmVoyagers } from "../target/types/realm_voyagers";
+
+import * as helper from "./utils/helpers";
+import * as steps from "./utils/common-steps";
+
+describe("Dimension area editing", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  // Program & keypairs
+  const program = anchor.workspace.RealmVoyagers as Program<RealmVoyagers>;
+  const realmMaster = anchor.web3.Keypair.generate();
+  const stranger = anchor.web3.Keypair.generate();
+
+  // Realm data
+  const realmId = "growing_realm";
+  const realmDescription = { name: "Growing Realm", details: "A realm built area by area", logo: "https://example.com/logo123" };
+  const makeArea = (id: number, name: string, x: number) => ({
+    "id": id,
+    "name": name,
+    "area": { "topLeft": { "x": x, "y": 0 }, "bottomRight": { "x": x + 99, "y": 100 } },
+    "tileset": "https://example.com/tileset.png",
+    "tilemap": "https://example.com/tilemap.json"
+  });
+  const dimension = { id: "frontier", name: "Frontier", areas: [makeArea(1, "Camp", 0)] };
+  const dimensionPDA = () => helper.getDimensionPDA(realmId, dimension.id, program);
+
+  // Listen events
+  let listeners = [];
+  let events = [];
+
+  before(async () => {
+    listeners.push(program.addEventListener("realmDimensionEvent", (event) => {
+      events.push(event);
+    }));
+  });
+
+  after(async () => {
+    for (const listener of listeners) {
+      await program.removeEventListener(listener);
+    }
+  });
+
+  async function fetchAreas() {
+    return (await program.account.realmDimension.fetch(dimensionPDA())).areas;
+  }
+
+  it("Airdrop to realm master", async () => await helper.airdrop(realmMaster.publicKey, 1 * anchor.web3.LAMPORTS_PER_SOL));
+  it("Airdrop to stranger", async () => await helper.airdrop(stranger.publicKey, 1 * anchor.web3.LAMPORTS_PER_SOL));
+  it("Create the realm", async () => await steps.createRealm(realmMaster, program, realmId, realmDescription, events));
+  it("Add the dimension", async () => await steps.addRealmDimension(realmMaster, program, realmId, dimension, events));
+
+  it("Add areas one by one", async () => {
+    for (const area of [makeArea(2, "Forest", 100), makeArea(3, "Swamp", 200)]) {
+      const tx = await program.methods
+        .addDimensionArea(realmId, dimension.id, area)
+        .accounts({ master: realmMaster.publicKey })
+        .signers([realmMaster])
+        .rpc();
+      await helper.confirmTransaction(tx);
+
+      let event = events[events.length - 1];
+      expect(event.eventType.areaAdded.areaId).to.equal(area.id);
+      expect(event.eventType.areaAdded.name).to.equal(area.name);
+    }
+
+    expect((await fetchAreas()).map((area) => area.id)).to.deep.equal([1, 2, 3]);
+  });
+
+  it("Try to add an area with an existing id", async () => {
+    try {
+      await program.methods
+        .addDimensionArea(realmId, dimension.id, makeArea(2, "Hills", 300))
+        .accounts({ master: realmMaster.publicKey })
+        .signers([realmMaster])
+        .rpc();
+      expect.fail("Area ids should stay unique");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("DuplicateAreaId");
+    }
+  });
+
+  it("Try to add an area overlapping another one", async () => {
+    try {
+      await program.methods
+        .addDimensionArea(realmId, dimension.id, makeArea(4, "Hills", 250))
+        .accounts({ master: realmMaster.publicKey })
+        .signers([realmMaster])
+        .rpc();
+      expect.fail("Added areas should be validated");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("OverlappingAreas");
+    }
+  });
+
+  it("Stranger tries to add an area, and it fails", async () => {
+    try {
+      await program.methods
+        .addDimensionArea(realmId, dimension.id, makeArea(4, "Hills", 300))
+        .accounts({ master: stranger.publicKey })
+        .signers([stranger])
+        .rpc();
+      expect.fail("Only realm masters should edit areas");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("AccountNotInitialized");
+    }
+  });
+
+  it("Update an area in place with a longer name", async () => {
+    const connection = anchor.getProvider().connection;
+    const sizeBefore = (await connection.getAccountInfo(dimensionPDA())).data.length;
+    const area = { ...makeArea(2, "Enchanted Forest", 100), tileset: "https://example.com/forest.png" };
+
+    const tx = await program.methods
+      .updateDimensionArea(realmId, dimension.id, area)
+      .accounts({ master: realmMaster.publicKey })
+      .signers([realmMaster])
+      .rpc();
+    await helper.confirmTransaction(tx);
+
+    const areas = await fetchAreas();
+    expect(areas.map((area) => area.id)).to.deep.equal([1, 2, 3]);
+    expect(areas[1]).to.deep.equal(area);
+    expect((await connection.getAccountInfo(dimensionPDA())).data.length).to.be.above(sizeBefore);
+
+    let event = events[events.length - 1];
+    expect(event.eventType.areaUpdated.areaId).to.equal(2);
+    expect(event.eventType.areaUpdated.name).to.equal(area.name);
+  });
+
+  it("Try to update an unknown area", async () => {
+    try {
+      await program.methods
+        .updateDimensionArea(realmId, dimension.id, makeArea(42, "Nowhere", 1000))
+        .accounts({ master: realmMaster.publicKey })
+        .signers([realmMaster])
+        .rpc();
+      expect.fail("Unknown area should not be updated");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("AreaNotFound");
+    }
+  });
+
+  it("Try to remove the camp holding the starting point", async () => {
+    try {
+      await program.methods
+        .removeDimensionArea(realmId, dimension.id, 1)
+        .accounts({ master: realmMaster.publicKey })
+        .signers([realmMaster])
+        .rpc();
+      expect.fail("Starting point should stay inside the dimension areas");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("StartingPointOutsideDimension");
+    }
+  });
+
+  it("Move the starting point into the forest", async () => {
+    const tx = await program.methods
+      .setRealmStartingPoint(realmId, dimension.id, { x: 150, y: 50 })
+      .accounts({ master: realmMaster.publicKey })
+      .signers([realmMaster])
+      .rpc();
+    await helper.confirmTransaction(tx);
+  });
+
+  it("Remove an area and keep the others' ids", async () => {
+    const connection = anchor.getProvider().connection;
+    const sizeBefore = (await connection.getAccountInfo(dimensionPDA())).data.length;
+
+    const tx = await program.methods
+      .removeDimensionArea(realmId, dimension.id, 1)
+      .accounts({ master: realmMaster.publicKey })
+      .signers([realmMaster])
+      .rpc();
+    await helper.confirmTransaction(tx);
+
+    expect((await fetchAreas()).map((area) => area.id)).to.deep.equal([2, 3]);
+    expect((await connection.getAccountInfo(dimensionPDA())).data.length).to.be.below(sizeBefore);
+
+    let event = events[events.length - 1];
+    expect(event.eventType.areaRemoved.areaId).to.equal(1);
+  });
+
+  it("Try to remove an already removed area", async () => {
+    try {
+      await program.methods
+        .removeDimensionArea(realmId, dimension.id, 1)
+        .accounts({ master: realmMaster.publicKey })
+        .signers([realmMaster])
+        .rpc();
+      expect.fail("Removed area should not be found");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("AreaNotFound");
+    }
+  });
+});
\ No newline at end of file
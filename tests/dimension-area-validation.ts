@@ -0,0 +1,105 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { expect } from "chai";
+
+import { RealmVoyagers } from "../target/types/realm_voyagers";
+
+import * as helper from "./utils/helpers";
+import * as steps from "./utils/common-steps";
+
+describe("Dimension area validation", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  // Program & keypairs
+  const program = anchor.workspace.RealmVoyagers as Program<RealmVoyagers>;
+  const realmMaster = anchor.web3.Keypair.generate();
+
+  // Realm data
+  const realmId = "validated_realm";
+  const realmDescription = { name: "Validated Realm", details: "A realm with well formed areas", logo: "https://example.com/logo123" };
+  let nextAreaId = 1;
+  const makeArea = (name: string, topLeft: any, bottomRight: any) => ({
+    "id": nextAreaId++,
+    "name": name,
+    "area": { "topLeft": topLeft, "bottomRight": bottomRight },
+    "tileset": "https://example.com/tileset.png",
+    "tilemap": "https://example.com/tilemap.json"
+  });
+  const meadow = makeArea("Meadow", { x: 0, y: 0 }, { x: 100, y: 100 });
+  const river = makeArea("River", { x: 50, y: 50 }, { x: 150, y: 60 });
+  const dimension = { id: "valley", name: "Valley", areas: [meadow] };
+
+  // Listen events
+  let listeners = [];
+  let events = [];
+
+  before(async () => {
+    listeners.push(program.addEventListener("realmDimensionEvent", (event) => {
+      events.push(event);
+    }));
+  });
+
+  after(async () => {
+    for (const listener of listeners) {
+      await program.removeEventListener(listener);
+    }
+  });
+
+  async function expectInvalidAreas(areas: any[], errorCode: string) {
+    try {
+      await program.methods
+        .updateRealmDimension(realmId, dimension.id, dimension.name, areas, false)
+        .accounts({ master: realmMaster.publicKey })
+        .signers([realmMaster])
+        .rpc();
+      expect.fail("Invalid areas should be rejected");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal(errorCode);
+    }
+  }
+
+  it("Airdrop to realm master", async () => await helper.airdrop(realmMaster.publicKey, 1 * anchor.web3.LAMPORTS_PER_SOL));
+  it("Create the realm", async () => await steps.createRealm(realmMaster, program, realmId, realmDescription, events));
+  it("Add the dimension", async () => await steps.addRealmDimension(realmMaster, program, realmId, dimension, events));
+
+  it("Try to add a dimension with an inverted area", async () => {
+    try {
+      await program.methods
+        .addRealmDimension(realmId, "upside_down", "Upside Down", [makeArea("Inverted", { x: 100, y: 100 }, { x: 0, y: 0 })], false)
+        .accounts({ master: realmMaster.publicKey })
+        .signers([realmMaster])
+        .rpc();
+      expect.fail("Inverted area should be rejected");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("InvertedArea");
+    }
+  });
+
+  it("Try to set an area inverted on one axis only", async () => {
+    await expectInvalidAreas([makeArea("Upside", { x: 0, y: 100 }, { x: 100, y: 99 })], "InvertedArea");
+  });
+  it("Try to set an out of bounds area", async () => await expectInvalidAreas([makeArea("Far away", { x: 0, y: 0 }, { x: 10_001, y: 100 })], "AreaOutOfBounds"));
+  it("Try to set overlapping areas", async () => await expectInvalidAreas([meadow, river], "OverlappingAreas"));
+  it("Try to set areas sharing an edge", async () => {
+    await expectInvalidAreas([meadow, makeArea("Field", { x: 100, y: 0 }, { x: 200, y: 100 })], "OverlappingAreas");
+  });
+  it("Try to set areas with the same id", async () => {
+    await expectInvalidAreas([meadow, { ...makeArea("Field", { x: 101, y: 0 }, { x: 200, y: 100 }), id: meadow.id }], "DuplicateAreaId");
+  });
+  it("Try to set an area with a long name", async () => {
+    await expectInvalidAreas([makeArea("a".repeat(81), { x: 0, y: 0 }, { x: 100, y: 100 })], "NameTooLong");
+  });
+  it("Try to set an area with a long tileset path", async () => {
+    await expectInvalidAreas([{ ...meadow, tileset: "https://example.com/" + "a".repeat(128) }], "ResourcePathTooLong");
+  });
+
+  it("Update the dimension with adjacent areas", async () => {
+    const field = makeArea("Field", { x: 101, y: 0 }, { x: 200, y: 100 });
+    await steps.updateRealmDimension(realmMaster, program, realmId, { ...dimension, areas: [meadow, field] }, events);
+  });
+
+  it("Update the dimension allowing overlapping areas", async () => {
+    await steps.updateRealmDimension(realmMaster, program, realmId, { ...dimension, areas: [meadow, river], allowOverlappingAreas: true }, events);
+  });
+});
\ No newline at end of file
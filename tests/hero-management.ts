@@ -11,30 +11,367 @@ describe("Hero Management", () => {
   // Program & keypairs
   const program = anchor.workspace.RealmVoyagers as Program<RealmVoyagers>;
   const player = anchor.web3.Keypair.generate();
+  const voyager = anchor.web3.Keypair.generate();
 
   // Heroes data
   const heroId = "hero_id_1";
   const heroDescription = { name: "Test Hero 1", graphics: "https://example.com/graphics1", lore: "A test hero" };
-  const heroStats = { strength: 10, dexterity: 5, intelligence: 3, charisma: 2, vitality: 8 };
+  const heroTags = [{ mage: {} }, { knight: {} }];
   const updatedHeroDescription = { name: "Updated Hero 1", graphics: "https://example.com/graphics1", lore: "An updated lore" };
+  const playerHeroId = "my_hero";
+
+  // Realm data
+  const realmId = "voyager_realm";
+  const realmDescription = { name: "Voyager Realm", details: "A realm of the voyager", logo: "https://example.com/logo123" };
+  const dimension = {
+    id: "training_grounds",
+    name: "Training Grounds",
+    areas: [{
+      "id": 1,
+      "name": "Training Grounds",
+      "area": { "topLeft": { "x": 0, "y": 0 }, "bottomRight": { "x": 10, "y": 10 } },
+      "tileset": "https://example.com/tileset.png",
+      "tilemap": "https://example.com/tilemap.json"
+    }]
+  };
 
   // Listen events
-  let listener = null;
+  let listeners = [];
   let events = [];
+  let realmEvents = [];
 
   before(async () => {
-    listener = program.addEventListener("heroEvent", (event) => {
+    listeners.push(program.addEventListener("heroEvent", (event) => {
       events.push(event);
-    });
+    }));
+    listeners.push(program.addEventListener("realmEvent", (event) => {
+      realmEvents.push(event);
+    }));
+    listeners.push(program.addEventListener("realmDimensionEvent", (event) => {
+      realmEvents.push(event);
+    }));
   });
 
   after(async () => {
-    await program.removeEventListener(listener);
+    for (const listener of listeners) {
+      await program.removeEventListener(listener);
+    }
   });
 
+  async function grantXpAsRealmMaster(amount: number) {
+    const tx = await program.methods
+      .grantXp(new anchor.BN(amount))
+      .accounts({
+        playerHero: helper.getPlayerHeroPDA(voyager.publicKey, playerHeroId, program),
+        realm: helper.getRealmPDA(realmId, program),
+        journey: helper.getJourneyPDA(realmId, voyager.publicKey, program),
+        masterMembership: helper.getMasterPDA(realmId, voyager.publicKey, program),
+        authority: voyager.publicKey,
+      })
+      .signers([voyager])
+      .rpc();
+    await helper.confirmTransaction(tx);
+  }
+
   it("Airdrop to player", async () => await helper.airdrop(player.publicKey, 1 * anchor.web3.LAMPORTS_PER_SOL));
 
-  it("Create the hero", async () => await steps.createHero(player, program, heroId, heroDescription, events));
+  it("Try to create a hero with duplicate tags", async () => {
+    try {
+      await program.methods
+        .createHero("duplicate_tags_hero", heroDescription, [{ mage: {} }, { mage: {} }])
+        .accounts({ master: player.publicKey })
+        .signers([player])
+        .rpc();
+      expect.fail("Hero with duplicate tags should not be created");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("DuplicateHeroTag");
+    }
+  });
+
+  it("Create the hero", async () => await steps.createHero(player, program, heroId, heroDescription, heroTags, events));
   it("Update the hero", async () => await steps.updateHeroDescription(player, program, heroId, updatedHeroDescription, events));
 
+  it("Airdrop to voyager", async () => await helper.airdrop(voyager.publicKey, 1 * anchor.web3.LAMPORTS_PER_SOL));
+
+  it("Mint a player hero from the hero class", async () => {
+    const heroPDA = helper.getHeroPDA(player.publicKey, heroId, program);
+    const playerHeroPDA = helper.getPlayerHeroPDA(voyager.publicKey, playerHeroId, program);
+
+    const tx = await program.methods
+      .mintPlayerHero(playerHeroId, "Sir Voyager")
+      .accounts({ hero: heroPDA, player: voyager.publicKey })
+      .signers([voyager])
+      .rpc();
+    await helper.confirmTransaction(tx);
+
+    const heroAccount = await program.account.hero.fetch(heroPDA);
+    const playerHeroAccount = await program.account.playerHero.fetch(playerHeroPDA);
+    expect(playerHeroAccount.player.toBase58()).to.equal(voyager.publicKey.toBase58());
+    expect(playerHeroAccount.hero.toBase58()).to.equal(heroPDA.toBase58());
+    expect(playerHeroAccount.name).to.equal("Sir Voyager");
+    expect(playerHeroAccount.level.toNumber()).to.equal(1);
+    expect(playerHeroAccount.xp.toNumber()).to.equal(0);
+    expect(playerHeroAccount.stats).to.deep.equal(heroAccount.baseStats);
+    expect(playerHeroAccount.items).to.be.empty;
+
+    expect(events.length).to.be.above(0);
+    let event = events[events.length - 1];
+    expect(event.eventType.playerHeroMinted.playerHeroPubkey.toBase58()).to.equal(playerHeroPDA.toBase58());
+    expect(event.eventType.playerHeroMinted.player.toBase58()).to.equal(voyager.publicKey.toBase58());
+  });
+
+  it("Mint a player hero keeping the class name", async () => {
+    const heroPDA = helper.getHeroPDA(player.publicKey, heroId, program);
+    const playerHeroPDA = helper.getPlayerHeroPDA(voyager.publicKey, playerHeroId + "_2", program);
+
+    const tx = await program.methods
+      .mintPlayerHero(playerHeroId + "_2", null)
+      .accounts({ hero: heroPDA, player: voyager.publicKey })
+      .signers([voyager])
+      .rpc();
+    await helper.confirmTransaction(tx);
+
+    const playerHeroAccount = await program.account.playerHero.fetch(playerHeroPDA);
+    expect(playerHeroAccount.name).to.equal(updatedHeroDescription.name);
+  });
+
+  it("Voyager tries to grant experience to their own hero, and it fails", async () => {
+    try {
+      await program.methods
+        .grantXp(new anchor.BN(1000))
+        .accounts({
+          playerHero: helper.getPlayerHeroPDA(voyager.publicKey, playerHeroId, program),
+          realm: null,
+          journey: null,
+          masterMembership: null,
+          authority: voyager.publicKey,
+        })
+        .signers([voyager])
+        .rpc();
+      expect.fail("Voyager should not be able to grant experience");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("UnauthorizedXpAuthority");
+    }
+  });
+
+  it("Hero class master grants experience", async () => {
+    const playerHeroPDA = helper.getPlayerHeroPDA(voyager.publicKey, playerHeroId, program);
+
+    const tx = await program.methods
+      .grantXp(new anchor.BN(120))
+      .accounts({
+        playerHero: playerHeroPDA,
+        realm: null,
+        journey: null,
+        masterMembership: null,
+        authority: player.publicKey,
+      })
+      .signers([player])
+      .rpc();
+    await helper.confirmTransaction(tx);
+
+    const playerHeroAccount = await program.account.playerHero.fetch(playerHeroPDA);
+    expect(playerHeroAccount.xp.toNumber()).to.equal(120);
+
+    let event = events[events.length - 1];
+    expect(event.eventType.xpGranted.amount.toNumber()).to.equal(120);
+    expect(event.eventType.xpGranted.authority.toBase58()).to.equal(player.publicKey.toBase58());
+  });
+
+  it("Voyager tries to overspend stat points, and it fails", async () => {
+    const allocation = {
+      strength: new anchor.BN(4), agility: new anchor.BN(0), dexterity: new anchor.BN(0), constitution: new anchor.BN(0),
+      intelligence: new anchor.BN(0), wisdom: new anchor.BN(0), charisma: new anchor.BN(0), willpower: new anchor.BN(0),
+    };
+    try {
+      await program.methods
+        .levelUp(playerHeroId, allocation)
+        .accounts({ player: voyager.publicKey })
+        .signers([voyager])
+        .rpc();
+      expect.fail("Voyager should not be able to spend more stat points than earned");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("InvalidStatAllocation");
+    }
+  });
+
+  it("Voyager levels up the hero", async () => {
+    const playerHeroPDA = helper.getPlayerHeroPDA(voyager.publicKey, playerHeroId, program);
+    const before = await program.account.playerHero.fetch(playerHeroPDA);
+    const allocation = {
+      strength: new anchor.BN(2), agility: new anchor.BN(0), dexterity: new anchor.BN(0), constitution: new anchor.BN(1),
+      intelligence: new anchor.BN(0), wisdom: new anchor.BN(0), charisma: new anchor.BN(0), willpower: new anchor.BN(0),
+    };
+
+    const tx = await program.methods
+      .levelUp(playerHeroId, allocation)
+      .accounts({ player: voyager.publicKey })
+      .signers([voyager])
+      .rpc();
+    await helper.confirmTransaction(tx);
+
+    const after = await program.account.playerHero.fetch(playerHeroPDA);
+    expect(after.level.toNumber()).to.equal(2);
+    expect(after.xp.toNumber()).to.equal(20);
+    expect(after.statPoints.toNumber()).to.equal(0);
+    expect(after.stats.strength.toNumber()).to.equal(before.stats.strength.toNumber() + 2);
+    expect(after.stats.constitution.toNumber()).to.equal(before.stats.constitution.toNumber() + 1);
+
+    let event = events[events.length - 1];
+    expect(event.eventType.heroLeveledUp.level.toNumber()).to.equal(2);
+  });
+
+  it("Voyager tries to level up without enough experience, and it fails", async () => {
+    const allocation = {
+      strength: new anchor.BN(0), agility: new anchor.BN(0), dexterity: new anchor.BN(0), constitution: new anchor.BN(0),
+      intelligence: new anchor.BN(0), wisdom: new anchor.BN(0), charisma: new anchor.BN(0), willpower: new anchor.BN(0),
+    };
+    try {
+      await program.methods
+        .levelUp(playerHeroId, allocation)
+        .accounts({ player: voyager.publicKey })
+        .signers([voyager])
+        .rpc();
+      expect.fail("Hero should not level up without enough experience");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("NotEnoughXp");
+    }
+  });
+
+  it("Hero class master caps the stats", async () => {
+    const heroPDA = helper.getHeroPDA(player.publicKey, heroId, program);
+    const playerHeroPDA = helper.getPlayerHeroPDA(voyager.publicKey, playerHeroId, program);
+    const playerHeroAccount = await program.account.playerHero.fetch(playerHeroPDA);
+
+    let tx = await program.methods
+      .setHeroStatCaps(heroId, playerHeroAccount.stats)
+      .accounts({ master: player.publicKey })
+      .signers([player])
+      .rpc();
+    await helper.confirmTransaction(tx);
+
+    const heroAccount = await program.account.hero.fetch(heroPDA);
+    expect(heroAccount.statCaps).to.deep.equal(playerHeroAccount.stats);
+
+    let event = events[events.length - 1];
+    expect(event.eventType.heroStatCapsUpdated.heroPubkey.toBase58()).to.equal(heroPDA.toBase58());
+
+    tx = await program.methods
+      .grantXp(new anchor.BN(150))
+      .accounts({ playerHero: playerHeroPDA, realm: null, journey: null, masterMembership: null, authority: player.publicKey })
+      .signers([player])
+      .rpc();
+    await helper.confirmTransaction(tx);
+  });
+
+  it("Voyager tries to raise a capped stat, and it fails", async () => {
+    const allocation = {
+      strength: new anchor.BN(1), agility: new anchor.BN(0), dexterity: new anchor.BN(0), constitution: new anchor.BN(0),
+      intelligence: new anchor.BN(0), wisdom: new anchor.BN(0), charisma: new anchor.BN(0), willpower: new anchor.BN(0),
+    };
+    try {
+      await program.methods
+        .levelUp(playerHeroId, allocation)
+        .accounts({ player: voyager.publicKey })
+        .signers([voyager])
+        .rpc();
+      expect.fail("Stat should not exceed its cap");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("StatOverflow");
+    }
+  });
+
+  it("Hero class master lowers the strength cap, and the voyager still levels up raising agility", async () => {
+    const playerHeroPDA = helper.getPlayerHeroPDA(voyager.publicKey, playerHeroId, program);
+    const before = await program.account.playerHero.fetch(playerHeroPDA);
+    const caps = {
+      ...before.stats,
+      strength: before.stats.strength.subn(1),
+      agility: before.stats.agility.addn(1),
+    };
+
+    let tx = await program.methods
+      .setHeroStatCaps(heroId, caps)
+      .accounts({ master: player.publicKey })
+      .signers([player])
+      .rpc();
+    await helper.confirmTransaction(tx);
+
+    const allocation = {
+      strength: new anchor.BN(0), agility: new anchor.BN(1), dexterity: new anchor.BN(0), constitution: new anchor.BN(0),
+      intelligence: new anchor.BN(0), wisdom: new anchor.BN(0), charisma: new anchor.BN(0), willpower: new anchor.BN(0),
+    };
+    tx = await program.methods
+      .levelUp(playerHeroId, allocation)
+      .accounts({ player: voyager.publicKey })
+      .signers([voyager])
+      .rpc();
+    await helper.confirmTransaction(tx);
+
+    const after = await program.account.playerHero.fetch(playerHeroPDA);
+    expect(after.level.toNumber()).to.equal(before.level.toNumber() + 1);
+    expect(after.stats.strength.toNumber()).to.equal(before.stats.strength.toNumber());
+    expect(after.stats.agility.toNumber()).to.equal(before.stats.agility.toNumber() + 1);
+  });
+
+  it("Voyager creates their own realm and starts a journey there", async () => {
+    await steps.createRealm(voyager, program, realmId, realmDescription, realmEvents);
+    await steps.addRealmDimension(voyager, program, realmId, dimension, realmEvents);
+
+    const tx = await program.methods
+      .startJourney(realmId, [], null)
+      .accounts({ player: voyager.publicKey, spawnPoint: null, playerHero: null, hero: null })
+      .signers([voyager])
+      .rpc();
+    await helper.confirmTransaction(tx);
+  });
+
+  it("Voyager tries to grant experience through an untrusted realm, and it fails", async () => {
+    try {
+      await grantXpAsRealmMaster(1000);
+      expect.fail("Only realms trusted by the hero class should grant experience");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("UnauthorizedXpAuthority");
+    }
+  });
+
+  it("Hero class master trusts the realm", async () => {
+    const heroPDA = helper.getHeroPDA(player.publicKey, heroId, program);
+    const realmPDA = helper.getRealmPDA(realmId, program);
+
+    const tx = await program.methods
+      .setHeroTrustedRealms(heroId, [realmPDA])
+      .accounts({ master: player.publicKey })
+      .signers([player])
+      .rpc();
+    await helper.confirmTransaction(tx);
+
+    const heroAccount = await program.account.hero.fetch(heroPDA);
+    expect(heroAccount.trustedRealms.map((realm) => realm.toBase58())).to.deep.equal([realmPDA.toBase58()]);
+
+    let event = events[events.length - 1];
+    expect(event.eventType.heroTrustedRealmsUpdated.heroPubkey.toBase58()).to.equal(heroPDA.toBase58());
+  });
+
+  it("Realm master of the trusted realm grants experience", async () => {
+    const playerHeroPDA = helper.getPlayerHeroPDA(voyager.publicKey, playerHeroId, program);
+    const xp = (await program.account.playerHero.fetch(playerHeroPDA)).xp.toNumber();
+
+    await grantXpAsRealmMaster(10);
+
+    const playerHeroAccount = await program.account.playerHero.fetch(playerHeroPDA);
+    expect(playerHeroAccount.xp.toNumber()).to.equal(xp + 10);
+  });
+
+  it("Voyager recreates the realm and tries to grant experience through the stale journey, and it fails", async () => {
+    await steps.deleteRealm(voyager, program, realmId, [dimension.id], realmEvents);
+    await steps.createRealm(voyager, program, realmId, realmDescription, realmEvents);
+
+    try {
+      await grantXpAsRealmMaster(10);
+      expect.fail("Journeys of a deleted realm should not be used to grant experience");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("StaleJourney");
+    }
+  });
 });
\ No newline at end of file
@@ -0,0 +1,353 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { expect } from "chai";
+
+import { RealmVoyagers } from "../target/types/realm_voyagers";
+
+import * as helper from "./utils/helpers";
+import * as steps from "./utils/common-steps";
+
+describe("Item Management", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  // Program & keypairs
+  const program = anchor.workspace.RealmVoyagers as Program<RealmVoyagers>;
+  const master = anchor.web3.Keypair.generate();
+  const alice = anchor.web3.Keypair.generate();
+  const bob = anchor.web3.Keypair.generate();
+
+  // Heroes & items data
+  const heroId = "item_hero";
+  const heroDescription = { name: "Squire", graphics: "https://example.com/squire", lore: "Carries things" };
+  const playerHeroId = "carrier";
+  const itemId = "iron_sword";
+  const itemDescription = { name: "Iron Sword", graphics: "https://example.com/iron_sword" };
+  const forgedItemId = "forged_blade";
+  const stats = (strength: number, dexterity: number) => ({
+    strength: new anchor.BN(strength), agility: new anchor.BN(0), dexterity: new anchor.BN(dexterity), constitution: new anchor.BN(0),
+    intelligence: new anchor.BN(0), wisdom: new anchor.BN(0), charisma: new anchor.BN(0), willpower: new anchor.BN(0),
+  });
+  const itemModifiers = stats(3, 1);
+  const axeId = "great_axe";
+  const axeDescription = { name: "Great Axe", graphics: "https://example.com/great_axe" };
+
+  // Listen events
+  let listeners = [];
+  let events = [];
+
+  before(async () => {
+    listeners.push(program.addEventListener("heroEvent", (event) => {
+      events.push(event);
+    }));
+    listeners.push(program.addEventListener("itemEvent", (event) => {
+      events.push(event);
+    }));
+  });
+
+  after(async () => {
+    for (const listener of listeners) {
+      await program.removeEventListener(listener);
+    }
+  });
+
+  it("Airdrop to master", async () => await helper.airdrop(master.publicKey, 1 * anchor.web3.LAMPORTS_PER_SOL));
+  it("Airdrop to Alice", async () => await helper.airdrop(alice.publicKey, 1 * anchor.web3.LAMPORTS_PER_SOL));
+  it("Airdrop to Bob", async () => await helper.airdrop(bob.publicKey, 1 * anchor.web3.LAMPORTS_PER_SOL));
+
+  it("Create the hero class", async () => await steps.createHero(master, program, heroId, heroDescription, [{ knight: {} }], events));
+
+  for (const voyager of [alice, bob]) {
+    it("Mint a player hero", async () => {
+      const tx = await program.methods
+        .mintPlayerHero(playerHeroId, null)
+        .accounts({ hero: helper.getHeroPDA(master.publicKey, heroId, program), player: voyager.publicKey })
+        .signers([voyager])
+        .rpc();
+      await helper.confirmTransaction(tx);
+    });
+  }
+
+  it("Create the item class", async () => {
+    const itemPDA = helper.getItemPDA(master.publicKey, itemId, program);
+
+    const tx = await program.methods
+      .createItem(itemId, itemDescription, { rare: {} }, { mainHand: {} }, itemModifiers, stats(0, 0))
+      .accounts({ master: master.publicKey })
+      .signers([master])
+      .rpc();
+    await helper.confirmTransaction(tx);
+
+    const itemAccount = await program.account.item.fetch(itemPDA);
+    expect(itemAccount.master.toBase58()).to.equal(master.publicKey.toBase58());
+    expect(itemAccount.description).to.deep.equal(itemDescription);
+    expect(itemAccount.rarity).to.deep.equal({ rare: {} });
+    expect(itemAccount.slot).to.deep.equal({ mainHand: {} });
+    expect(itemAccount.modifiers.strength.toNumber()).to.equal(3);
+
+    let event = events[events.length - 1];
+    expect(event.eventType.itemCreated.itemPubkey.toBase58()).to.equal(itemPDA.toBase58());
+  });
+
+  it("Alice tries to grant the item to herself, and it fails", async () => {
+    try {
+      await program.methods
+        .grantItem()
+        .accounts({
+          item: helper.getItemPDA(master.publicKey, itemId, program),
+          playerHero: helper.getPlayerHeroPDA(alice.publicKey, playerHeroId, program),
+          hero: helper.getHeroPDA(master.publicKey, heroId, program),
+          realm: null,
+          journey: null,
+          masterMembership: null,
+          player: alice.publicKey,
+          master: alice.publicKey,
+        })
+        .signers([alice])
+        .rpc();
+      expect.fail("Alice should not be able to grant items she does not own");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("UnauthorizedItemMaster");
+    }
+  });
+
+  it("Alice creates her own item and tries to grant it to her hero, and it fails", async () => {
+    let tx = await program.methods
+      .createItem(forgedItemId, itemDescription, { legendary: {} }, { mainHand: {} }, stats(100, 0), stats(0, 0))
+      .accounts({ master: alice.publicKey })
+      .signers([alice])
+      .rpc();
+    await helper.confirmTransaction(tx);
+
+    try {
+      await program.methods
+        .grantItem()
+        .accounts({
+          item: helper.getItemPDA(alice.publicKey, forgedItemId, program),
+          playerHero: helper.getPlayerHeroPDA(alice.publicKey, playerHeroId, program),
+          hero: helper.getHeroPDA(master.publicKey, heroId, program),
+          realm: null,
+          journey: null,
+          masterMembership: null,
+          player: alice.publicKey,
+          master: alice.publicKey,
+        })
+        .signers([alice])
+        .rpc();
+      expect.fail("Only the hero class master or trusted realms should grant items");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("UnauthorizedItemMaster");
+    }
+  });
+
+  it("Master tries to grant the item without Alice accepting it, and it fails", async () => {
+    try {
+      await program.methods
+        .grantItem()
+        .accounts({
+          item: helper.getItemPDA(master.publicKey, itemId, program),
+          playerHero: helper.getPlayerHeroPDA(alice.publicKey, playerHeroId, program),
+          hero: helper.getHeroPDA(master.publicKey, heroId, program),
+          realm: null,
+          journey: null,
+          masterMembership: null,
+          player: master.publicKey,
+          master: master.publicKey,
+        })
+        .signers([master])
+        .rpc();
+      expect.fail("Items should only be granted with the consent of the player");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("ConstraintHasOne");
+    }
+  });
+
+  it("Master grants the item to Alice", async () => {
+    const itemPDA = helper.getItemPDA(master.publicKey, itemId, program);
+    const playerHeroPDA = helper.getPlayerHeroPDA(alice.publicKey, playerHeroId, program);
+
+    const tx = await program.methods
+      .grantItem()
+      .accounts({
+        item: itemPDA,
+        playerHero: playerHeroPDA,
+        hero: helper.getHeroPDA(master.publicKey, heroId, program),
+        realm: null,
+        journey: null,
+        masterMembership: null,
+        player: alice.publicKey,
+        master: master.publicKey,
+      })
+      .signers([master, alice])
+      .rpc();
+    await helper.confirmTransaction(tx);
+
+    const playerHeroAccount = await program.account.playerHero.fetch(playerHeroPDA);
+    expect(playerHeroAccount.items.map((item) => item.toBase58())).to.deep.equal([itemPDA.toBase58()]);
+
+    let event = events[events.length - 1];
+    expect(event.eventType.itemGranted.playerHeroPubkey.toBase58()).to.equal(playerHeroPDA.toBase58());
+  });
+
+  it("Create an item class with high requirements", async () => {
+    const tx = await program.methods
+      .createItem(axeId, axeDescription, { epic: {} }, { mainHand: {} }, stats(8, 0), stats(100, 0))
+      .accounts({ master: master.publicKey })
+      .signers([master])
+      .rpc();
+    await helper.confirmTransaction(tx);
+  });
+
+  it("Alice tries to equip the sword into a wrong slot, and it fails", async () => {
+    try {
+      await program.methods
+        .equipItem(playerHeroId, { head: {} })
+        .accounts({ item: helper.getItemPDA(master.publicKey, itemId, program), player: alice.publicKey })
+        .signers([alice])
+        .rpc();
+      expect.fail("Sword should not fit on the head");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("InvalidEquipmentSlot");
+    }
+  });
+
+  it("Alice equips the sword", async () => {
+    const itemPDA = helper.getItemPDA(master.publicKey, itemId, program);
+    const playerHeroPDA = helper.getPlayerHeroPDA(alice.publicKey, playerHeroId, program);
+    const before = await program.account.playerHero.fetch(playerHeroPDA);
+
+    const tx = await program.methods
+      .equipItem(playerHeroId, { mainHand: {} })
+      .accounts({ item: itemPDA, player: alice.publicKey })
+      .signers([alice])
+      .rpc();
+    await helper.confirmTransaction(tx);
+
+    const playerHeroAccount = await program.account.playerHero.fetch(playerHeroPDA);
+    expect(playerHeroAccount.equipment.length).to.equal(1);
+    expect(playerHeroAccount.equipment[0].slot).to.deep.equal({ mainHand: {} });
+    expect(playerHeroAccount.equipment[0].item.toBase58()).to.equal(itemPDA.toBase58());
+    expect(playerHeroAccount.stats).to.deep.equal(before.stats);
+
+    let event = events[events.length - 1];
+    expect(event.eventType.itemEquipped.effectiveStats.strength.toNumber()).to.equal(before.stats.strength.toNumber() + 3);
+    expect(event.eventType.itemEquipped.effectiveStats.dexterity.toNumber()).to.equal(before.stats.dexterity.toNumber() + 1);
+  });
+
+  it("Alice tries to give away the equipped sword, and it fails", async () => {
+    try {
+      await program.methods
+        .transferItem(playerHeroId, helper.getItemPDA(master.publicKey, itemId, program))
+        .accounts({
+          targetPlayerHero: helper.getPlayerHeroPDA(bob.publicKey, playerHeroId, program),
+          player: alice.publicKey,
+          targetPlayer: bob.publicKey,
+        })
+        .signers([alice, bob])
+        .rpc();
+      expect.fail("Equipped item should not be transferred");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("ItemIsEquipped");
+    }
+  });
+
+  it("Alice unequips the sword", async () => {
+    const playerHeroPDA = helper.getPlayerHeroPDA(alice.publicKey, playerHeroId, program);
+
+    const tx = await program.methods
+      .unequipItem(playerHeroId, { mainHand: {} })
+      .accounts({ player: alice.publicKey })
+      .signers([alice])
+      .rpc();
+    await helper.confirmTransaction(tx);
+
+    const playerHeroAccount = await program.account.playerHero.fetch(playerHeroPDA);
+    expect(playerHeroAccount.equipment).to.be.empty;
+
+    let event = events[events.length - 1];
+    expect(event.eventType.itemUnequipped.slot).to.deep.equal({ mainHand: {} });
+  });
+
+  it("Alice tries to equip the axe without enough strength, and it fails", async () => {
+    const axePDA = helper.getItemPDA(master.publicKey, axeId, program);
+    let tx = await program.methods
+      .grantItem()
+      .accounts({
+        item: axePDA,
+        playerHero: helper.getPlayerHeroPDA(alice.publicKey, playerHeroId, program),
+        hero: helper.getHeroPDA(master.publicKey, heroId, program),
+        realm: null,
+        journey: null,
+        masterMembership: null,
+        player: alice.publicKey,
+        master: master.publicKey,
+      })
+      .signers([master, alice])
+      .rpc();
+    await helper.confirmTransaction(tx);
+
+    try {
+      await program.methods
+        .equipItem(playerHeroId, { mainHand: {} })
+        .accounts({ item: axePDA, player: alice.publicKey })
+        .signers([alice])
+        .rpc();
+      expect.fail("Alice should not meet the axe requirements");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("ItemRequirementsNotMet");
+    }
+  });
+
+  it("Alice transfers the item to Bob", async () => {
+    const itemPDA = helper.getItemPDA(master.publicKey, itemId, program);
+    const alicePDA = helper.getPlayerHeroPDA(alice.publicKey, playerHeroId, program);
+    const bobPDA = helper.getPlayerHeroPDA(bob.publicKey, playerHeroId, program);
+
+    const tx = await program.methods
+      .transferItem(playerHeroId, itemPDA)
+      .accounts({ targetPlayerHero: bobPDA, player: alice.publicKey, targetPlayer: bob.publicKey })
+      .signers([alice, bob])
+      .rpc();
+    await helper.confirmTransaction(tx);
+
+    const aliceAccount = await program.account.playerHero.fetch(alicePDA);
+    const bobAccount = await program.account.playerHero.fetch(bobPDA);
+    expect(aliceAccount.items.map((item) => item.toBase58())).to.deep.equal([helper.getItemPDA(master.publicKey, axeId, program).toBase58()]);
+    expect(bobAccount.items.map((item) => item.toBase58())).to.deep.equal([itemPDA.toBase58()]);
+
+    let event = events[events.length - 1];
+    expect(event.eventType.itemTransferred.fromPlayerHero.toBase58()).to.equal(alicePDA.toBase58());
+    expect(event.eventType.itemTransferred.toPlayerHero.toBase58()).to.equal(bobPDA.toBase58());
+  });
+
+  it("Alice tries to drop the item she no longer has, and it fails", async () => {
+    try {
+      await program.methods
+        .dropItem(playerHeroId, helper.getItemPDA(master.publicKey, itemId, program))
+        .accounts({ player: alice.publicKey })
+        .signers([alice])
+        .rpc();
+      expect.fail("Alice should not be able to drop an item she does not carry");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("ItemNotInInventory");
+    }
+  });
+
+  it("Bob drops the item", async () => {
+    const itemPDA = helper.getItemPDA(master.publicKey, itemId, program);
+    const bobPDA = helper.getPlayerHeroPDA(bob.publicKey, playerHeroId, program);
+
+    const tx = await program.methods
+      .dropItem(playerHeroId, itemPDA)
+      .accounts({ player: bob.publicKey })
+      .signers([bob])
+      .rpc();
+    await helper.confirmTransaction(tx);
+
+    const bobAccount = await program.account.playerHero.fetch(bobPDA);
+    expect(bobAccount.items).to.be.empty;
+
+    let event = events[events.length - 1];
+    expect(event.eventType.itemDropped.itemPubkey.toBase58()).to.equal(itemPDA.toBase58());
+  });
+});
\ No newline at end of file
@@ -0,0 +1,252 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { expect } from "chai";
+
+import { RealmVoyagers } from "../target/types/realm_voyagers";
+
+import * as helper from "./utils/helpers";
+import * as steps from "./utils/common-steps";
+
+describe("Journey session keys", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  // Program & keypairs
+  const program = anchor.workspace.RealmVoyagers as Program<RealmVoyagers>;
+  const realmMaster = anchor.web3.Keypair.generate();
+  const player = anchor.web3.Keypair.generate();
+  const sessionSigner = anchor.web3.Keypair.generate();
+  const shortSessionSigner = anchor.web3.Keypair.generate();
+  const travelSessionSigner = anchor.web3.Keypair.generate();
+  const staleSessionSigner = anchor.web3.Keypair.generate();
+  const stranger = anchor.web3.Keypair.generate();
+
+  // Session scopes
+  const MOVE_HERO = 1 << 0;
+  const TRAVEL_THROUGH_PORTAL = 1 << 1;
+
+  // Realm data
+  const realmId = "session_realm";
+  const realmDescription = { name: "Session Realm", details: "A realm for long journeys", logo: "https://example.com/logo123" };
+  const dimension = {
+    id: "plains",
+    name: "Plains",
+    areas: [
+      {
+        "id": 1,
+        "name": "Open Plains",
+        "area": { "topLeft": { "x": 0, "y": 0 }, "bottomRight": { "x": 100, "y": 100 } },
+        "tileset": "https://example.com/tileset_1.png",
+        "tilemap": "https://example.com/tilemap.json"
+      },
+    ]
+  };
+  const startingPosition = { x: 10, y: 10 };
+
+  // Listen events
+  let listeners = [];
+  let events = [];
+
+  before(async () => {
+    listeners.push(program.addEventListener("realmEvent", (event) => {
+      events.push(event);
+    }));
+    listeners.push(program.addEventListener("realmDimensionEvent", (event) => {
+      events.push(event);
+    }));
+    listeners.push(program.addEventListener("journeyEvent", (event) => {
+      events.push(event);
+    }));
+  });
+
+  after(async () => {
+    for (const listener of listeners) {
+      await program.removeEventListener(listener);
+    }
+  });
+
+  async function chainTime(): Promise<number> {
+    const slot = await provider.connection.getSlot();
+    return await provider.connection.getBlockTime(slot);
+  }
+
+  async function createSessionKey(authority: anchor.web3.PublicKey, scope: number, expiresAt: number) {
+    const tx = await program.methods
+      .createSessionKey(realmId, authority, scope, new anchor.BN(expiresAt))
+      .accounts({ player: player.publicKey })
+      .signers([player])
+      .rpc();
+    await helper.confirmTransaction(tx);
+  }
+
+  async function moveWithSessionKey(signer: anchor.web3.Keypair, position: any) {
+    const journeyPDA = helper.getJourneyPDA(realmId, player.publicKey, program);
+    const tx = await program.methods
+      .moveHero(realmId, position)
+      .accounts({
+        player: player.publicKey,
+        authority: signer.publicKey,
+        sessionKey: helper.getSessionKeyPDA(journeyPDA, signer.publicKey, program),
+        grid: null,
+      })
+      .signers([signer])
+      .rpc();
+    await helper.confirmTransaction(tx);
+  }
+
+  it("Airdrop to realm master", async () => await helper.airdrop(realmMaster.publicKey, 1 * anchor.web3.LAMPORTS_PER_SOL));
+  it("Create the realm", async () => await steps.createRealm(realmMaster, program, realmId, realmDescription, events));
+  it("Add the dimension", async () => steps.addRealmDimension(realmMaster, program, realmId, dimension, events));
+
+  it("Set the starting point", async () => {
+    let tx = await program.methods
+      .setRealmStartingPoint(realmId, dimension.id, startingPosition)
+      .accounts({ master: realmMaster.publicKey })
+      .signers([realmMaster])
+      .rpc();
+    await helper.confirmTransaction(tx);
+  });
+
+  it("Airdrop to player", async () => await helper.airdrop(player.publicKey, 1 * anchor.web3.LAMPORTS_PER_SOL));
+
+  it("Player starts the journey", async () => {
+    const tx = await program.methods
+      .startJourney(realmId, [], null)
+      .accounts({ player: player.publicKey, spawnPoint: null, playerHero: null, hero: null })
+      .signers([player])
+      .rpc();
+    await helper.confirmTransaction(tx);
+  });
+
+  it("Player creates a move-only session key", async () => {
+    const expiresAt = (await chainTime()) + 60 * 60;
+    await createSessionKey(sessionSigner.publicKey, MOVE_HERO, expiresAt);
+
+    const journeyPDA = helper.getJourneyPDA(realmId, player.publicKey, program);
+    const sessionKeyPDA = helper.getSessionKeyPDA(journeyPDA, sessionSigner.publicKey, program);
+    const sessionKey = await program.account.sessionKey.fetch(sessionKeyPDA);
+    expect(sessionKey.journey.toBase58()).to.equal(journeyPDA.toBase58());
+    expect(sessionKey.player.toBase58()).to.equal(player.publicKey.toBase58());
+    expect(sessionKey.authority.toBase58()).to.equal(sessionSigner.publicKey.toBase58());
+    expect(sessionKey.scope).to.equal(MOVE_HERO);
+    expect(sessionKey.expiresAt.toNumber()).to.equal(expiresAt);
+    const journeyAccount = await program.account.journey.fetch(journeyPDA);
+    expect(sessionKey.journeyStartedAt.toNumber()).to.equal(journeyAccount.startedAt.toNumber());
+    expect(sessionKey.journeyNonce.toNumber()).to.equal(journeyAccount.nonce.toNumber());
+
+    let event = events[events.length - 1];
+    expect(event.eventType.sessionKeyCreated.authority.toBase58()).to.equal(sessionSigner.publicKey.toBase58());
+    expect(event.eventType.sessionKeyCreated.scope).to.equal(MOVE_HERO);
+  });
+
+  it("Try to create a session key that is already expired, and it fails", async () => {
+    try {
+      await createSessionKey(stranger.publicKey, MOVE_HERO, (await chainTime()) - 1);
+      expect.fail("Session key should expire in the future");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("InvalidSessionExpiry");
+    }
+  });
+
+  it("Session key moves the hero without the player signing", async () => {
+    const newPosition = { x: 15, y: 10 };
+    await moveWithSessionKey(sessionSigner, newPosition);
+
+    const journeyPDA = helper.getJourneyPDA(realmId, player.publicKey, program);
+    const journeyAccount = await program.account.journey.fetch(journeyPDA);
+    expect(journeyAccount.position).to.deep.equal(newPosition);
+
+    let event = events[events.length - 1];
+    expect(event.eventType.heroMoved.to).to.deep.equal(newPosition);
+    expect(event.playerPubkey.toBase58()).to.equal(player.publicKey.toBase58());
+  });
+
+  it("Try to move the hero as a stranger, and it fails", async () => {
+    try {
+      await program.methods
+        .moveHero(realmId, { x: 20, y: 10 })
+        .accounts({ player: player.publicKey, authority: stranger.publicKey, sessionKey: null, grid: null })
+        .signers([stranger])
+        .rpc();
+      expect.fail("Only the player or their session keys can move the hero");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("UnauthorizedJourneyAuthority");
+    }
+  });
+
+  it("Try to move the hero with a travel-only session key, and it fails", async () => {
+    await createSessionKey(travelSessionSigner.publicKey, TRAVEL_THROUGH_PORTAL, (await chainTime()) + 60 * 60);
+
+    try {
+      await moveWithSessionKey(travelSessionSigner, { x: 20, y: 10 });
+      expect.fail("Session key should not move the hero outside of its scope");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("SessionScopeNotAllowed");
+    }
+  });
+
+  it("Try to move the hero with an expired session key, and it fails", async () => {
+    await createSessionKey(shortSessionSigner.publicKey, MOVE_HERO, (await chainTime()) + 2);
+    await new Promise((resolve) => setTimeout(resolve, 4000));
+
+    try {
+      await moveWithSessionKey(shortSessionSigner, { x: 20, y: 10 });
+      expect.fail("Expired session key should be rejected");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("SessionKeyExpired");
+    }
+  });
+
+  it("Player revokes the session key", async () => {
+    const journeyPDA = helper.getJourneyPDA(realmId, player.publicKey, program);
+    const sessionKeyPDA = helper.getSessionKeyPDA(journeyPDA, sessionSigner.publicKey, program);
+
+    const tx = await program.methods
+      .revokeSessionKey()
+      .accounts({ sessionKey: sessionKeyPDA, player: player.publicKey })
+      .signers([player])
+      .rpc();
+    await helper.confirmTransaction(tx);
+
+    expect(await provider.connection.getAccountInfo(sessionKeyPDA)).to.be.null;
+
+    let event = events[events.length - 1];
+    expect(event.eventType.sessionKeyRevoked.authority.toBase58()).to.equal(sessionSigner.publicKey.toBase58());
+  });
+
+  it("Try to move the hero with the revoked session key, and it fails", async () => {
+    try {
+      await moveWithSessionKey(sessionSigner, { x: 20, y: 10 });
+      expect.fail("Revoked session key should be rejected");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("AccountNotInitialized");
+    }
+  });
+
+  it("Player creates a session key, then ends the journey and starts a new one", async () => {
+    await createSessionKey(staleSessionSigner.publicKey, MOVE_HERO, (await chainTime()) + 60 * 60);
+
+    let tx = await program.methods
+      .endJourney(realmId)
+      .accounts({ player: player.publicKey })
+      .signers([player])
+      .rpc();
+    await helper.confirmTransaction(tx);
+
+    tx = await program.methods
+      .startJourney(realmId, [], null)
+      .accounts({ player: player.publicKey, spawnPoint: null, playerHero: null, hero: null })
+      .signers([player])
+      .rpc();
+    await helper.confirmTransaction(tx);
+  });
+
+  it("Try to move the hero with a session key of the previous journey, and it fails", async () => {
+    try {
+      await moveWithSessionKey(staleSessionSigner, { x: 15, y: 10 });
+      expect.fail("Session key of an ended journey should be rejected");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("StaleSessionKey");
+    }
+  });
+});
\ No newline at end of file
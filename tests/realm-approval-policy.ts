@@ -0,0 +1,290 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { expect } from "chai";
+
+import { RealmVoyagers } from "../target/types/realm_voyagers";
+
+import * as helper from "./utils/helpers";
+import * as steps from "./utils/common-steps";
+
+describe("Realm approval policy", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  // Program & keypairs
+  const program = anchor.workspace.RealmVoyagers as Program<RealmVoyagers>;
+  const alice = anchor.web3.Keypair.generate();
+  const bob = anchor.web3.Keypair.generate();
+  const carol = anchor.web3.Keypair.generate();
+
+  // Realm data
+  const realmId = "multisig_realm";
+  const realmDescription = { name: "Valuable Realm", details: "A realm guarded by its masters", logo: "https://example.com/logo123" };
+  const proposalId = "delete_realm";
+  const addCarolProposalId = "add_carol";
+  const approverMemberships = () => [alice.publicKey, bob.publicKey].map((master) => (
+    { pubkey: helper.getMasterPDA(realmId, master, program), isSigner: false, isWritable: false }
+  ));
+
+  // Listen events
+  let listeners = [];
+  let events = [];
+
+  before(async () => {
+    listeners.push(program.addEventListener("realmEvent", (event) => {
+      events.push(event);
+    }));
+    listeners.push(program.addEventListener("realmProposalEvent", (event) => {
+      events.push(event);
+    }));
+  });
+
+  after(async () => {
+    for (const listener of listeners) {
+      await program.removeEventListener(listener);
+    }
+  });
+
+  it("Airdrop to Alice", async () => await helper.airdrop(alice.publicKey, 1 * anchor.web3.LAMPORTS_PER_SOL));
+  it("Airdrop to Bob", async () => await helper.airdrop(bob.publicKey, 1 * anchor.web3.LAMPORTS_PER_SOL));
+  it("Alice creates a realm", async () => await steps.createRealm(alice, program, realmId, realmDescription, events));
+  it("Alice adds Bob as a realm admin", async () => await steps.addRealmMaster(alice, program, realmId, bob.publicKey, { admin: {} }, events));
+
+  it("Alice grants Bob the permission to approve proposals", async () => {
+    const approveProposalsPermission = 1 << 9;
+    let tx = await program.methods
+      .updateRealmMasterPermissions(realmId, bob.publicKey, approveProposalsPermission, 0)
+      .accounts({ master: alice.publicKey, proposal: null, proposer: null })
+      .signers([alice])
+      .rpc();
+    await helper.confirmTransaction(tx);
+
+    const membership = await program.account.realmMembership.fetch(helper.getMasterPDA(realmId, bob.publicKey, program));
+    expect(membership.master.permissions & approveProposalsPermission).to.equal(approveProposalsPermission);
+  });
+
+  it("Alice tries to require more approvals than there are masters, and it fails", async () => {
+    try {
+      await program.methods
+        .setRealmApprovalThreshold(realmId, 3)
+        .accounts({ master: alice.publicKey, proposal: null, proposer: null })
+        .remainingAccounts(approverMemberships())
+        .signers([alice])
+        .rpc();
+      expect.fail("Threshold should not exceed the number of approvers");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("InvalidApprovalThreshold");
+    }
+  });
+
+  it("Alice enables the 2-of-2 approval policy", async () => {
+    let tx = await program.methods
+      .setRealmApprovalThreshold(realmId, 2)
+      .accounts({ master: alice.publicKey, proposal: null, proposer: null })
+      .remainingAccounts(approverMemberships())
+      .signers([alice])
+      .rpc();
+    await helper.confirmTransaction(tx);
+
+    const realmAccount = await program.account.realm.fetch(helper.getRealmPDA(realmId, program));
+    expect(realmAccount.approvalThreshold).to.equal(2);
+
+    let event = events[events.length - 1];
+    expect(event.eventType.realmApprovalThresholdChanged.threshold).to.equal(2);
+  });
+
+  it("Alice tries to add Carol as a realm admin directly, and it fails", async () => {
+    try {
+      await program.methods
+        .addRealmMaster(realmId, carol.publicKey, { admin: {} })
+        .accounts({ master: alice.publicKey, proposal: null, proposer: null })
+        .signers([alice])
+        .rpc();
+      expect.fail("Masters should not be added without an approved proposal");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("ProposalRequired");
+    }
+  });
+
+  it("Alice proposes to add Carol and Bob rejects it", async () => {
+    let tx = await program.methods
+      .createRealmProposal(realmId, addCarolProposalId, { addMaster: { master: carol.publicKey, role: { admin: {} } } })
+      .accounts({ master: alice.publicKey })
+      .signers([alice])
+      .rpc();
+    await helper.confirmTransaction(tx);
+
+    tx = await program.methods
+      .rejectRealmProposal(realmId, addCarolProposalId)
+      .accounts({ master: bob.publicKey })
+      .signers([bob])
+      .rpc();
+    await helper.confirmTransaction(tx);
+
+    let event = events[events.length - 1];
+    expect(event.eventType.proposalRejected.master.toBase58()).to.equal(bob.publicKey.toBase58());
+  });
+
+  it("Alice tries to add Carol through the rejected proposal, and it fails", async () => {
+    try {
+      await program.methods
+        .addRealmMaster(realmId, carol.publicKey, { admin: {} })
+        .accounts({ master: alice.publicKey, proposal: helper.getProposalPDA(realmId, addCarolProposalId, program), proposer: alice.publicKey })
+        .remainingAccounts(approverMemberships())
+        .signers([alice])
+        .rpc();
+      expect.fail("Rejected proposals should not be executed");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("ProposalRejected");
+    }
+  });
+
+  it("Alice cancels the rejected proposal", async () => {
+    let tx = await program.methods
+      .cancelRealmProposal(realmId, addCarolProposalId)
+      .accounts({ proposer: alice.publicKey })
+      .signers([alice])
+      .rpc();
+    await helper.confirmTransaction(tx);
+
+    expect(await anchor.getProvider().connection.getAccountInfo(helper.getProposalPDA(realmId, addCarolProposalId, program))).to.be.null;
+  });
+
+  it("Alice tries to remove Bob directly, and it fails", async () => {
+    try {
+      await program.methods
+        .removeRealmMaster(realmId, bob.publicKey)
+        .accounts({ master: alice.publicKey, proposal: null, proposer: null })
+        .remainingAccounts(approverMemberships())
+        .signers([alice])
+        .rpc();
+      expect.fail("Masters should not be removed without an approved proposal");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("ProposalRequired");
+    }
+  });
+
+  it("Bob tries to resign below the approval threshold, and it fails", async () => {
+    try {
+      await program.methods
+        .resignRealmMaster(realmId)
+        .accounts({ master: bob.publicKey })
+        .remainingAccounts(approverMemberships())
+        .signers([bob])
+        .rpc();
+      expect.fail("Resigning should not leave too few approvers for the threshold");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("ApprovalThresholdUnreachable");
+    }
+  });
+
+  it("Alice tries to delete the realm directly, and it fails", async () => {
+    try {
+      await program.methods
+        .deleteRealm(realmId)
+        .accounts({ master: alice.publicKey, proposal: null, proposer: null, queuedAction: null })
+        .signers([alice])
+        .rpc();
+      expect.fail("Realm should not be deleted without an approved proposal");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("ProposalRequired");
+    }
+  });
+
+  it("Alice proposes to delete the realm", async () => {
+    const proposalPDA = helper.getProposalPDA(realmId, proposalId, program);
+
+    let tx = await program.methods
+      .createRealmProposal(realmId, proposalId, { deleteRealm: {} })
+      .accounts({ master: alice.publicKey })
+      .signers([alice])
+      .rpc();
+    await helper.confirmTransaction(tx);
+
+    const proposalAccount = await program.account.realmProposal.fetch(proposalPDA);
+    expect(proposalAccount.action).to.deep.equal({ deleteRealm: {} });
+    expect(proposalAccount.approvals.map((a) => a.toBase58())).to.deep.equal([alice.publicKey.toBase58()]);
+
+    let event = events[events.length - 1];
+    expect(event.eventType.proposalCreated.action).to.deep.equal({ deleteRealm: {} });
+    expect(event.proposalPubkey.toBase58()).to.equal(proposalPDA.toBase58());
+  });
+
+  it("Alice tries to delete the realm with a half-approved proposal, and it fails", async () => {
+    try {
+      await program.methods
+        .deleteRealm(realmId)
+        .accounts({ master: alice.publicKey, proposal: helper.getProposalPDA(realmId, proposalId, program), proposer: alice.publicKey, queuedAction: null })
+        .signers([alice])
+        .rpc();
+      expect.fail("Realm should not be deleted with a single approval");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("ProposalNotApproved");
+    }
+  });
+
+  it("Alice tries to approve twice, and it fails", async () => {
+    try {
+      await program.methods
+        .approveRealmProposal(realmId, proposalId)
+        .accounts({ master: alice.publicKey })
+        .signers([alice])
+        .rpc();
+      expect.fail("Alice should not vote twice");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("AlreadyVoted");
+    }
+  });
+
+  it("Bob approves the proposal", async () => {
+    let tx = await program.methods
+      .approveRealmProposal(realmId, proposalId)
+      .accounts({ master: bob.publicKey })
+      .signers([bob])
+      .rpc();
+    await helper.confirmTransaction(tx);
+
+    let event = events[events.length - 1];
+    expect(event.eventType.proposalApproved.master.toBase58()).to.equal(bob.publicKey.toBase58());
+    expect(event.eventType.proposalApproved.approvals).to.equal(2);
+  });
+
+  it("Alice tries to delete the realm without proving Bob's approval, and it fails", async () => {
+    try {
+      await program.methods
+        .deleteRealm(realmId)
+        .accounts({ master: alice.publicKey, proposal: helper.getProposalPDA(realmId, proposalId, program), proposer: alice.publicKey, queuedAction: null })
+        .remainingAccounts([{ pubkey: helper.getMasterPDA(realmId, alice.publicKey, program), isSigner: false, isWritable: false }])
+        .signers([alice])
+        .rpc();
+      expect.fail("Approvals should only count for approvers proven by their memberships");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("ProposalNotApproved");
+    }
+  });
+
+  it("Alice deletes the realm through the approved proposal", async () => {
+    const realmPDA = helper.getRealmPDA(realmId, program);
+    const proposalPDA = helper.getProposalPDA(realmId, proposalId, program);
+
+    let tx = await program.methods
+      .deleteRealm(realmId)
+      .accounts({ master: alice.publicKey, proposal: proposalPDA, proposer: alice.publicKey, queuedAction: null })
+      .remainingAccounts([
+        { pubkey: helper.getMasterPDA(realmId, alice.publicKey, program), isSigner: false, isWritable: false },
+        { pubkey: helper.getMasterPDA(realmId, bob.publicKey, program), isSigner: false, isWritable: true },
+      ])
+      .signers([alice])
+      .rpc();
+    await helper.confirmTransaction(tx);
+
+    expect(await anchor.getProvider().connection.getAccountInfo(realmPDA)).to.be.null;
+    expect(await anchor.getProvider().connection.getAccountInfo(proposalPDA)).to.be.null;
+    expect(await anchor.getProvider().connection.getAccountInfo(helper.getMasterPDA(realmId, bob.publicKey, program))).to.be.null;
+
+    let event = events[events.length - 1];
+    expect(event.eventType.realmDeleted).not.to.be.undefined;
+    event = events[events.length - 2];
+    expect(event.eventType.proposalExecuted).not.to.be.undefined;
+  });
+});
\ No newline at end of file
@@ -0,0 +1,195 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { expect } from "chai";
+
+import { RealmVoyagers } from "../target/types/realm_voyagers";
+
+import * as helper from "./utils/helpers";
+import * as steps from "./utils/common-steps";
+
+describe("Realm deletion refunds", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  // Program & keypairs
+  const program = anchor.workspace.RealmVoyagers as Program<RealmVoyagers>;
+  const alice = anchor.web3.Keypair.generate();
+  const bob = anchor.web3.Keypair.generate();
+  const carol = anchor.web3.Keypair.generate();
+  const player = anchor.web3.Keypair.generate();
+
+  // Realm data
+  const realmId = "shared_realm";
+  const realmDescription = { name: "Shared Realm", details: "A realm built by several admins", logo: "https://example.com/logo123" };
+  const area = {
+    "id": 1,
+    "name": "Meadow",
+    "area": { "topLeft": { "x": 0, "y": 0 }, "bottomRight": { "x": 100, "y": 100 } },
+    "tileset": "https://example.com/tileset.png",
+    "tilemap": "https://example.com/tilemap.json"
+  };
+  const aliceDimension = { id: "alice_meadow", name: "Alice Meadow", areas: [area] };
+  const bobDimension = { id: "bob_forest", name: "Bob Forest", areas: [area] };
+  const carolDimension = { id: "carol_lake", name: "Carol Lake", areas: [area] };
+  const carolCellar = { id: "carol_cellar", name: "Carol Cellar", areas: [area] };
+  const startingPosition = { x: 10, y: 10 };
+
+  // Listen events
+  let listeners = [];
+  let events = [];
+
+  before(async () => {
+    listeners.push(program.addEventListener("realmEvent", (event) => {
+      events.push(event);
+    }));
+    listeners.push(program.addEventListener("realmDimensionEvent", (event) => {
+      events.push(event);
+    }));
+    listeners.push(program.addEventListener("journeyEvent", (event) => {
+      events.push(event);
+    }));
+  });
+
+  after(async () => {
+    for (const listener of listeners) {
+      await program.removeEventListener(listener);
+    }
+  });
+
+  async function setStartingPoint() {
+    let tx = await program.methods
+      .setRealmStartingPoint(realmId, aliceDimension.id, startingPosition)
+      .accounts({ master: alice.publicKey })
+      .signers([alice])
+      .rpc();
+    await helper.confirmTransaction(tx);
+  }
+
+  it("Airdrop to Alice", async () => await helper.airdrop(alice.publicKey, 1 * anchor.web3.LAMPORTS_PER_SOL));
+  it("Airdrop to Bob", async () => await helper.airdrop(bob.publicKey, 1 * anchor.web3.LAMPORTS_PER_SOL));
+  it("Airdrop to Carol", async () => await helper.airdrop(carol.publicKey, 1 * anchor.web3.LAMPORTS_PER_SOL));
+  it("Airdrop to player", async () => await helper.airdrop(player.publicKey, 1 * anchor.web3.LAMPORTS_PER_SOL));
+
+  it("Alice creates a realm", async () => await steps.createRealm(alice, program, realmId, realmDescription, events));
+  it("Alice adds Bob as a realm admin", async () => await steps.addRealmMaster(alice, program, realmId, bob.publicKey, { admin: {} }, events));
+  it("Alice adds Carol as a realm admin", async () => await steps.addRealmMaster(alice, program, realmId, carol.publicKey, { admin: {} }, events));
+
+  it("Alice adds her dimension", async () => await steps.addRealmDimension(alice, program, realmId, aliceDimension, events));
+  it("Bob adds his dimension", async () => await steps.addRealmDimension(bob, program, realmId, bobDimension, events));
+  it("Carol adds her dimension", async () => await steps.addRealmDimension(carol, program, realmId, carolDimension, events));
+  it("Carol adds her cellar", async () => await steps.addRealmDimension(carol, program, realmId, carolCellar, events));
+
+  it("Alice removes Carol's cellar and Carol gets the rent back", async () => {
+    const connection = anchor.getProvider().connection;
+    const cellarRent = await connection.getBalance(helper.getDimensionPDA(realmId, carolCellar.id, program));
+    const carolBalanceBefore = await connection.getBalance(carol.publicKey);
+
+    await steps.removeRealmDimension(alice, program, realmId, carolCellar.id, events, [], carol.publicKey);
+
+    expect(await connection.getBalance(carol.publicKey)).to.equal(carolBalanceBefore + cellarRent);
+  });
+  it("Alice sets the starting point", async () => await setStartingPoint());
+
+  it("Player starts a journey", async () => {
+    const tx = await program.methods
+      .startJourney(realmId, [], null)
+      .accounts({ player: player.publicKey, spawnPoint: null, playerHero: null, hero: null })
+      .signers([player])
+      .rpc();
+    await helper.confirmTransaction(tx);
+  });
+
+  it("Alice tries to delete the realm without Bob's account, and it fails", async () => {
+    const remainingAccounts = [aliceDimension, bobDimension, carolDimension]
+      .map((dimension) => ({ pubkey: helper.getDimensionPDA(realmId, dimension.id, program), isSigner: false, isWritable: true }));
+    remainingAccounts.push({ pubkey: carol.publicKey, isSigner: false, isWritable: true });
+
+    try {
+      await program.methods
+        .deleteRealm(realmId)
+        .accounts({ master: alice.publicKey, proposal: null, proposer: null, queuedAction: null })
+        .remainingAccounts(remainingAccounts)
+        .signers([alice])
+        .rpc();
+      expect.fail("Dimension rent should not go anywhere but to its owner");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("DimensionOwnerNotProvided");
+    }
+  });
+
+  it("Alice deletes the realm and every dimension owner gets the rent back", async () => {
+    const connection = anchor.getProvider().connection;
+    const bobDimensionPDA = helper.getDimensionPDA(realmId, bobDimension.id, program);
+    const carolDimensionPDA = helper.getDimensionPDA(realmId, carolDimension.id, program);
+
+    const bobDimensionRent = await connection.getBalance(bobDimensionPDA);
+    const carolDimensionRent = await connection.getBalance(carolDimensionPDA);
+    const bobBalanceBefore = await connection.getBalance(bob.publicKey);
+    const carolBalanceBefore = await connection.getBalance(carol.publicKey);
+
+    const remainingAccounts = [aliceDimension, bobDimension, carolDimension]
+      .map((dimension) => ({ pubkey: helper.getDimensionPDA(realmId, dimension.id, program), isSigner: false, isWritable: true }));
+    for (const owner of [bob.publicKey, carol.publicKey]) {
+      remainingAccounts.push({ pubkey: owner, isSigner: false, isWritable: true });
+      remainingAccounts.push({ pubkey: helper.getMasterPDA(realmId, owner, program), isSigner: false, isWritable: true });
+    }
+
+    const tx = await program.methods
+      .deleteRealm(realmId)
+      .accounts({ master: alice.publicKey, proposal: null, proposer: null, queuedAction: null })
+      .remainingAccounts(remainingAccounts)
+      .signers([alice])
+      .rpc();
+    await helper.confirmTransaction(tx);
+
+    expect(await connection.getBalance(bob.publicKey)).to.equal(bobBalanceBefore + bobDimensionRent);
+    expect(await connection.getBalance(carol.publicKey)).to.equal(carolBalanceBefore + carolDimensionRent);
+
+    for (const dimension of [aliceDimension, bobDimension, carolDimension]) {
+      const dimensionInfo = await connection.getAccountInfo(helper.getDimensionPDA(realmId, dimension.id, program));
+      expect(dimensionInfo).to.be.null;
+    }
+    expect(await connection.getAccountInfo(helper.getRealmPDA(realmId, program))).to.be.null;
+
+    let event = events[events.length - 1];
+    expect(event.eventType.realmDeleted).not.to.be.undefined;
+  });
+
+  it("Player's journey outlives the realm", async () => {
+    const journeyPDA = helper.getJourneyPDA(realmId, player.publicKey, program);
+    const journeyAccount = await program.account.journey.fetch(journeyPDA);
+    expect(journeyAccount.realm.toBase58()).to.equal(helper.getRealmPDA(realmId, program).toBase58());
+  });
+
+  it("Alice recreates the realm under the same id", async () => {
+    await steps.createRealm(alice, program, realmId, realmDescription, events);
+    await steps.addRealmDimension(alice, program, realmId, aliceDimension, events);
+    await setStartingPoint();
+  });
+
+  it("Player tries to resume the old journey, and it fails", async () => {
+    try {
+      await program.methods
+        .moveHero(realmId, { x: 15, y: 10 })
+        .accounts({ player: player.publicKey, authority: player.publicKey, sessionKey: null, grid: null })
+        .signers([player])
+        .rpc();
+      expect.fail("Journey from the deleted realm should not be resumed");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("StaleJourney");
+    }
+  });
+
+  it("Player ends the old journey and gets the rent back", async () => {
+    const journeyPDA = helper.getJourneyPDA(realmId, player.publicKey, program);
+
+    const tx = await program.methods
+      .endJourney(realmId)
+      .accounts({ player: player.publicKey })
+      .signers([player])
+      .rpc();
+    await helper.confirmTransaction(tx);
+
+    expect(await anchor.getProvider().connection.getAccountInfo(journeyPDA)).to.be.null;
+  });
+});
\ No newline at end of file
@@ -14,6 +14,8 @@ describe("Realm Management", () => {
   // Program & keypairs
   const program = anchor.workspace.RealmVoyagers as Program<RealmVoyagers>;
   const realmMaster = anchor.web3.Keypair.generate();
+  // Owner of the legacy realm loaded from tests/fixtures
+  const legacyMaster = anchor.web3.Keypair.fromSeed(new Uint8Array(32).fill(7));
 
   // Realms data
   const firstRealmId = "realm_id_1";
@@ -21,6 +23,8 @@ describe("Realm Management", () => {
   const firstRealmDescription = { name: "Test Realm 1", details: "A test realm", logo: "https://example.com/logo1" };
   const firstRealmUpdatedDescription = { name: "Updated Realm 1", details: "An updated description", logo: "https://example.com/logo1" };
   const secondRealmDescription = { name: "Test Realm 2", details: "Another test realm", logo: "https://example.com/logo2" };
+  const legacyRealmId = "legacy_realm";
+  const legacyDimensionId = "legacy_dimension";
   const secondRealmUpdatedDescription = { name: "Updated Realm 2", details: "Another updated description", logo: "https://example.com/logo2" };
 
   // Listen events
@@ -56,7 +60,7 @@ describe("Realm Management", () => {
     try {
       await program.methods
         .deleteRealm("unexisting_realm")
-        .accounts({ master: realmMaster.publicKey })
+        .accounts({ master: realmMaster.publicKey, proposal: null, proposer: null, queuedAction: null })
         .signers([realmMaster])
         .rpc();
     } catch (err) {
@@ -64,6 +68,19 @@ describe("Realm Management", () => {
     }
   });
 
+  it("Try to migrate masters of an up-to-date realm, and it fails", async () => {
+    try {
+      await program.methods
+        .migrateRealmMasters(firstRealmId)
+        .accounts({ master: realmMaster.publicKey })
+        .signers([realmMaster])
+        .rpc();
+      expect.fail("Realm masters are already stored in membership accounts");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("RealmAlreadyMigrated");
+    }
+  });
+
   it("Create the second realm", async () => await steps.createRealm(realmMaster, program, secondRealmId, secondRealmDescription, events));
   it("Update first realm", async () => await steps.updateRealmDescription(realmMaster, program, firstRealmId, firstRealmUpdatedDescription, events));
   it("Delete the first realm", async () => await steps.deleteRealm(realmMaster, program, firstRealmId, [], events));
@@ -81,4 +98,44 @@ describe("Realm Management", () => {
       expect(err.error.errorCode.code).to.equal("AccountNotInitialized");
     }
   });
-});
+
+  it("Airdrop to the legacy realm master", async () => await helper.airdrop(legacyMaster.publicKey, 1 * anchor.web3.LAMPORTS_PER_SOL));
+
+  it("Migrate the legacy realm along with its dimension", async () => {
+    const realmPDA = helper.getRealmPDA(legacyRealmId, program);
+    const membershipPDA = helper.getMasterPDA(legacyRealmId, legacyMaster.publicKey, program);
+    const dimensionPDA = helper.getDimensionPDA(legacyRealmId, legacyDimensionId, program);
+
+    let tx = await program.methods
+      .migrateRealmMasters(legacyRealmId)
+      .accounts({ master: legacyMaster.publicKey })
+      .remainingAccounts([
+        { pubkey: membershipPDA, isSigner: false, isWritable: true },
+        { pubkey: dimensionPDA, isSigner: false, isWritable: true },
+      ])
+      .signers([legacyMaster])
+      .rpc();
+    await helper.confirmTransaction(tx);
+
+    const realmAccount = await program.account.realm.fetch(realmPDA);
+    expect(realmAccount.owner.toBase58()).to.equal(legacyMaster.publicKey.toBase58());
+    expect(realmAccount.mastersCount).to.equal(1);
+    expect(realmAccount.masters).to.be.empty;
+    expect(realmAccount.dimensions.map((dimension) => dimension.toBase58())).to.deep.equal([dimensionPDA.toBase58()]);
+
+    const membershipAccount = await program.account.realmMembership.fetch(membershipPDA);
+    expect(membershipAccount.realm.toBase58()).to.equal(realmPDA.toBase58());
+    expect(membershipAccount.master.pubkey.toBase58()).to.equal(legacyMaster.publicKey.toBase58());
+    expect(membershipAccount.master.role).to.deep.equal({ owner: {} });
+
+    const dimensionAccount = await program.account.realmDimension.fetch(dimensionPDA);
+    expect(dimensionAccount.owner.toBase58()).to.equal(legacyMaster.publicKey.toBase58());
+    expect(dimensionAccount.areas.map((area) => area.id)).to.deep.equal([0]);
+    expect(dimensionAccount.allowOverlappingAreas).to.be.true;
+
+    let event = events[events.length - 1];
+    expect(event.eventType.realmMastersMigrated.mastersCount).to.equal(1);
+  });
+
+  it("Delete the migrated realm", async () => await steps.deleteRealm(legacyMaster, program, legacyRealmId, [legacyDimensionId], events));
+});
\ No newline at end of file
@@ -0,0 +1,115 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { expect } from "chai";
+
+import { RealmVoyagers } from "../target/types/realm_voyagers";
+
+import * as helper from "./utils/helpers";
+import * as steps from "./utils/common-steps";
+
+describe("Realm master resignation", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  // Program & keypairs
+  const program = anchor.workspace.RealmVoyagers as Program<RealmVoyagers>;
+  const alice = anchor.web3.Keypair.generate();
+  const bob = anchor.web3.Keypair.generate();
+
+  // Realm data
+  const realmId = "resignation_realm";
+  const realmDescription = { name: "Test Realm", details: "A realm with a leaving admin", logo: "https://example.com/logo123" };
+  const dimension = { id: "bob_tower", name: "Bob's Tower", areas: [] };
+  const aliceDimension = { id: "alice_keep", name: "Alice's Keep", areas: [] };
+
+  // Listen events
+  let listeners = [];
+  let events = [];
+
+  before(async () => {
+    listeners.push(program.addEventListener("realmEvent", (event) => {
+      events.push(event);
+    }));
+    listeners.push(program.addEventListener("realmDimensionEvent", (event) => {
+      events.push(event);
+    }));
+  });
+
+  after(async () => {
+    for (const listener of listeners) {
+      await program.removeEventListener(listener);
+    }
+  });
+
+  it("Airdrop to Alice", async () => await helper.airdrop(alice.publicKey, 1 * anchor.web3.LAMPORTS_PER_SOL));
+  it("Airdrop to Bob", async () => await helper.airdrop(bob.publicKey, 1 * anchor.web3.LAMPORTS_PER_SOL));
+  it("Alice creates a realm", async () => await steps.createRealm(alice, program, realmId, realmDescription, events));
+  it("Alice adds Bob as a realm admin", async () => await steps.addRealmMaster(alice, program, realmId, bob.publicKey, { admin: {} }, events));
+  it("Bob adds a dimension", async () => await steps.addRealmDimension(bob, program, realmId, dimension, events));
+  it("Alice adds a dimension", async () => await steps.addRealmDimension(alice, program, realmId, aliceDimension, events));
+
+  it("Memberships count the dimensions owned by each master", async () => {
+    const aliceMembership = await program.account.realmMembership.fetch(helper.getMasterPDA(realmId, alice.publicKey, program));
+    const bobMembership = await program.account.realmMembership.fetch(helper.getMasterPDA(realmId, bob.publicKey, program));
+    expect(aliceMembership.dimensionsCount).to.equal(1);
+    expect(bobMembership.dimensionsCount).to.equal(1);
+  });
+
+  it("Alice tries to resign as the owner, and it fails", async () => {
+    try {
+      await program.methods
+        .resignRealmMaster(realmId)
+        .accounts({ master: alice.publicKey })
+        .signers([alice])
+        .rpc();
+      expect.fail("Owner should not be able to resign");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("CantRemoveRealmOwner");
+    }
+  });
+
+  it("Bob tries to resign without listing his dimension, and it fails", async () => {
+    try {
+      await program.methods
+        .resignRealmMaster(realmId)
+        .accounts({ master: bob.publicKey })
+        .signers([bob])
+        .rpc();
+      expect.fail("Resignation should not leave dimensions owned by a former master");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("DimensionNotProvided");
+    }
+  });
+
+  it("Bob resigns listing only his own dimension and hands it over to Alice", async () => {
+    const realmPDA = helper.getRealmPDA(realmId, program);
+    const dimensionPDA = helper.getDimensionPDA(realmId, dimension.id, program);
+    const balanceBefore = await anchor.getProvider().connection.getBalance(bob.publicKey);
+
+    let tx = await program.methods
+      .resignRealmMaster(realmId)
+      .accounts({ master: bob.publicKey })
+      .remainingAccounts([{ pubkey: dimensionPDA, isSigner: false, isWritable: true }])
+      .signers([bob])
+      .rpc();
+    await helper.confirmTransaction(tx);
+
+    const realmAccount = await program.account.realm.fetch(realmPDA);
+    expect(realmAccount.mastersCount).to.equal(1);
+    expect(await anchor.getProvider().connection.getAccountInfo(helper.getMasterPDA(realmId, bob.publicKey, program))).to.be.null;
+
+    const dimensionAccount = await program.account.realmDimension.fetch(dimensionPDA);
+    expect(dimensionAccount.owner.toBase58()).to.equal(alice.publicKey.toBase58());
+
+    const aliceMembership = await program.account.realmMembership.fetch(helper.getMasterPDA(realmId, alice.publicKey, program));
+    expect(aliceMembership.dimensionsCount).to.equal(2);
+
+    const balanceAfter = await anchor.getProvider().connection.getBalance(bob.publicKey);
+    expect(balanceAfter).to.be.above(balanceBefore);
+
+    let event = events[events.length - 1];
+    expect(event.eventType.realmMasterResigned.master.pubkey.toBase58()).to.equal(bob.publicKey.toBase58());
+    event = events[events.length - 2];
+    expect(event.eventType.dimensionOwnerChanged.owner.toBase58()).to.equal(alice.publicKey.toBase58());
+  });
+});
\ No newline at end of file
@@ -48,49 +48,191 @@ describe("Realm ownership flows", () => {
         .rpc();
       expect.fail("Bob should not be authorized to update the realm");
     } catch (err) {
-      expect(err.error.errorCode.code).to.equal("UnauthorizedRealmMaster");
+      expect(err.error.errorCode.code).to.equal("AccountNotInitialized");
     }
   });
 
   it("Bob tries to add himself as a realm master, and it fails", async () => {
     try {
       await program.methods
-        .addRealmMaster(realmId, bob.publicKey)
-        .accounts({ master: bob.publicKey })
+        .addRealmMaster(realmId, bob.publicKey, { admin: {} })
+        .accounts({ master: bob.publicKey, proposal: null, proposer: null })
         .signers([bob])
         .rpc();
       expect.fail("Bob should not be authorized to add himself as realm master");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("AccountNotInitialized");
+    }
+  });
+
+  it("Alice tries to add Bob as a second owner, and it fails", async () => {
+    try {
+      await program.methods
+        .addRealmMaster(realmId, bob.publicKey, { owner: {} })
+        .accounts({ master: alice.publicKey, proposal: null, proposer: null })
+        .signers([alice])
+        .rpc();
+      expect.fail("Realm should not have a second owner");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("CantAssignOwnerRole");
+    }
+  });
+
+  it("Alice adds Bob as a realm curator", async () => await steps.addRealmMaster(alice, program, realmId, bob.publicKey, { curator: {} }, events));
+
+  it("Bob as a curator tries to update the realm, and it fails", async () => {
+    try {
+      await program.methods
+        .updateRealmDescription(realmId, updatedRealmDescription)
+        .accounts({ master: bob.publicKey })
+        .signers([bob])
+        .rpc();
+      expect.fail("Curator should not be authorized to update the realm");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("UnauthorizedRealmMaster");
+    }
+  });
+
+  it("Alice grants Bob the permission to update the realm", async () => {
+    const updateRealmPermission = 1 << 0;
+    let tx = await program.methods
+      .updateRealmMasterPermissions(realmId, bob.publicKey, updateRealmPermission, 0)
+      .accounts({ master: alice.publicKey, proposal: null, proposer: null })
+      .signers([alice])
+      .rpc();
+    await helper.confirmTransaction(tx);
+
+    const membership = await program.account.realmMembership.fetch(helper.getMasterPDA(realmId, bob.publicKey, program));
+    expect(membership.master.role).to.deep.equal({ curator: {} });
+    expect(membership.master.permissions).to.equal(updateRealmPermission);
+
+    let event = events[events.length - 1];
+    expect(event.eventType.realmMasterPermissionsChanged.master.pubkey.toBase58()).to.equal(bob.publicKey.toBase58());
+    expect(event.eventType.realmMasterPermissionsChanged.previousPermissions).to.equal(0);
+  });
+
+  it("Bob as a curator updates the realm with the granted permission", async () => await steps.updateRealmDescription(bob, program, realmId, updatedRealmDescription, events));
+
+  it("Alice grants Bob the permission to manage masters", async () => {
+    const manageMastersPermission = 1 << 2;
+    let tx = await program.methods
+      .updateRealmMasterPermissions(realmId, bob.publicKey, manageMastersPermission, 0)
+      .accounts({ master: alice.publicKey, proposal: null, proposer: null })
+      .signers([alice])
+      .rpc();
+    await helper.confirmTransaction(tx);
+
+    const membership = await program.account.realmMembership.fetch(helper.getMasterPDA(realmId, bob.publicKey, program));
+    expect(membership.master.permissions & manageMastersPermission).to.equal(manageMastersPermission);
+  });
+
+  it("Bob tries to promote himself to a realm admin, and it fails", async () => {
+    try {
+      await program.methods
+        .setRealmMasterRole(realmId, bob.publicKey, { admin: {} })
+        .accounts({ master: bob.publicKey, proposal: null, proposer: null })
+        .signers([bob])
+        .rpc();
+      expect.fail("Bob should not assign a role with permissions he does not have");
     } catch (err) {
       expect(err.error.errorCode.code).to.equal("UnauthorizedRealmMaster");
     }
+
+    const membership = await program.account.realmMembership.fetch(helper.getMasterPDA(realmId, bob.publicKey, program));
+    expect(membership.master.role).to.deep.equal({ curator: {} });
+  });
+
+  it("Alice tries to revoke her own permissions, and it fails", async () => {
+    try {
+      await program.methods
+        .updateRealmMasterPermissions(realmId, alice.publicKey, 0, 1 << 1)
+        .accounts({ master: alice.publicKey, proposal: null, proposer: null })
+        .signers([alice])
+        .rpc();
+      expect.fail("Owner permissions should not be changed");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("CantChangeOwnerPermissions");
+    }
   });
 
-  it("Alice adds Bob as a realm master", async () => await steps.addRealmMaster(alice, program, realmId, bob.publicKey, events));
+  it("Alice tries to demote herself, and it fails", async () => {
+    try {
+      await program.methods
+        .setRealmMasterRole(realmId, alice.publicKey, { admin: {} })
+        .accounts({ master: alice.publicKey, proposal: null, proposer: null })
+        .signers([alice])
+        .rpc();
+      expect.fail("Realm should not lose its owner");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("CantRemoveRealmOwner");
+    }
+  });
+
+  it("Alice promotes Bob to a realm admin", async () => await steps.setRealmMasterRole(alice, program, realmId, bob.publicKey, { admin: {} }, events));
   it("Bob updates the realm successfully", async () => await steps.updateRealmDescription(bob, program, realmId, updatedRealmDescription, events));
 
-  it("Bob tries to transfer ownership to himself, and it fails", async () => {
+  it("Alice grants Bob the permission to manage masters as an admin", async () => {
+    let tx = await program.methods
+      .updateRealmMasterPermissions(realmId, bob.publicKey, 1 << 2, 0)
+      .accounts({ master: alice.publicKey, proposal: null, proposer: null })
+      .signers([alice])
+      .rpc();
+    await helper.confirmTransaction(tx);
+  });
+
+  it("Bob tries to propose himself as the owner, and it fails", async () => {
     try {
       await program.methods
-        .transferRealmOwnership(realmId, bob.publicKey)
-        .accounts({ master: bob.publicKey })
+        .proposeRealmOwner(realmId, bob.publicKey)
+        .accounts({ master: bob.publicKey, proposal: null, proposer: null, queuedAction: null })
         .signers([bob])
         .rpc();
-      expect.fail("Bob should not be authorized to transfer realm ownership");
+      expect.fail("Bob should not be authorized to propose a realm owner");
     } catch (err) {
       expect(err.error.errorCode.code).to.equal("UnauthorizedRealmMaster");
     }
 
-    // Ensure that the realm description has not been updated
+    // Ensure that the realm ownership has not been changed
     const realmPDA = helper.getRealmPDA(realmId, program);
     const realmAccount = await program.account.realm.fetch(realmPDA);
-    expect(realmAccount.masters.length).to.equal(2);
-    expect(realmAccount.masters[0].pubkey.toBase58()).to.equal(alice.publicKey.toBase58());
-    expect(realmAccount.masters[0].role).to.deep.equal({ owner: {} });
-    expect(realmAccount.masters[1].pubkey.toBase58()).to.equal(bob.publicKey.toBase58());
-    expect(realmAccount.masters[1].role).to.deep.equal({ admin: {} });
+    expect(realmAccount.pendingOwner).to.be.null;
+    expect(realmAccount.owner.toBase58()).to.equal(alice.publicKey.toBase58());
+    expect(realmAccount.mastersCount).to.equal(2);
+  });
+
+  it("Alice proposes Bob as the owner", async () => await steps.proposeRealmOwner(alice, program, realmId, bob.publicKey, events));
+
+  it("Alice cancels the proposal", async () => {
+    let tx = await program.methods
+      .cancelRealmOwnershipProposal(realmId)
+      .accounts({ master: alice.publicKey })
+      .signers([alice])
+      .rpc();
+    await helper.confirmTransaction(tx);
+
+    const realmPDA = helper.getRealmPDA(realmId, program);
+    const realmAccount = await program.account.realm.fetch(realmPDA);
+    expect(realmAccount.pendingOwner).to.be.null;
+
+    let event = events[events.length - 1];
+    expect(event.eventType.realmOwnershipProposalCancelled.pendingOwner.toBase58()).to.equal(bob.publicKey.toBase58());
   });
 
-  it("Alice transfers ownership to Bob", async () => await steps.transferRealmOwnership(alice, program, realmId, bob.publicKey, events));
+  it("Bob tries to accept the cancelled proposal, and it fails", async () => {
+    try {
+      await program.methods
+        .acceptRealmOwnership(realmId)
+        .accounts({ newOwner: bob.publicKey })
+        .signers([bob])
+        .rpc();
+      expect.fail("Bob should not be able to accept a cancelled proposal");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("NoPendingRealmOwner");
+    }
+  });
+
+  it("Alice proposes Bob as the owner again", async () => await steps.proposeRealmOwner(alice, program, realmId, bob.publicKey, events));
+  it("Bob accepts the ownership", async () => await steps.acceptRealmOwnership(bob, program, realmId, alice.publicKey, events));
   it("Bob removes Alice as a realm master", async () => await steps.removeRealmMaster(bob, program, realmId, alice.publicKey, events));
 
   it("Alice tries to update the realm, and it fails", async () => {
@@ -102,7 +244,7 @@ describe("Realm ownership flows", () => {
         .rpc();
       expect.fail("Alice should not be authorized to update the realm");
     } catch (err) {
-      expect(err.error.errorCode.code).to.equal("UnauthorizedRealmMaster");
+      expect(err.error.errorCode.code).to.equal("AccountNotInitialized");
     }
   });
 
@@ -0,0 +1,322 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { expect } from "chai";
+import { createHash } from "crypto";
+
+import { RealmVoyagers } from "../target/types/realm_voyagers";
+
+import * as helper from "./utils/helpers";
+import * as steps from "./utils/common-steps";
+
+describe("Realm spawn points", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  // Program & keypairs
+  const program = anchor.workspace.RealmVoyagers as Program<RealmVoyagers>;
+  const realmMaster = anchor.web3.Keypair.generate();
+  const wanderer = anchor.web3.Keypair.generate();
+  const rookie = anchor.web3.Keypair.generate();
+  const knight = anchor.web3.Keypair.generate();
+
+  // Realm data
+  const realmId = "spawning_realm";
+  const realmDescription = { name: "Spawning Realm", details: "A realm with many entrances", logo: "https://example.com/logo123" };
+  const makeDimension = (id: string, name: string) => ({
+    id: id,
+    name: name,
+    areas: [{
+      "id": 1,
+      "name": name,
+      "area": { "topLeft": { "x": 0, "y": 0 }, "bottomRight": { "x": 100, "y": 100 } },
+      "tileset": "https://example.com/tileset.png",
+      "tilemap": "https://example.com/tilemap.json"
+    }]
+  });
+  const town = makeDimension("town", "Town");
+  const arena = makeDimension("arena", "Arena");
+  const makeSpawnPoint = (name: string, position: any, weight: number, minLevel: number, heroTag: any) => ({
+    name: name,
+    position: position,
+    weight: weight,
+    minLevel: new anchor.BN(minLevel),
+    heroTag: heroTag,
+  });
+  const spawnPoints = {
+    gate: { dimension: town, settings: makeSpawnPoint("Town Gate", { x: 0, y: 50 }, 1, 0, null) },
+    square: { dimension: town, settings: makeSpawnPoint("Town Square", { x: 50, y: 50 }, 3, 0, null) },
+    arena: { dimension: arena, settings: makeSpawnPoint("Arena Gates", { x: 10, y: 10 }, 0, 0, { knight: {} }) },
+    veterans: { dimension: arena, settings: makeSpawnPoint("Veterans Lodge", { x: 90, y: 90 }, 0, 5, null) },
+  };
+
+  // Hero data
+  const heroDescription = { name: "Squire", graphics: "https://example.com/squire", lore: "Trained for the arena" };
+  const mageDescription = { name: "Apprentice", graphics: "https://example.com/apprentice", lore: "Fresh out of school" };
+
+  // Listen events
+  let listeners = [];
+  let events = [];
+
+  before(async () => {
+    listeners.push(program.addEventListener("realmEvent", (event) => {
+      events.push(event);
+    }));
+    listeners.push(program.addEventListener("journeyEvent", (event) => {
+      events.push(event);
+    }));
+  });
+
+  after(async () => {
+    for (const listener of listeners) {
+      await program.removeEventListener(listener);
+    }
+  });
+
+  function getSpawnPointPDA(spawnPointId: string) {
+    const [pda, _] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("spawn_point"), Buffer.from(realmId), Buffer.from(spawnPointId)],
+      program.programId
+    );
+    return pda;
+  }
+
+  function spawnPointAccounts(spawnPointIds: string[]) {
+    return spawnPointIds.map((spawnPointId) => ({ pubkey: getSpawnPointPDA(spawnPointId), isSigner: false, isWritable: false }));
+  }
+
+  async function mintPlayerHero(player: anchor.web3.Keypair, heroId: string) {
+    const tx = await program.methods
+      .mintPlayerHero(heroId, null)
+      .accounts({ hero: helper.getHeroPDA(realmMaster.publicKey, heroId, program), player: player.publicKey })
+      .signers([player])
+      .rpc();
+    await helper.confirmTransaction(tx);
+  }
+
+  async function startJourney(player: anchor.web3.Keypair, spawnPointId: string | null, heroId: string | null, remainingAccounts = []) {
+    const tx = await program.methods
+      .startJourney(realmId, [], spawnPointId)
+      .accounts({
+        player: player.publicKey,
+        spawnPoint: spawnPointId ? getSpawnPointPDA(spawnPointId) : null,
+        playerHero: heroId ? helper.getPlayerHeroPDA(player.publicKey, heroId, program) : null,
+        hero: heroId ? helper.getHeroPDA(realmMaster.publicKey, heroId, program) : null,
+      })
+      .remainingAccounts(remainingAccounts)
+      .signers([player])
+      .rpc();
+    await helper.confirmTransaction(tx);
+  }
+
+  // Mirrors the weighted pick of the program
+  function expectedSpawnPoint(player: anchor.web3.PublicKey, spawnPointIds: string[]) {
+    const sorted = spawnPointIds
+      .filter((spawnPointId) => spawnPoints[spawnPointId].settings.weight > 0)
+      .sort((a, b) => Buffer.compare(getSpawnPointPDA(a).toBuffer(), getSpawnPointPDA(b).toBuffer()));
+    const totalWeight = sorted.reduce((total, spawnPointId) => total + spawnPoints[spawnPointId].settings.weight, 0);
+    const seed = createHash("sha256").update(helper.getRealmPDA(realmId, program).toBuffer()).update(player.toBuffer()).digest();
+    let roll = Number(seed.readBigUInt64LE(0) % BigInt(totalWeight));
+    return sorted.find((spawnPointId) => {
+      if (roll < spawnPoints[spawnPointId].settings.weight) {
+        return true;
+      }
+      roll -= spawnPoints[spawnPointId].settings.weight;
+      return false;
+    });
+  }
+
+  it("Airdrop to realm master", async () => await helper.airdrop(realmMaster.publicKey, 1 * anchor.web3.LAMPORTS_PER_SOL));
+  it("Airdrop to players", async () => {
+    for (const player of [wanderer, rookie, knight]) {
+      await helper.airdrop(player.publicKey, 1 * anchor.web3.LAMPORTS_PER_SOL);
+    }
+  });
+
+  it("Create the realm", async () => await steps.createRealm(realmMaster, program, realmId, realmDescription, events));
+  it("Add the town", async () => await steps.addRealmDimension(realmMaster, program, realmId, town, events));
+  it("Add the arena", async () => await steps.addRealmDimension(realmMaster, program, realmId, arena, events));
+  it("Create the hero classes", async () => {
+    await steps.createHero(realmMaster, program, "squire", heroDescription, [{ knight: {} }], events);
+    await steps.createHero(realmMaster, program, "apprentice", mageDescription, [{ mage: {} }], events);
+  });
+  it("Mint the player heroes", async () => {
+    await mintPlayerHero(knight, "squire");
+    await mintPlayerHero(rookie, "apprentice");
+  });
+
+  it("Try to add a spawn point outside of the dimension areas", async () => {
+    try {
+      await program.methods
+        .addRealmSpawnPoint(realmId, "nowhere", town.id, makeSpawnPoint("Nowhere", { x: 500, y: 500 }, 1, 0, null))
+        .accounts({ master: realmMaster.publicKey })
+        .signers([realmMaster])
+        .rpc();
+      expect.fail("Spawn point should be inside the dimension areas");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("PositionOutsideDimension");
+    }
+  });
+
+  it("Wanderer tries to add a spawn point, and it fails", async () => {
+    try {
+      await program.methods
+        .addRealmSpawnPoint(realmId, "shortcut", town.id, makeSpawnPoint("Shortcut", { x: 1, y: 1 }, 1, 0, null))
+        .accounts({ master: wanderer.publicKey })
+        .signers([wanderer])
+        .rpc();
+      expect.fail("Only realm masters should manage spawn points");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("AccountNotInitialized");
+    }
+  });
+
+  it("Add the spawn points", async () => {
+    for (const [spawnPointId, spawnPoint] of Object.entries(spawnPoints)) {
+      const tx = await program.methods
+        .addRealmSpawnPoint(realmId, spawnPointId, spawnPoint.dimension.id, spawnPoint.settings)
+        .accounts({ master: realmMaster.publicKey })
+        .signers([realmMaster])
+        .rpc();
+      await helper.confirmTransaction(tx);
+
+      const spawnPointAccount = await program.account.realmSpawnPoint.fetch(getSpawnPointPDA(spawnPointId));
+      expect(spawnPointAccount.dimension.toBase58()).to.equal(helper.getDimensionPDA(realmId, spawnPoint.dimension.id, program).toBase58());
+      expect(spawnPointAccount.settings.name).to.equal(spawnPoint.settings.name);
+
+      let event = events[events.length - 1];
+      expect(event.eventType.spawnPointAdded.spawnPoint.toBase58()).to.equal(getSpawnPointPDA(spawnPointId).toBase58());
+    }
+
+    const realmAccount = await program.account.realm.fetch(helper.getRealmPDA(realmId, program));
+    expect(realmAccount.spawnPointsCount).to.equal(4);
+  });
+
+  it("Wanderer tries to start without all the spawn points, and it fails", async () => {
+    try {
+      await startJourney(wanderer, null, null, spawnPointAccounts(["gate", "square"]));
+      expect.fail("Pick should consider every spawn point");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("SpawnPointNotProvided");
+    }
+  });
+
+  it("Wanderer starts at a spawn point picked for them", async () => {
+    const spawnPointIds = Object.keys(spawnPoints);
+    await startJourney(wanderer, null, null, spawnPointAccounts(spawnPointIds.reverse()));
+
+    const expected = expectedSpawnPoint(wanderer.publicKey, spawnPointIds);
+    const journeyAccount = await program.account.journey.fetch(helper.getJourneyPDA(realmId, wanderer.publicKey, program));
+    expect(journeyAccount.dimension.toBase58()).to.equal(helper.getDimensionPDA(realmId, town.id, program).toBase58());
+    expect(journeyAccount.position).to.deep.equal(spawnPoints[expected].settings.position);
+
+    let event = events[events.length - 1];
+    expect(event.eventType.journeyStarted.spawnPoint.toBase58()).to.equal(getSpawnPointPDA(expected).toBase58());
+  });
+
+  it("Rookie tries to start in the arena without a knight, and it fails", async () => {
+    try {
+      await startJourney(rookie, "arena", "apprentice");
+      expect.fail("Arena should require a knight");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("SpawnRequirementsNotMet");
+    }
+  });
+
+  it("Rookie tries to start in the veterans lodge, and it fails", async () => {
+    try {
+      await startJourney(rookie, "veterans", "apprentice");
+      expect.fail("Veterans lodge should require a higher level");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("SpawnRequirementsNotMet");
+    }
+  });
+
+  it("Rookie chooses to start at the town gate", async () => {
+    await startJourney(rookie, "gate", null);
+
+    const journeyAccount = await program.account.journey.fetch(helper.getJourneyPDA(realmId, rookie.publicKey, program));
+    expect(journeyAccount.position).to.deep.equal(spawnPoints.gate.settings.position);
+  });
+
+  it("Knight starts in the arena with their hero", async () => {
+    await startJourney(knight, "arena", "squire");
+
+    const journeyAccount = await program.account.journey.fetch(helper.getJourneyPDA(realmId, knight.publicKey, program));
+    expect(journeyAccount.dimension.toBase58()).to.equal(helper.getDimensionPDA(realmId, arena.id, program).toBase58());
+    expect(journeyAccount.position).to.deep.equal(spawnPoints.arena.settings.position);
+  });
+
+  it("Move the town gate into the arena", async () => {
+    const settings = makeSpawnPoint("Arena Entrance", { x: 50, y: 0 }, 1, 0, null);
+
+    const tx = await program.methods
+      .updateRealmSpawnPoint(realmId, "gate", arena.id, settings)
+      .accounts({ master: realmMaster.publicKey })
+      .signers([realmMaster])
+      .rpc();
+    await helper.confirmTransaction(tx);
+
+    const spawnPointAccount = await program.account.realmSpawnPoint.fetch(getSpawnPointPDA("gate"));
+    expect(spawnPointAccount.dimension.toBase58()).to.equal(helper.getDimensionPDA(realmId, arena.id, program).toBase58());
+    expect(spawnPointAccount.settings.name).to.equal(settings.name);
+
+    let event = events[events.length - 1];
+    expect(event.eventType.spawnPointUpdated.spawnPoint.toBase58()).to.equal(getSpawnPointPDA("gate").toBase58());
+  });
+
+  it("Remove the veterans lodge", async () => {
+    const tx = await program.methods
+      .removeRealmSpawnPoint(realmId, "veterans")
+      .accounts({ master: realmMaster.publicKey })
+      .signers([realmMaster])
+      .rpc();
+    await helper.confirmTransaction(tx);
+
+    expect(await anchor.getProvider().connection.getAccountInfo(getSpawnPointPDA("veterans"))).to.be.null;
+    const realmAccount = await program.account.realm.fetch(helper.getRealmPDA(realmId, program));
+    expect(realmAccount.spawnPointsCount).to.equal(3);
+
+    let event = events[events.length - 1];
+    expect(event.eventType.spawnPointRemoved.spawnPoint.toBase58()).to.equal(getSpawnPointPDA("veterans").toBase58());
+  });
+
+  const remainingSpawnPointIds = () => Object.keys(spawnPoints).filter((spawnPointId) => spawnPointId !== "veterans");
+  const dimensionAccounts = () => [town, arena].map((dimension) => ({
+    pubkey: helper.getDimensionPDA(realmId, dimension.id, program),
+    isSigner: false,
+    isWritable: true,
+  }));
+
+  it("Try to delete the realm without its spawn points", async () => {
+    try {
+      await program.methods
+        .deleteRealm(realmId)
+        .accounts({ master: realmMaster.publicKey, proposal: null, proposer: null, queuedAction: null })
+        .remainingAccounts(dimensionAccounts())
+        .signers([realmMaster])
+        .rpc();
+      expect.fail("Realm should not be deleted while leaving its spawn points behind");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("SpawnPointNotProvided");
+    }
+  });
+
+  it("Delete the realm along with its spawn points", async () => {
+    const spawnPointAccounts = remainingSpawnPointIds().map((spawnPointId) => ({
+      pubkey: getSpawnPointPDA(spawnPointId),
+      isSigner: false,
+      isWritable: true,
+    }));
+    const tx = await program.methods
+      .deleteRealm(realmId)
+      .accounts({ master: realmMaster.publicKey, proposal: null, proposer: null, queuedAction: null })
+      .remainingAccounts([...dimensionAccounts(), ...spawnPointAccounts])
+      .signers([realmMaster])
+      .rpc();
+    await helper.confirmTransaction(tx);
+
+    for (const spawnPointId of remainingSpawnPointIds()) {
+      expect(await anchor.getProvider().connection.getAccountInfo(getSpawnPointPDA(spawnPointId))).to.be.null;
+    }
+  });
+});
\ No newline at end of file
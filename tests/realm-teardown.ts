@@ -0,0 +1,231 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { expect } from "chai";
+
+import { RealmVoyagers } from "../target/types/realm_voyagers";
+
+import * as helper from "./utils/helpers";
+import * as steps from "./utils/common-steps";
+
+describe("Realm teardown", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  // Program & keypairs
+  const program = anchor.workspace.RealmVoyagers as Program<RealmVoyagers>;
+  const alice = anchor.web3.Keypair.generate();
+  const bob = anchor.web3.Keypair.generate();
+  const player = anchor.web3.Keypair.generate();
+  const voyager = anchor.web3.Keypair.generate();
+
+  // Realm data
+  const realmId = "vast_realm";
+  const realmDescription = { name: "Vast Realm", details: "A realm too big to delete at once", logo: "https://example.com/logo123" };
+  const area = {
+    "id": 1,
+    "name": "Meadow",
+    "area": { "topLeft": { "x": 0, "y": 0 }, "bottomRight": { "x": 100, "y": 100 } },
+    "tileset": "https://example.com/tileset.png",
+    "tilemap": "https://example.com/tilemap.json"
+  };
+  const aliceDimensions = [1, 2, 3].map((i) => ({ id: `alice_meadow_${i}`, name: `Alice Meadow ${i}`, areas: [area] }));
+  const bobDimension = { id: "bob_forest", name: "Bob Forest", areas: [area] };
+
+  // Listen events
+  let listeners = [];
+  let events = [];
+
+  before(async () => {
+    listeners.push(program.addEventListener("realmEvent", (event) => {
+      events.push(event);
+    }));
+    listeners.push(program.addEventListener("realmDimensionEvent", (event) => {
+      events.push(event);
+    }));
+  });
+
+  after(async () => {
+    for (const listener of listeners) {
+      await program.removeEventListener(listener);
+    }
+  });
+
+  function dimensionAccount(dimensionId: string) {
+    return { pubkey: helper.getDimensionPDA(realmId, dimensionId, program), isSigner: false, isWritable: true };
+  }
+
+  async function continueTeardown(remainingAccounts) {
+    const tx = await program.methods
+      .continueRealmTeardown(realmId)
+      .accounts({ master: alice.publicKey })
+      .remainingAccounts(remainingAccounts)
+      .signers([alice])
+      .rpc();
+    await helper.confirmTransaction(tx);
+  }
+
+  it("Airdrop to Alice", async () => await helper.airdrop(alice.publicKey, 1 * anchor.web3.LAMPORTS_PER_SOL));
+  it("Airdrop to Bob", async () => await helper.airdrop(bob.publicKey, 1 * anchor.web3.LAMPORTS_PER_SOL));
+  it("Airdrop to player", async () => await helper.airdrop(player.publicKey, 1 * anchor.web3.LAMPORTS_PER_SOL));
+  it("Airdrop to voyager", async () => await helper.airdrop(voyager.publicKey, 1 * anchor.web3.LAMPORTS_PER_SOL));
+
+  it("Alice creates a realm", async () => await steps.createRealm(alice, program, realmId, realmDescription, events));
+  it("Alice adds Bob as a realm admin", async () => await steps.addRealmMaster(alice, program, realmId, bob.publicKey, { admin: {} }, events));
+
+  it("Alice adds her dimensions", async () => {
+    for (const dimension of aliceDimensions) {
+      await steps.addRealmDimension(alice, program, realmId, dimension, events);
+    }
+  });
+  it("Bob adds his dimension", async () => await steps.addRealmDimension(bob, program, realmId, bobDimension, events));
+
+  it("Voyager starts a journey", async () => {
+    const tx = await program.methods
+      .startJourney(realmId, [], null)
+      .accounts({ player: voyager.publicKey, spawnPoint: null, playerHero: null, hero: null })
+      .signers([voyager])
+      .rpc();
+    await helper.confirmTransaction(tx);
+  });
+
+  it("Alice tries to continue a teardown that was never started, and it fails", async () => {
+    try {
+      await continueTeardown([dimensionAccount(aliceDimensions[0].id)]);
+      expect.fail("Active realm should not be torn down");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("RealmNotClosing");
+    }
+  });
+
+  it("Alice begins the realm teardown", async () => {
+    const tx = await program.methods
+      .beginRealmTeardown(realmId)
+      .accounts({ master: alice.publicKey, proposal: null, proposer: null, queuedAction: null })
+      .signers([alice])
+      .rpc();
+    await helper.confirmTransaction(tx);
+
+    const realmAccount = await program.account.realm.fetch(helper.getRealmPDA(realmId, program));
+    expect(realmAccount.status).to.deep.equal({ closing: {} });
+
+    let event = events[events.length - 1];
+    expect(event.eventType.realmTeardownStarted.dimensionsLeft).to.equal(4);
+    expect(event.eventType.realmTeardownStarted.mastersLeft).to.equal(1);
+    expect(event.eventType.realmTeardownStarted.spawnPointsLeft).to.equal(0);
+  });
+
+  it("Alice tries to begin the teardown again, and it fails", async () => {
+    try {
+      await program.methods
+        .beginRealmTeardown(realmId)
+        .accounts({ master: alice.publicKey, proposal: null, proposer: null, queuedAction: null })
+        .signers([alice])
+        .rpc();
+      expect.fail("Teardown should only begin once");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("RealmClosing");
+    }
+  });
+
+  it("Bob tries to add a dimension to the closing realm, and it fails", async () => {
+    try {
+      await steps.addRealmDimension(bob, program, realmId, { id: "bob_lake", name: "Bob Lake", areas: [area] }, events);
+      expect.fail("Closing realm should not be edited");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("RealmClosing");
+    }
+  });
+
+  it("Player tries to start a journey in the closing realm, and it fails", async () => {
+    try {
+      await program.methods
+        .startJourney(realmId, [], null)
+        .accounts({ player: player.publicKey, spawnPoint: null, playerHero: null, hero: null })
+        .signers([player])
+        .rpc();
+      expect.fail("Closing realm should not accept new journeys");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("RealmClosing");
+    }
+  });
+
+  it("Voyager tries to move in the closing realm, and it fails", async () => {
+    try {
+      await program.methods
+        .moveHero(realmId, { x: 1, y: 1 })
+        .accounts({ player: voyager.publicKey, authority: voyager.publicKey, sessionKey: null, grid: null })
+        .signers([voyager])
+        .rpc();
+      expect.fail("Closing realm should not be voyaged");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("RealmClosing");
+    }
+  });
+
+  it("Alice tries to finish the teardown early, and it fails", async () => {
+    try {
+      await program.methods
+        .finishRealmTeardown(realmId)
+        .accounts({ master: alice.publicKey })
+        .signers([alice])
+        .rpc();
+      expect.fail("Realm should not be closed while dimensions remain");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("RealmTeardownIncomplete");
+    }
+  });
+
+  it("Alice closes a first batch of dimensions", async () => {
+    await continueTeardown(aliceDimensions.slice(0, 2).map((dimension) => dimensionAccount(dimension.id)));
+
+    let event = events[events.length - 1];
+    expect(event.eventType.realmTeardownProgress.dimensionsClosed).to.equal(2);
+    expect(event.eventType.realmTeardownProgress.mastersClosed).to.equal(0);
+    expect(event.eventType.realmTeardownProgress.dimensionsLeft).to.equal(2);
+    expect(event.eventType.realmTeardownProgress.mastersLeft).to.equal(1);
+
+    const realmAccount = await program.account.realm.fetch(helper.getRealmPDA(realmId, program));
+    expect(realmAccount.dimensions.length).to.equal(2);
+  });
+
+  it("Alice closes the remaining dimensions and Bob's membership", async () => {
+    const connection = anchor.getProvider().connection;
+    const bobDimensionRent = await connection.getBalance(helper.getDimensionPDA(realmId, bobDimension.id, program));
+    const bobBalanceBefore = await connection.getBalance(bob.publicKey);
+
+    await continueTeardown([
+      dimensionAccount(aliceDimensions[2].id),
+      dimensionAccount(bobDimension.id),
+      { pubkey: bob.publicKey, isSigner: false, isWritable: true },
+      { pubkey: helper.getMasterPDA(realmId, bob.publicKey, program), isSigner: false, isWritable: true },
+    ]);
+
+    expect(await connection.getBalance(bob.publicKey)).to.equal(bobBalanceBefore + bobDimensionRent);
+    expect(await connection.getAccountInfo(helper.getMasterPDA(realmId, bob.publicKey, program))).to.be.null;
+
+    let event = events[events.length - 1];
+    expect(event.eventType.realmTeardownProgress.dimensionsClosed).to.equal(2);
+    expect(event.eventType.realmTeardownProgress.mastersClosed).to.equal(1);
+    expect(event.eventType.realmTeardownProgress.dimensionsLeft).to.equal(0);
+    expect(event.eventType.realmTeardownProgress.mastersLeft).to.equal(0);
+  });
+
+  it("Alice finishes the teardown", async () => {
+    const tx = await program.methods
+      .finishRealmTeardown(realmId)
+      .accounts({ master: alice.publicKey })
+      .signers([alice])
+      .rpc();
+    await helper.confirmTransaction(tx);
+
+    const connection = anchor.getProvider().connection;
+    for (const dimension of [...aliceDimensions, bobDimension]) {
+      expect(await connection.getAccountInfo(helper.getDimensionPDA(realmId, dimension.id, program))).to.be.null;
+    }
+    expect(await connection.getAccountInfo(helper.getMasterPDA(realmId, alice.publicKey, program))).to.be.null;
+    expect(await connection.getAccountInfo(helper.getRealmPDA(realmId, program))).to.be.null;
+
+    let event = events[events.length - 1];
+    expect(event.eventType.realmDeleted).not.to.be.undefined;
+  });
+});
\ No newline at end of file
@@ -0,0 +1,198 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { expect } from "chai";
+
+import { RealmVoyagers } from "../target/types/realm_voyagers";
+
+import * as helper from "./utils/helpers";
+import * as steps from "./utils/common-steps";
+
+describe("Realm timelock", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  // Program & keypairs
+  const program = anchor.workspace.RealmVoyagers as Program<RealmVoyagers>;
+  const alice = anchor.web3.Keypair.generate();
+
+  // Realm data
+  const realmId = "timelocked_realm";
+  const realmDescription = { name: "Patient Realm", details: "A realm that warns its voyagers", logo: "https://example.com/logo123" };
+  const timelock = 2; // seconds
+  const cancelledActionId = "delete_cancelled";
+  const actionId = "delete";
+  const staleActionId = "disable_timelock";
+
+  // Listen events
+  let listener = null;
+  let events = [];
+
+  before(async () => {
+    listener = program.addEventListener("realmEvent", (event) => {
+      events.push(event);
+    });
+  });
+
+  after(async () => {
+    await program.removeEventListener(listener);
+  });
+
+  async function queueDeletion(actionId: string) {
+    let tx = await program.methods
+      .queueRealmAction(realmId, actionId, { deleteRealm: {} })
+      .accounts({ master: alice.publicKey })
+      .signers([alice])
+      .rpc();
+    await helper.confirmTransaction(tx);
+
+    const queuedActionPDA = helper.getQueuedActionPDA(realmId, actionId, program);
+    const queuedAction = await program.account.realmQueuedAction.fetch(queuedActionPDA);
+    expect(queuedAction.action).to.deep.equal({ deleteRealm: {} });
+    expect(queuedAction.executeAfter.toNumber()).to.equal(queuedAction.queuedAt.toNumber() + timelock);
+
+    let event = events[events.length - 1];
+    expect(event.eventType.realmActionQueued.queuedAction.toBase58()).to.equal(queuedActionPDA.toBase58());
+    expect(event.eventType.realmActionQueued.executeAfter.toNumber()).to.equal(queuedAction.executeAfter.toNumber());
+  }
+
+  it("Airdrop to Alice", async () => await helper.airdrop(alice.publicKey, 1 * anchor.web3.LAMPORTS_PER_SOL));
+  it("Alice creates a realm", async () => await steps.createRealm(alice, program, realmId, realmDescription, events));
+
+  it("Alice enables the timelock", async () => {
+    let tx = await program.methods
+      .setRealmTimelock(realmId, new anchor.BN(timelock))
+      .accounts({ master: alice.publicKey, queuedAction: null })
+      .signers([alice])
+      .rpc();
+    await helper.confirmTransaction(tx);
+
+    const realmAccount = await program.account.realm.fetch(helper.getRealmPDA(realmId, program));
+    expect(realmAccount.timelock.toNumber()).to.equal(timelock);
+
+    let event = events[events.length - 1];
+    expect(event.eventType.realmTimelockChanged.timelock.toNumber()).to.equal(timelock);
+  });
+
+  it("Alice tries to lower the timelock without queueing it, and it fails", async () => {
+    try {
+      await program.methods
+        .setRealmTimelock(realmId, new anchor.BN(0))
+        .accounts({ master: alice.publicKey, queuedAction: null })
+        .signers([alice])
+        .rpc();
+      expect.fail("Lowering the timelock should be queued");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("TimelockRequired");
+    }
+  });
+
+  it("Alice tries to delete the realm directly, and it fails", async () => {
+    try {
+      await program.methods
+        .deleteRealm(realmId)
+        .accounts({ master: alice.publicKey, proposal: null, proposer: null, queuedAction: null })
+        .signers([alice])
+        .rpc();
+      expect.fail("Realm deletion should be queued");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("TimelockRequired");
+    }
+  });
+
+  it("Alice queues the realm deletion", async () => await queueDeletion(cancelledActionId));
+
+  it("Alice tries to delete the realm before the timelock elapses, and it fails", async () => {
+    try {
+      await program.methods
+        .deleteRealm(realmId)
+        .accounts({
+          master: alice.publicKey,
+          proposal: null,
+          proposer: null,
+          queuedAction: helper.getQueuedActionPDA(realmId, cancelledActionId, program),
+        })
+        .signers([alice])
+        .rpc();
+      expect.fail("Realm deletion should wait for the timelock");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("TimelockNotElapsed");
+    }
+  });
+
+  it("Alice cancels the queued deletion", async () => {
+    const queuedActionPDA = helper.getQueuedActionPDA(realmId, cancelledActionId, program);
+
+    let tx = await program.methods
+      .cancelRealmAction(realmId, cancelledActionId)
+      .accounts({ master: alice.publicKey, queuedBy: alice.publicKey })
+      .signers([alice])
+      .rpc();
+    await helper.confirmTransaction(tx);
+
+    expect(await anchor.getProvider().connection.getAccountInfo(queuedActionPDA)).to.be.null;
+
+    let event = events[events.length - 1];
+    expect(event.eventType.realmActionCancelled.queuedAction.toBase58()).to.equal(queuedActionPDA.toBase58());
+  });
+
+  it("Alice queues the realm deletion again", async () => await queueDeletion(actionId));
+
+  it("Alice tries to raise the timelock with the queued deletion, and it fails", async () => {
+    try {
+      await program.methods
+        .setRealmTimelock(realmId, new anchor.BN(timelock + 1))
+        .accounts({ master: alice.publicKey, queuedAction: helper.getQueuedActionPDA(realmId, actionId, program) })
+        .signers([alice])
+        .rpc();
+      expect.fail("Queued action should match the executed action");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("QueuedActionMismatch");
+    }
+  });
+
+  it("Alice queues disabling the timelock", async () => {
+    let tx = await program.methods
+      .queueRealmAction(realmId, staleActionId, { setTimelock: { timelock: new anchor.BN(0) } })
+      .accounts({ master: alice.publicKey })
+      .signers([alice])
+      .rpc();
+    await helper.confirmTransaction(tx);
+  });
+
+  it("Alice deletes the realm once the timelock elapsed", async () => {
+    await new Promise((resolve) => setTimeout(resolve, (timelock + 2) * 1000));
+
+    const realmPDA = helper.getRealmPDA(realmId, program);
+    const queuedActionPDA = helper.getQueuedActionPDA(realmId, actionId, program);
+
+    let tx = await program.methods
+      .deleteRealm(realmId)
+      .accounts({ master: alice.publicKey, proposal: null, proposer: null, queuedAction: queuedActionPDA })
+      .signers([alice])
+      .rpc();
+    await helper.confirmTransaction(tx);
+
+    expect(await anchor.getProvider().connection.getAccountInfo(realmPDA)).to.be.null;
+    expect(await anchor.getProvider().connection.getAccountInfo(queuedActionPDA)).to.be.null;
+
+    let event = events[events.length - 2];
+    expect(event.eventType.realmActionExecuted.queuedAction.toBase58()).to.equal(queuedActionPDA.toBase58());
+    event = events[events.length - 1];
+    expect(event.eventType.realmDeleted).not.to.be.undefined;
+  });
+
+  it("Alice recreates the realm", async () => await steps.createRealm(alice, program, realmId, realmDescription, events));
+
+  it("Alice tries to use the action queued for the deleted realm, and it fails", async () => {
+    try {
+      await program.methods
+        .setRealmTimelock(realmId, new anchor.BN(0))
+        .accounts({ master: alice.publicKey, queuedAction: helper.getQueuedActionPDA(realmId, staleActionId, program) })
+        .signers([alice])
+        .rpc();
+      expect.fail("Actions queued for a deleted realm should be stale");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("StaleQueuedAction");
+    }
+  });
+});
\ No newline at end of file
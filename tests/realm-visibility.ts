@@ -0,0 +1,171 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { expect } from "chai";
+
+import { RealmVoyagers } from "../target/types/realm_voyagers";
+
+import * as helper from "./utils/helpers";
+import * as steps from "./utils/common-steps";
+
+describe("Realm visibility", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  // Program & keypairs
+  const program = anchor.workspace.RealmVoyagers as Program<RealmVoyagers>;
+  const realmMaster = anchor.web3.Keypair.generate();
+  const guests = [1, 2, 3, 4, 5].map(() => anchor.web3.Keypair.generate());
+  const stranger = anchor.web3.Keypair.generate();
+  const invited = guests.map((guest) => guest.publicKey);
+
+  // Realm data
+  const realmId = "secret_realm";
+  const realmDescription = { name: "Secret Realm", details: "A realm for invited guests", logo: "https://example.com/logo123" };
+  const dimension = {
+    id: "hidden_garden",
+    name: "Hidden Garden",
+    areas: [{
+      "id": 1,
+      "name": "Garden",
+      "area": { "topLeft": { "x": 0, "y": 0 }, "bottomRight": { "x": 100, "y": 100 } },
+      "tileset": "https://example.com/tileset.png",
+      "tilemap": "https://example.com/tilemap.json"
+    }]
+  };
+  const startingPosition = { x: 10, y: 10 };
+
+  // Listen events
+  let listeners = [];
+  let events = [];
+
+  before(async () => {
+    listeners.push(program.addEventListener("realmEvent", (event) => {
+      events.push(event);
+    }));
+  });
+
+  after(async () => {
+    for (const listener of listeners) {
+      await program.removeEventListener(listener);
+    }
+  });
+
+  async function setVisibility(visibility: any) {
+    const tx = await program.methods
+      .setRealmVisibility(realmId, visibility)
+      .accounts({ master: realmMaster.publicKey })
+      .signers([realmMaster])
+      .rpc();
+    await helper.confirmTransaction(tx);
+
+    const realmAccount = await program.account.realm.fetch(helper.getRealmPDA(realmId, program));
+    expect(realmAccount.visibility).to.deep.equal(visibility);
+
+    let event = events[events.length - 1];
+    expect(event.eventType.realmVisibilityChanged.visibility).to.deep.equal(visibility);
+  }
+
+  async function startJourney(player: anchor.web3.Keypair, proof: number[][]) {
+    const tx = await program.methods
+      .startJourney(realmId, proof, null)
+      .accounts({ player: player.publicKey, spawnPoint: null, playerHero: null, hero: null })
+      .signers([player])
+      .rpc();
+    await helper.confirmTransaction(tx);
+  }
+
+  it("Airdrop to realm master", async () => await helper.airdrop(realmMaster.publicKey, 1 * anchor.web3.LAMPORTS_PER_SOL));
+  it("Airdrop to guests", async () => {
+    for (const guest of guests) {
+      await helper.airdrop(guest.publicKey, 1 * anchor.web3.LAMPORTS_PER_SOL);
+    }
+  });
+  it("Airdrop to stranger", async () => await helper.airdrop(stranger.publicKey, 1 * anchor.web3.LAMPORTS_PER_SOL));
+
+  it("Create the realm", async () => {
+    await steps.createRealm(realmMaster, program, realmId, realmDescription, events);
+
+    const realmAccount = await program.account.realm.fetch(helper.getRealmPDA(realmId, program));
+    expect(realmAccount.visibility).to.deep.equal({ public: {} });
+  });
+  it("Add the dimension", async () => await steps.addRealmDimension(realmMaster, program, realmId, dimension, events));
+  it("Set the starting point", async () => {
+    const tx = await program.methods
+      .setRealmStartingPoint(realmId, dimension.id, startingPosition)
+      .accounts({ master: realmMaster.publicKey })
+      .signers([realmMaster])
+      .rpc();
+    await helper.confirmTransaction(tx);
+  });
+
+  it("Stranger tries to change the visibility, and it fails", async () => {
+    try {
+      await program.methods
+        .setRealmVisibility(realmId, { unlisted: {} })
+        .accounts({ master: stranger.publicKey })
+        .signers([stranger])
+        .rpc();
+      expect.fail("Only realm masters should change the visibility");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("AccountNotInitialized");
+    }
+  });
+
+  it("Make the realm unlisted", async () => await setVisibility({ unlisted: {} }));
+
+  it("Anyone can still join an unlisted realm", async () => {
+    await startJourney(guests[0], []);
+
+    const tx = await program.methods
+      .endJourney(realmId)
+      .accounts({ player: guests[0].publicKey })
+      .signers([guests[0]])
+      .rpc();
+    await helper.confirmTransaction(tx);
+  });
+
+  it("Make the realm invite-only", async () => {
+    await setVisibility({ inviteOnly: { allowlistRoot: helper.getAllowlistRoot(invited) } });
+  });
+
+  it("Stranger tries to join without a proof, and it fails", async () => {
+    try {
+      await startJourney(stranger, []);
+      expect.fail("Stranger should not join an invite-only realm");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("PlayerNotInvited");
+    }
+  });
+
+  it("Stranger tries to join with a guest's proof, and it fails", async () => {
+    try {
+      await startJourney(stranger, helper.getAllowlistProof(invited, guests[1].publicKey));
+      expect.fail("Proofs should not be transferable");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("PlayerNotInvited");
+    }
+  });
+
+  it("Guest tries to join with a proof that is too long, and it fails", async () => {
+    try {
+      await startJourney(guests[1], new Array(33).fill(new Array(32).fill(0)));
+      expect.fail("Proof length should be capped");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("AllowlistProofTooLong");
+    }
+  });
+
+  it("Every invited guest joins with a proof", async () => {
+    for (const guest of guests) {
+      await startJourney(guest, helper.getAllowlistProof(invited, guest.publicKey));
+
+      const journeyAccount = await program.account.journey.fetch(helper.getJourneyPDA(realmId, guest.publicKey, program));
+      expect(journeyAccount.player.toBase58()).to.equal(guest.publicKey.toBase58());
+    }
+  });
+
+  it("Invite the stranger with a new allowlist", async () => {
+    await setVisibility({ inviteOnly: { allowlistRoot: helper.getAllowlistRoot([...invited, stranger.publicKey]) } });
+    await startJourney(stranger, helper.getAllowlistProof([...invited, stranger.publicKey], stranger.publicKey));
+  });
+});
\ No newline at end of file
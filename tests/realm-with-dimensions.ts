@@ -19,6 +19,7 @@ describe("Test realm with dimensions", () => {
   const realmId = "realm_with_dimensions";
   const realmDescription = { name: "Test Realm", details: "A test realm details", logo: "https://example.com/logo123" };
   const exampleArea = {
+    "id": 1,
     "name": "Example Area",
     "area": {
       "topLeft": {
@@ -69,7 +70,7 @@ describe("Test realm with dimensions", () => {
   it("Try to update unexisting dimension", async () => {
     try {
       await program.methods
-        .updateRealmDimension(realmId, "unexisting_dimension", "New Name", [])
+        .updateRealmDimension(realmId, "unexisting_dimension", "New Name", [], false)
         .accounts({
           master: realmMaster.publicKey,
         })
@@ -86,6 +87,8 @@ describe("Test realm with dimensions", () => {
         .removeRealmDimension(realmId, "unexisting_dimension")
         .accounts({
           master: realmMaster.publicKey,
+          owner: realmMaster.publicKey,
+          queuedAction: null,
         })
         .signers([realmMaster])
         .rpc();
@@ -24,6 +24,7 @@ describe("Test simple journey", () => {
     name: "Dungeon",
     areas: [
       {
+        "id": 1,
         "name": "Test Area 1",
         "area": {
           "topLeft": {
@@ -39,6 +40,7 @@ describe("Test simple journey", () => {
         "tilemap": "https://example.com/tilemap.json"
       },
       {
+        "id": 2,
         "name": "Test Area 2",
         "area": {
           "topLeft": {
@@ -53,9 +55,10 @@ describe("Test simple journey", () => {
         "tileset": "https://example.com/tileset_2.png",
         "tilemap": "https://example.com/tilemap.json"
       },
-    ]
+    ],
+    allowOverlappingAreas: true,
   };
-  const startingPosition = { x: 10, y: -13 };
+  const startingPosition = { x: 10, y: 5 };
 
   // Listen events
   let listeners = [];
@@ -68,6 +71,9 @@ describe("Test simple journey", () => {
     listeners.push(program.addEventListener("realmDimensionEvent", (event) => {
       events.push(event);
     }));
+    listeners.push(program.addEventListener("journeyEvent", (event) => {
+      events.push(event);
+    }));
   });
 
   after(async () => {
@@ -81,6 +87,25 @@ describe("Test simple journey", () => {
   it("Add the dimension", async () => steps.addRealmDimension(realmMaster, program, realmId, dimension, events));
 
 
+  it("New dimension becomes the starting one at its first area", async () => {
+    const realmAccount = await program.account.realm.fetch(helper.getRealmPDA(realmId, program));
+    expect(realmAccount.startingDimension.toBase58()).to.equal(helper.getDimensionPDA(realmId, dimension.id, program).toBase58());
+    expect(realmAccount.startingPosition).to.deep.equal(dimension.areas[0].area.topLeft);
+  });
+
+  it("Try to set the starting point outside of the dimension areas", async () => {
+    try {
+      await program.methods
+        .setRealmStartingPoint(realmId, dimension.id, { x: 10, y: -13 })
+        .accounts({ master: realmMaster.publicKey })
+        .signers([realmMaster])
+        .rpc();
+      expect.fail("Starting point should be inside the dimension areas");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("PositionOutsideDimension");
+    }
+  });
+
   it("Set the starting point", async () => {
     let tx = await program.methods
     .setRealmStartingPoint(realmId, dimension.id, startingPosition)
@@ -90,6 +115,23 @@ describe("Test simple journey", () => {
     .signers([realmMaster])
     .rpc();
     await helper.confirmTransaction(tx);
+
+    let event = events[events.length - 1];
+    expect(event.eventType.realmStartingPointChanged.dimension.toBase58()).to.equal(helper.getDimensionPDA(realmId, dimension.id, program).toBase58());
+    expect(event.eventType.realmStartingPointChanged.position).to.deep.equal(startingPosition);
+  });
+
+  it("Try to remove the area holding the starting point", async () => {
+    try {
+      await program.methods
+        .removeDimensionArea(realmId, dimension.id, dimension.areas[0].id)
+        .accounts({ master: realmMaster.publicKey })
+        .signers([realmMaster])
+        .rpc();
+      expect.fail("Starting point should stay inside the dimension areas");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("StartingPointOutsideDimension");
+    }
   });
 
   // TODO: add ability to pay for players from realm master account
@@ -97,9 +139,12 @@ describe("Test simple journey", () => {
 
   it("Join the realm as a player", async () => {
     const tx = await program.methods
-      .startJourney(realmId)
+      .startJourney(realmId, [], null)
       .accounts({
         player: player.publicKey,
+        spawnPoint: null,
+        playerHero: null,
+        hero: null,
       })
       .signers([player])
       .rpc();
@@ -114,5 +159,114 @@ describe("Test simple journey", () => {
     expect(journeyAccount.dimension.toBase58()).to.equal(dimensionPDA.toBase58());
     expect(journeyAccount.position.x).to.equal(startingPosition.x);
     expect(journeyAccount.position.y).to.equal(startingPosition.y);
+
+    expect(events.length).to.be.above(0);
+    let event = events[events.length - 1];
+    expect(event.eventType.journeyStarted.dimension.toBase58()).to.equal(dimensionPDA.toBase58());
+    expect(event.eventType.journeyStarted.spawnPoint).to.be.null;
+    expect(event.journeyPubkey.toBase58()).to.equal(journeyPDA.toBase58());
+  });
+
+  it("Move the hero inside the dimension", async () => {
+    const journeyPDA = helper.getJourneyPDA(realmId, player.publicKey, program);
+    const newPosition = { x: 15, y: 0 };
+
+    const tx = await program.methods
+      .moveHero(realmId, newPosition)
+      .accounts({
+        player: player.publicKey,
+        authority: player.publicKey,
+        sessionKey: null,
+        grid: null,
+      })
+      .signers([player])
+      .rpc();
+    await helper.confirmTransaction(tx);
+
+    const journeyAccount = await program.account.journey.fetch(journeyPDA);
+    expect(journeyAccount.position.x).to.equal(newPosition.x);
+    expect(journeyAccount.position.y).to.equal(newPosition.y);
+
+    expect(events.length).to.be.above(0);
+    let event = events[events.length - 1];
+    expect(event.eventType.heroMoved.from).to.deep.equal(startingPosition);
+    expect(event.eventType.heroMoved.to).to.deep.equal(newPosition);
+    expect(event.journeyPubkey.toBase58()).to.equal(journeyPDA.toBase58());
+    expect(event.playerPubkey.toBase58()).to.equal(player.publicKey.toBase58());
+  });
+
+  it("Try to move the hero outside of the dimension areas", async () => {
+    try {
+      await program.methods
+        .moveHero(realmId, { x: 15, y: -5 })
+        .accounts({ player: player.publicKey, authority: player.publicKey, sessionKey: null, grid: null })
+        .signers([player])
+        .rpc();
+      expect.fail("Hero should not be able to leave the dimension areas");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("PositionOutsideDimension");
+    }
+  });
+
+  it("Try to move the hero too far", async () => {
+    try {
+      await program.methods
+        .moveHero(realmId, { x: 15, y: 40 })
+        .accounts({ player: player.publicKey, authority: player.publicKey, sessionKey: null, grid: null })
+        .signers([player])
+        .rpc();
+      expect.fail("Hero should not be able to move that far in one step");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("MoveDistanceTooLong");
+    }
+  });
+
+  it("Realm master kicks the player out of the realm", async () => {
+    const tx = await program.methods
+      .kickVoyager(realmId)
+      .accounts({
+        master: realmMaster.publicKey,
+        player: player.publicKey,
+      })
+      .signers([realmMaster])
+      .rpc();
+    await helper.confirmTransaction(tx);
+
+    const journeyPDA = helper.getJourneyPDA(realmId, player.publicKey, program);
+    let journeyInfo = await anchor.getProvider().connection.getAccountInfo(journeyPDA);
+    expect(journeyInfo).to.be.null;
+
+    let event = events[events.length - 1];
+    expect(event.eventType.voyagerKicked.master.toBase58()).to.equal(realmMaster.publicKey.toBase58());
+    expect(event.playerPubkey.toBase58()).to.equal(player.publicKey.toBase58());
+  });
+
+  it("Player restarts the journey and ends it", async () => {
+    let tx = await program.methods
+      .startJourney(realmId, [], null)
+      .accounts({ player: player.publicKey, spawnPoint: null, playerHero: null, hero: null })
+      .signers([player])
+      .rpc();
+    await helper.confirmTransaction(tx);
+
+    const journeyPDA = helper.getJourneyPDA(realmId, player.publicKey, program);
+    const balanceBefore = await anchor.getProvider().connection.getBalance(player.publicKey);
+
+    tx = await program.methods
+      .endJourney(realmId)
+      .accounts({ player: player.publicKey })
+      .signers([player])
+      .rpc();
+    await helper.confirmTransaction(tx);
+
+    let journeyInfo = await anchor.getProvider().connection.getAccountInfo(journeyPDA);
+    expect(journeyInfo).to.be.null;
+
+    const balanceAfter = await anchor.getProvider().connection.getBalance(player.publicKey);
+    expect(balanceAfter).to.be.above(balanceBefore);
+
+    let event = events[events.length - 1];
+    expect(event.eventType.journeyEnded).not.to.be.undefined;
+    expect(event.journeyPubkey.toBase58()).to.equal(journeyPDA.toBase58());
   });
 });
\ No newline at end of file
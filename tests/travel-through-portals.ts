@@ -0,0 +1,316 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { expect } from "chai";
+
+import { RealmVoyagers } from "../target/types/realm_voyagers";
+
+import * as helper from "./utils/helpers";
+import * as steps from "./utils/common-steps";
+
+describe("Travel through portals", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  // Program & keypairs
+  const program = anchor.workspace.RealmVoyagers as Program<RealmVoyagers>;
+  const realmMaster = anchor.web3.Keypair.generate();
+  const player = anchor.web3.Keypair.generate();
+
+  // Realm data
+  const realmId = "portal_realm";
+  const realmDescription = { name: "Portal Realm", details: "A realm with portals", logo: "https://example.com/logo123" };
+  const makeArea = (name: string) => ({
+    "id": 1,
+    "name": name,
+    "area": {
+      "topLeft": {
+        "x": 0,
+        "y": 0
+      },
+      "bottomRight": {
+        "x": 50,
+        "y": 50
+      }
+    },
+    "tileset": "https://example.com/tileset.png",
+    "tilemap": "https://example.com/tilemap.json"
+  });
+  const village = { id: "village", name: "Village", areas: [makeArea("Square")] };
+  const cave = { id: "cave", name: "Cave", areas: [makeArea("Entrance")] };
+  const cellar = { id: "cellar", name: "Cellar", areas: [makeArea("Barrels")] };
+
+  const portalId = "village_to_cave";
+  const portalArea = { topLeft: { x: 20, y: 20 }, bottomRight: { x: 22, y: 22 } };
+  const portalTarget = { x: 5, y: 5 };
+
+  // Listen events
+  let listeners = [];
+  let events = [];
+
+  before(async () => {
+    listeners.push(program.addEventListener("realmDimensionEvent", (event) => {
+      events.push(event);
+    }));
+    listeners.push(program.addEventListener("journeyEvent", (event) => {
+      events.push(event);
+    }));
+  });
+
+  after(async () => {
+    for (const listener of listeners) {
+      await program.removeEventListener(listener);
+    }
+  });
+
+  it("Airdrop to realm master", async () => await helper.airdrop(realmMaster.publicKey, 1 * anchor.web3.LAMPORTS_PER_SOL));
+  it("Create the realm", async () => await steps.createRealm(realmMaster, program, realmId, realmDescription, events));
+  it("Add the village dimension", async () => steps.addRealmDimension(realmMaster, program, realmId, village, events));
+  it("Add the cave dimension", async () => steps.addRealmDimension(realmMaster, program, realmId, cave, events));
+
+  it("Try to add a portal reaching outside of the village", async () => {
+    try {
+      await program.methods
+        .addRealmPortal(realmId, village.id, portalId, { topLeft: { x: 49, y: 49 }, bottomRight: { x: 51, y: 51 } }, portalTarget)
+        .accounts({
+          master: realmMaster.publicKey,
+          targetDimension: helper.getDimensionPDA(realmId, cave.id, program),
+        })
+        .signers([realmMaster])
+        .rpc();
+      expect.fail("Portal should lie within its dimension");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("PortalOutsideDimension");
+    }
+  });
+
+  it("Add a portal from the village to the cave", async () => {
+    const tx = await program.methods
+      .addRealmPortal(realmId, village.id, portalId, portalArea, portalTarget)
+      .accounts({
+        master: realmMaster.publicKey,
+        targetDimension: helper.getDimensionPDA(realmId, cave.id, program),
+      })
+      .signers([realmMaster])
+      .rpc();
+    await helper.confirmTransaction(tx);
+
+    const portalPDA = helper.getPortalPDA(realmId, village.id, portalId, program);
+    const portalAccount = await program.account.realmPortal.fetch(portalPDA);
+    expect(portalAccount.dimension.toBase58()).to.equal(helper.getDimensionPDA(realmId, village.id, program).toBase58());
+    expect(portalAccount.targetDimension.toBase58()).to.equal(helper.getDimensionPDA(realmId, cave.id, program).toBase58());
+    expect(portalAccount.area).to.deep.equal(portalArea);
+    expect(portalAccount.targetPosition).to.deep.equal(portalTarget);
+    expect(portalAccount.creator.toBase58()).to.equal(realmMaster.publicKey.toBase58());
+
+    const caveAccount = await program.account.realmDimension.fetch(helper.getDimensionPDA(realmId, cave.id, program));
+    expect(caveAccount.incomingPortalsCount).to.equal(1);
+
+    let event = events[events.length - 1];
+    expect(event.eventType.portalAdded.portalPubkey.toBase58()).to.equal(portalPDA.toBase58());
+  });
+
+  it("Airdrop to player", async () => await helper.airdrop(player.publicKey, 1 * anchor.web3.LAMPORTS_PER_SOL));
+
+  it("Player starts a journey in the village", async () => {
+    const tx = await program.methods
+      .startJourney(realmId, [], null)
+      .accounts({ player: player.publicKey, spawnPoint: null, playerHero: null, hero: null })
+      .signers([player])
+      .rpc();
+    await helper.confirmTransaction(tx);
+  });
+
+  it("Try to travel while standing outside of the portal", async () => {
+    try {
+      await program.methods
+        .travelThroughPortal(realmId)
+        .accounts({
+          player: player.publicKey,
+          authority: player.publicKey,
+          sessionKey: null,
+          portal: helper.getPortalPDA(realmId, village.id, portalId, program),
+          targetGrid: null,
+        })
+        .signers([player])
+        .rpc();
+      expect.fail("Hero should not be able to use a portal from a distance");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("HeroNotInPortal");
+    }
+  });
+
+  it("Walk to the portal", async () => {
+    for (const position of [{ x: 10, y: 10 }, { x: 21, y: 21 }]) {
+      const tx = await program.methods
+        .moveHero(realmId, position)
+        .accounts({ player: player.publicKey, authority: player.publicKey, sessionKey: null, grid: null })
+        .signers([player])
+        .rpc();
+      await helper.confirmTransaction(tx);
+    }
+  });
+
+  const updateCaveEntrance = async (topLeft: { x: number, y: number }) => {
+    const entrance = makeArea("Entrance");
+    entrance.area.topLeft = topLeft;
+    const tx = await program.methods
+      .updateDimensionArea(realmId, cave.id, entrance)
+      .accounts({ master: realmMaster.publicKey })
+      .signers([realmMaster])
+      .rpc();
+    await helper.confirmTransaction(tx);
+  };
+
+  it("Shrink the cave away from the portal target", async () => await updateCaveEntrance({ x: 10, y: 10 }));
+
+  it("Try to travel to a position the cave no longer covers", async () => {
+    try {
+      await program.methods
+        .travelThroughPortal(realmId)
+        .accounts({
+          player: player.publicKey,
+          authority: player.publicKey,
+          sessionKey: null,
+          portal: helper.getPortalPDA(realmId, village.id, portalId, program),
+          targetGrid: null,
+        })
+        .signers([player])
+        .rpc();
+      expect.fail("Hero should not land outside of the target dimension");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("PositionOutsideDimension");
+    }
+  });
+
+  it("Restore the cave entrance", async () => await updateCaveEntrance({ x: 0, y: 0 }));
+
+  it("Travel through the portal to the cave", async () => {
+    const portalPDA = helper.getPortalPDA(realmId, village.id, portalId, program);
+    const tx = await program.methods
+      .travelThroughPortal(realmId)
+      .accounts({
+        player: player.publicKey,
+        authority: player.publicKey,
+        sessionKey: null,
+        portal: portalPDA,
+        targetGrid: null,
+      })
+      .signers([player])
+      .rpc();
+    await helper.confirmTransaction(tx);
+
+    const journeyPDA = helper.getJourneyPDA(realmId, player.publicKey, program);
+    const journeyAccount = await program.account.journey.fetch(journeyPDA);
+    expect(journeyAccount.dimension.toBase58()).to.equal(helper.getDimensionPDA(realmId, cave.id, program).toBase58());
+    expect(journeyAccount.position).to.deep.equal(portalTarget);
+
+    let event = events[events.length - 1];
+    expect(event.eventType.heroTraveled.portalPubkey.toBase58()).to.equal(portalPDA.toBase58());
+    expect(event.eventType.heroTraveled.toDimension.toBase58()).to.equal(journeyAccount.dimension.toBase58());
+  });
+
+  it("Remove the portal", async () => {
+    const tx = await program.methods
+      .removeRealmPortal(realmId, village.id, portalId)
+      .accounts({ master: realmMaster.publicKey, creator: realmMaster.publicKey })
+      .signers([realmMaster])
+      .rpc();
+    await helper.confirmTransaction(tx);
+
+    const portalPDA = helper.getPortalPDA(realmId, village.id, portalId, program);
+    let portalInfo = await anchor.getProvider().connection.getAccountInfo(portalPDA);
+    expect(portalInfo).to.be.null;
+
+    const caveAccount = await program.account.realmDimension.fetch(helper.getDimensionPDA(realmId, cave.id, program));
+    expect(caveAccount.incomingPortalsCount).to.equal(0);
+
+    let event = events[events.length - 1];
+    expect(event.eventType.portalRemoved.portalPubkey.toBase58()).to.equal(portalPDA.toBase58());
+  });
+
+  it("Add the portal back", async () => {
+    const tx = await program.methods
+      .addRealmPortal(realmId, village.id, portalId, portalArea, portalTarget)
+      .accounts({
+        master: realmMaster.publicKey,
+        targetDimension: helper.getDimensionPDA(realmId, cave.id, program),
+      })
+      .signers([realmMaster])
+      .rpc();
+    await helper.confirmTransaction(tx);
+  });
+
+  it("Add the cellar dimension", async () => steps.addRealmDimension(realmMaster, program, realmId, cellar, events));
+
+  it("Add a portal from the village to the cellar", async () => {
+    const tx = await program.methods
+      .addRealmPortal(realmId, village.id, "village_to_cellar", portalArea, portalTarget)
+      .accounts({
+        master: realmMaster.publicKey,
+        targetDimension: helper.getDimensionPDA(realmId, cellar.id, program),
+      })
+      .signers([realmMaster])
+      .rpc();
+    await helper.confirmTransaction(tx);
+  });
+
+  it("Try to remove the cellar while a portal still leads into it", async () => {
+    try {
+      await program.methods
+        .removeRealmDimension(realmId, cellar.id)
+        .accounts({ master: realmMaster.publicKey, owner: realmMaster.publicKey, queuedAction: null })
+        .signers([realmMaster])
+        .rpc();
+      expect.fail("Dimension should not be removed while leaving portals into it behind");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("PortalNotProvided");
+    }
+  });
+
+  it("Remove the cellar along with the portal leading into it", async () => {
+    const portalPDA = helper.getPortalPDA(realmId, village.id, "village_to_cellar", program);
+    const villagePDA = helper.getDimensionPDA(realmId, village.id, program);
+    await steps.removeRealmDimension(realmMaster, program, realmId, cellar.id, events, [portalPDA, villagePDA]);
+
+    let portalInfo = await anchor.getProvider().connection.getAccountInfo(portalPDA);
+    expect(portalInfo).to.be.null;
+    const villageAccount = await program.account.realmDimension.fetch(villagePDA);
+    expect(villageAccount.portalsCount).to.equal(1);
+    expect(events.some((event) => event.eventType.portalRemoved?.portalPubkey.equals(portalPDA))).to.be.true;
+  });
+
+  const dimensionAccounts = () => [village, cave].map((dimension) => ({
+    pubkey: helper.getDimensionPDA(realmId, dimension.id, program),
+    isSigner: false,
+    isWritable: true,
+  }));
+
+  it("Try to delete the realm without its portal", async () => {
+    try {
+      await program.methods
+        .deleteRealm(realmId)
+        .accounts({ master: realmMaster.publicKey, proposal: null, proposer: null, queuedAction: null })
+        .remainingAccounts(dimensionAccounts())
+        .signers([realmMaster])
+        .rpc();
+      expect.fail("Realm should not be deleted while leaving its portals behind");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("PortalNotProvided");
+    }
+  });
+
+  it("Delete the realm along with its portal", async () => {
+    const portalPDA = helper.getPortalPDA(realmId, village.id, portalId, program);
+    const tx = await program.methods
+      .deleteRealm(realmId)
+      .accounts({ master: realmMaster.publicKey, proposal: null, proposer: null, queuedAction: null })
+      .remainingAccounts([...dimensionAccounts(), { pubkey: portalPDA, isSigner: false, isWritable: true }])
+      .signers([realmMaster])
+      .rpc();
+    await helper.confirmTransaction(tx);
+
+    let portalInfo = await anchor.getProvider().connection.getAccountInfo(portalPDA);
+    expect(portalInfo).to.be.null;
+    expect(events.some((event) => event.eventType.portalRemoved?.portalPubkey.equals(portalPDA))).to.be.true;
+  });
+});
\ No newline at end of file
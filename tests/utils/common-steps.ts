@@ -24,6 +24,12 @@ export async function createRealm(
   expect(realmAccount.description).to.deep.equal(description);
   expect(realmAccount.createdAt).to.be.not.null
   expect(realmAccount.updatedAt.eq(realmAccount.createdAt)).to.be.true;
+  expect(realmAccount.owner.toBase58()).to.equal(realmMaster.publicKey.toBase58());
+  expect(realmAccount.mastersCount).to.equal(1);
+  expect(realmAccount.status).to.deep.equal({ active: {} });
+
+  const membership = await program.account.realmMembership.fetch(helper.getMasterPDA(realmId, realmMaster.publicKey, program));
+  expect(membership.master.role).to.deep.equal({ owner: {} });
 
   expect(events.length).to.be.above(0);
   let event = events[events.length - 1];
@@ -62,16 +68,20 @@ export async function deleteRealm(
   program: anchor.Program<RealmVoyagers>,
   realmId: string,
   dimensions: string[],
-  events: any[]
+  events: any[],
+  otherMasters: anchor.web3.PublicKey[] = []
 ) {
   let remainingAccounts = [];
   for (let dimensionId of dimensions) {
     remainingAccounts.push({ pubkey: helper.getDimensionPDA(realmId, dimensionId, program), isSigner: false, isWritable: true });
   }
+  for (let master of otherMasters) {
+    remainingAccounts.push({ pubkey: helper.getMasterPDA(realmId, master, program), isSigner: false, isWritable: true });
+  }
 
   let tx = await program.methods
     .deleteRealm(realmId)
-    .accounts({ master: realmMaster.publicKey })
+    .accounts({ master: realmMaster.publicKey, proposal: null, proposer: null, queuedAction: null })
     .remainingAccounts(remainingAccounts)
     .signers([realmMaster])
     .rpc();
@@ -81,6 +91,11 @@ export async function deleteRealm(
   let realmInfo = await anchor.getProvider().connection.getAccountInfo(realmPDA);
   expect(realmInfo).to.be.null;
 
+  for (let master of [realmMaster.publicKey, ...otherMasters]) {
+    let membershipInfo = await anchor.getProvider().connection.getAccountInfo(helper.getMasterPDA(realmId, master, program));
+    expect(membershipInfo).to.be.null;
+  }
+
   expect(events.length).to.be.above(0);
   let event = events[events.length - 1];
   expect(event.eventType.deleted).not.to.be.null;
@@ -92,28 +107,61 @@ export async function addRealmMaster(
   program: anchor.Program<RealmVoyagers>,
   realmId: string,
   newMaster: anchor.web3.PublicKey,
+  role: any,
   events: any[]
 ) {
   let tx = await program.methods
-    .addRealmMaster(realmId, newMaster)
-    .accounts({ master: realmMaster.publicKey })
+    .addRealmMaster(realmId, newMaster, role)
+    .accounts({ master: realmMaster.publicKey, proposal: null, proposer: null })
     .signers([realmMaster])
     .rpc();
   await helper.confirmTransaction(tx);
 
   const realmPDA = helper.getRealmPDA(realmId, program);
   const realmAccount = await program.account.realm.fetch(realmPDA);
-  expect(realmAccount.masters.length).to.be.above(1);
-  expect(realmAccount.masters[0].pubkey.toBase58()).to.equal(realmMaster.publicKey.toBase58());
-  expect(realmAccount.masters[0].role).to.deep.equal({ owner: {} });
-  expect(realmAccount.masters[realmAccount.masters.length - 1].pubkey.toBase58()).to.equal(newMaster.toBase58());
-  expect(realmAccount.masters[realmAccount.masters.length - 1].role).to.deep.equal({ admin: {} });
+  expect(realmAccount.mastersCount).to.be.above(1);
 
-  expect(events.length).to.be.above(0);
-  let event = events[events.length - 1];
+  const membership = await program.account.realmMembership.fetch(helper.getMasterPDA(realmId, newMaster, program));
+  expect(membership.realm.toBase58()).to.equal(realmPDA.toBase58());
+  expect(membership.master.pubkey.toBase58()).to.equal(newMaster.toBase58());
+  expect(membership.master.role).to.deep.equal(role);
+
+  expect(events.length).to.be.above(1);
+  let event = events[events.length - 2];
   expect(event.eventType.realmMasterAdded).not.to.be.null;
   expect(event.eventType.realmMasterAdded.master.pubkey.toBase58()).to.equal(newMaster.toBase58());
-  expect(event.eventType.realmMasterAdded.master.role).to.deep.equal({ admin: {} });
+  expect(event.eventType.realmMasterAdded.master.role).to.deep.equal(role);
+  expect(event.realmPubkey.toBase58()).to.equal(realmPDA.toBase58());
+
+  event = events[events.length - 1];
+  expect(event.eventType.realmMasterRoleChanged.master.pubkey.toBase58()).to.equal(newMaster.toBase58());
+  expect(event.eventType.realmMasterRoleChanged.master.role).to.deep.equal(role);
+  expect(event.eventType.realmMasterRoleChanged.previousRole).to.be.null;
+}
+
+export async function setRealmMasterRole(
+  realmMaster: anchor.web3.Keypair,
+  program: anchor.Program<RealmVoyagers>,
+  realmId: string,
+  master: anchor.web3.PublicKey,
+  role: any,
+  events: any[]
+) {
+  let tx = await program.methods
+    .setRealmMasterRole(realmId, master, role)
+    .accounts({ master: realmMaster.publicKey, proposal: null, proposer: null })
+    .signers([realmMaster])
+    .rpc();
+  await helper.confirmTransaction(tx);
+
+  const realmPDA = helper.getRealmPDA(realmId, program);
+  const membership = await program.account.realmMembership.fetch(helper.getMasterPDA(realmId, master, program));
+  expect(membership.master.role).to.deep.equal(role);
+
+  expect(events.length).to.be.above(0);
+  let event = events[events.length - 1];
+  expect(event.eventType.realmMasterRoleChanged.master.pubkey.toBase58()).to.equal(master.toBase58());
+  expect(event.eventType.realmMasterRoleChanged.master.role).to.deep.equal(role);
   expect(event.realmPubkey.toBase58()).to.equal(realmPDA.toBase58());
 }
 
@@ -126,15 +174,14 @@ export async function removeRealmMaster(
 ) {
   let tx = await program.methods
     .removeRealmMaster(realmId, masterToRemove)
-    .accounts({ master: realmMaster.publicKey })
+    .accounts({ master: realmMaster.publicKey, proposal: null, proposer: null })
     .signers([realmMaster])
     .rpc();
   await helper.confirmTransaction(tx);
 
   const realmPDA = helper.getRealmPDA(realmId, program);
-  const realmAccount = await program.account.realm.fetch(realmPDA);
-  expect(realmAccount.masters.length).to.be.above(0);
-  expect(realmAccount.masters.filter((master) => master.pubkey.toBase58() === masterToRemove.toBase58()).length).to.equal(0);
+  const membershipInfo = await anchor.getProvider().connection.getAccountInfo(helper.getMasterPDA(realmId, masterToRemove, program));
+  expect(membershipInfo).to.be.null;
 
   expect(events.length).to.be.above(0);
   let event = events[events.length - 1];
@@ -143,7 +190,7 @@ export async function removeRealmMaster(
   expect(event.realmPubkey.toBase58()).to.equal(realmPDA.toBase58());
 }
 
-export async function transferRealmOwnership(
+export async function proposeRealmOwner(
   realmMaster: anchor.web3.Keypair,
   program: anchor.Program<RealmVoyagers>,
   realmId: string,
@@ -151,33 +198,55 @@ export async function transferRealmOwnership(
   events: any[]
 ) {
   let tx = await program.methods
-    .transferRealmOwnership(realmId, newOwner)
-    .accounts({ master: realmMaster.publicKey })
+    .proposeRealmOwner(realmId, newOwner)
+    .accounts({ master: realmMaster.publicKey, proposal: null, proposer: null, queuedAction: null })
     .signers([realmMaster])
     .rpc();
   await helper.confirmTransaction(tx);
 
   const realmPDA = helper.getRealmPDA(realmId, program);
   const realmAccount = await program.account.realm.fetch(realmPDA);
-  expect(realmAccount.masters.length).to.be.above(0);
-  expect(realmAccount.masters[0].pubkey.toBase58()).to.equal(realmMaster.publicKey.toBase58());
-  expect(realmAccount.masters[0].role).to.deep.equal({ admin: {} });
-
-  let newOwnerFound = false;
-  for (let master of realmAccount.masters) {
-    if (master.pubkey.toBase58() === newOwner.toBase58()) {
-      expect(master.role).to.deep.equal({ owner: {} });
-      newOwnerFound = true;
-    }
-  }
-  expect(newOwnerFound).to.be.true;
+  expect(realmAccount.pendingOwner.toBase58()).to.equal(newOwner.toBase58());
+  expect(realmAccount.owner.toBase58()).to.equal(realmMaster.publicKey.toBase58());
+
+  expect(events.length).to.be.above(0);
+  let event = events[events.length - 1];
+  expect(event.eventType.realmOwnershipProposed.owner.pubkey.toBase58()).to.equal(realmMaster.publicKey.toBase58());
+  expect(event.eventType.realmOwnershipProposed.pendingOwner.toBase58()).to.equal(newOwner.toBase58());
+  expect(event.realmPubkey.toBase58()).to.equal(realmPDA.toBase58());
+}
+
+export async function acceptRealmOwnership(
+  newOwner: anchor.web3.Keypair,
+  program: anchor.Program<RealmVoyagers>,
+  realmId: string,
+  oldOwner: anchor.web3.PublicKey,
+  events: any[]
+) {
+  let tx = await program.methods
+    .acceptRealmOwnership(realmId)
+    .accounts({ newOwner: newOwner.publicKey })
+    .signers([newOwner])
+    .rpc();
+  await helper.confirmTransaction(tx);
+
+  const realmPDA = helper.getRealmPDA(realmId, program);
+  const realmAccount = await program.account.realm.fetch(realmPDA);
+  expect(realmAccount.pendingOwner).to.be.null;
+  expect(realmAccount.owner.toBase58()).to.equal(newOwner.publicKey.toBase58());
+
+  const oldOwnerMembership = await program.account.realmMembership.fetch(helper.getMasterPDA(realmId, oldOwner, program));
+  expect(oldOwnerMembership.master.role).to.deep.equal({ admin: {} });
+
+  const newOwnerMembership = await program.account.realmMembership.fetch(helper.getMasterPDA(realmId, newOwner.publicKey, program));
+  expect(newOwnerMembership.master.role).to.deep.equal({ owner: {} });
 
   expect(events.length).to.be.above(0);
   let event = events[events.length - 1];
   expect(event.eventType.realmOwnershipTransferred).not.to.be.null;
-  expect(event.eventType.realmOwnershipTransferred.newOwner.pubkey.toBase58()).to.equal(newOwner.toBase58());
+  expect(event.eventType.realmOwnershipTransferred.newOwner.pubkey.toBase58()).to.equal(newOwner.publicKey.toBase58());
   expect(event.eventType.realmOwnershipTransferred.newOwner.role).to.deep.equal({ owner: {} });
-  expect(event.eventType.realmOwnershipTransferred.oldOwner.pubkey.toBase58()).to.equal(realmMaster.publicKey.toBase58());
+  expect(event.eventType.realmOwnershipTransferred.oldOwner.pubkey.toBase58()).to.equal(oldOwner.toBase58());
   expect(event.eventType.realmOwnershipTransferred.oldOwner.role).to.deep.equal({ admin: {} });
   expect(event.realmPubkey.toBase58()).to.equal(realmPDA.toBase58());
 }
@@ -193,7 +262,7 @@ export async function addRealmDimension(
   const realmPDA = helper.getRealmPDA(realmId, program);
 
   const tx = await program.methods
-    .addRealmDimension(realmId, dimension.id, dimension.name, dimension.areas)
+    .addRealmDimension(realmId, dimension.id, dimension.name, dimension.areas, dimension.allowOverlappingAreas ?? false)
     .accounts({
       master: realmMaster.publicKey,
     })
@@ -205,6 +274,7 @@ export async function addRealmDimension(
   expect(dimensionAccount.name).to.equal(dimension.name);
   expect(dimensionAccount.realm.toBase58()).to.equal(realmPDA.toBase58());
   expect(dimensionAccount.areas).to.deep.equal(dimension.areas);
+  expect(dimensionAccount.allowOverlappingAreas).to.equal(dimension.allowOverlappingAreas ?? false);
 
   expect(events.length).to.be.above(0);
   let event = events[events.length - 1];
@@ -224,7 +294,7 @@ export async function updateRealmDimension(
   const realmPDA = helper.getRealmPDA(realmId, program);
 
   const tx = await program.methods
-    .updateRealmDimension(realmId, dimension.id, dimension.name, dimension.areas)
+    .updateRealmDimension(realmId, dimension.id, dimension.name, dimension.areas, dimension.allowOverlappingAreas ?? false)
     .accounts({
       master: realmMaster.publicKey,
     })
@@ -236,6 +306,7 @@ export async function updateRealmDimension(
   expect(dimensionAccount.name).to.equal(dimension.name);
   expect(dimensionAccount.realm.toBase58()).to.equal(realmPDA.toBase58());
   expect(dimensionAccount.areas).to.deep.equal(dimension.areas);
+  expect(dimensionAccount.allowOverlappingAreas).to.equal(dimension.allowOverlappingAreas ?? false);
 
   expect(events.length).to.be.above(0);
   let event = events[events.length - 1];
@@ -249,7 +320,9 @@ export async function removeRealmDimension(
   program: anchor.Program<RealmVoyagers>,
   realmId: string,
   dimensionId: string,
-  events: any[]
+  events: any[],
+  dimensionAccounts: anchor.web3.PublicKey[] = [],
+  owner: anchor.web3.PublicKey = realmMaster.publicKey
 ) {
   const dimensionPDA = helper.getDimensionPDA(realmId, dimensionId, program);
   const realmPDA = helper.getRealmPDA(realmId, program);
@@ -258,7 +331,10 @@ export async function removeRealmDimension(
     .removeRealmDimension(realmId, dimensionId)
     .accounts({
       master: realmMaster.publicKey,
+      owner,
+      queuedAction: null,
     })
+    .remainingAccounts(dimensionAccounts.map((pubkey) => ({ pubkey, isSigner: false, isWritable: true })))
     .signers([realmMaster])
     .rpc();
   await helper.confirmTransaction(tx);
@@ -278,12 +354,13 @@ export async function createHero(
   program: anchor.Program<RealmVoyagers>,
   heroId: string,
   description: any,
+  tags: any[],
   events: any[]
 ) {
   const heroPDA = helper.getHeroPDA(master.publicKey, heroId, program);
 
   let tx = await program.methods
-    .createHero(heroId, description)
+    .createHero(heroId, description, tags)
     .accounts({ master: master.publicKey })
     .signers([master])
     .rpc();
@@ -291,6 +368,10 @@ export async function createHero(
 
   const heroAccount = await program.account.hero.fetch(heroPDA);
   expect(heroAccount.description).to.deep.equal(description);
+  expect(heroAccount.tags).to.deep.equal(tags);
+  if (tags.length > 0) {
+    expect(heroAccount.baseStats.strength.toNumber()).to.be.above(0);
+  }
   expect(heroAccount.createdAt).to.be.not.null
   expect(heroAccount.updatedAt.eq(heroAccount.createdAt)).to.be.true;
 
@@ -1,4 +1,5 @@
 import * as anchor from "@coral-xyz/anchor";
+import { createHash } from "crypto";
 
 export function getRealmPDA(realmId: string, program: anchor.Program<any>): anchor.web3.PublicKey{
   const [pda, _] =  anchor.web3.PublicKey.findProgramAddressSync(
@@ -8,6 +9,14 @@ export function getRealmPDA(realmId: string, program: anchor.Program<any>): anch
   return pda;
 }
 
+export function getMasterPDA(realmId: string, master: anchor.web3.PublicKey, program: anchor.Program<any>) {
+  const [pda, _] =  anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("master"), getRealmPDA(realmId, program).toBuffer(), master.toBuffer()],
+    program.programId
+  );
+  return pda;
+}
+
 export function getDimensionPDA(realmId: string, dimensionId: string, program: anchor.Program<any>) {
   const [pda, _] =  anchor.web3.PublicKey.findProgramAddressSync(
     [Buffer.from("dimension"), Buffer.from(realmId), Buffer.from(dimensionId)],
@@ -16,6 +25,30 @@ export function getDimensionPDA(realmId: string, dimensionId: string, program: a
   return pda;
 }
 
+export function getPortalPDA(realmId: string, dimensionId: string, portalId: string, program: anchor.Program<any>) {
+  const [pda, _] =  anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("portal"), Buffer.from(realmId), Buffer.from(dimensionId), Buffer.from(portalId)],
+    program.programId
+  );
+  return pda;
+}
+
+export function getProposalPDA(realmId: string, proposalId: string, program: anchor.Program<any>) {
+  const [pda, _] =  anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("proposal"), Buffer.from(realmId), Buffer.from(proposalId)],
+    program.programId
+  );
+  return pda;
+}
+
+export function getQueuedActionPDA(realmId: string, actionId: string, program: anchor.Program<any>) {
+  const [pda, _] =  anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("queued_action"), Buffer.from(realmId), Buffer.from(actionId)],
+    program.programId
+  );
+  return pda;
+}
+
 export function getJourneyPDA(realmId: string, player: anchor.web3.PublicKey, program: anchor.Program<any>) {
   const [pda, _] =  anchor.web3.PublicKey.findProgramAddressSync(
     [Buffer.from("journey"), Buffer.from(realmId), player.toBuffer()],
@@ -24,6 +57,14 @@ export function getJourneyPDA(realmId: string, player: anchor.web3.PublicKey, pr
   return pda;
 }
 
+export function getSessionKeyPDA(journey: anchor.web3.PublicKey, authority: anchor.web3.PublicKey, program: anchor.Program<any>) {
+  const [pda, _] =  anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("session_key"), journey.toBuffer(), authority.toBuffer()],
+    program.programId
+  );
+  return pda;
+}
+
 export function getHeroPDA(master: anchor.web3.PublicKey, heroId: string, program: anchor.Program<any>) {
   const [pda, _] =  anchor.web3.PublicKey.findProgramAddressSync(
     [Buffer.from("hero"), master.toBuffer(), Buffer.from(heroId)],
@@ -32,7 +73,61 @@ export function getHeroPDA(master: anchor.web3.PublicKey, heroId: string, progra
   return pda;
 }
 
-//seeds = [HERO_SEED, player.key().as_ref(), hero_id.as_bytes()],
+export function getPlayerHeroPDA(player: anchor.web3.PublicKey, playerHeroId: string, program: anchor.Program<any>) {
+  const [pda, _] =  anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("player_hero"), player.toBuffer(), Buffer.from(playerHeroId)],
+    program.programId
+  );
+  return pda;
+}
+
+export function getItemPDA(master: anchor.web3.PublicKey, itemId: string, program: anchor.Program<any>) {
+  const [pda, _] =  anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("item"), master.toBuffer(), Buffer.from(itemId)],
+    program.programId
+  );
+  return pda;
+}
+
+function hashAllowlistNode(prefix: number, ...values: Buffer[]): Buffer {
+  return createHash("sha256").update(Buffer.from([prefix])).update(Buffer.concat(values)).digest();
+}
+
+// Levels of the allowlist Merkle tree, from the leaves up to the root. Pairs are hashed in sorted order
+function getAllowlistLevels(players: anchor.web3.PublicKey[]): Buffer[][] {
+  let levels = [players.map((player) => hashAllowlistNode(0, player.toBuffer()))];
+  while (levels[levels.length - 1].length > 1) {
+    const level = levels[levels.length - 1];
+    const nextLevel = [];
+    for (let i = 0; i < level.length; i += 2) {
+      if (i + 1 == level.length) {
+        nextLevel.push(level[i]);
+      } else {
+        nextLevel.push(hashAllowlistNode(1, ...[level[i], level[i + 1]].sort(Buffer.compare)));
+      }
+    }
+    levels.push(nextLevel);
+  }
+  return levels;
+}
+
+export function getAllowlistRoot(players: anchor.web3.PublicKey[]): number[] {
+  const levels = getAllowlistLevels(players);
+  return Array.from(levels[levels.length - 1][0]);
+}
+
+export function getAllowlistProof(players: anchor.web3.PublicKey[], player: anchor.web3.PublicKey): number[][] {
+  let index = players.findIndex((invited) => invited.equals(player));
+  let proof = [];
+  for (const level of getAllowlistLevels(players).slice(0, -1)) {
+    const sibling = index % 2 == 0 ? index + 1 : index - 1;
+    if (sibling < level.length) {
+      proof.push(Array.from(level[sibling]));
+    }
+    index = Math.floor(index / 2);
+  }
+  return proof;
+}
 
 export async function airdrop(publicKey: anchor.web3.PublicKey, lamports: number) {
   let airdropTx = await anchor.getProvider().connection.requestAirdrop(publicKey, lamports);
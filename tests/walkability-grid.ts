@@ -0,0 +1,270 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { expect } from "chai";
+
+import { RealmVoyagers } from "../target/types/realm_voyagers";
+
+import * as helper from "./utils/helpers";
+import * as steps from "./utils/common-steps";
+
+describe("Walkability grid", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  // Program & keypairs
+  const program = anchor.workspace.RealmVoyagers as Program<RealmVoyagers>;
+  const realmMaster = anchor.web3.Keypair.generate();
+  const player = anchor.web3.Keypair.generate();
+
+  // Realm data
+  const realmId = "walled_realm";
+  const realmDescription = { name: "Walled Realm", details: "A realm with walls", logo: "https://example.com/logo123" };
+  const dimension = {
+    id: "courtyard",
+    name: "Courtyard",
+    areas: [{
+      "id": 1,
+      "name": "Courtyard",
+      "area": { "topLeft": { "x": 0, "y": 0 }, "bottomRight": { "x": 99, "y": 99 } },
+      "tileset": "https://example.com/tileset.png",
+      "tilemap": "https://example.com/tilemap.json"
+    }]
+  };
+  const startingPosition = { x: 20, y: 10 };
+
+  // Grid data: a wall along x = 30 with a door at y = 50
+  const gridOrigin = { x: 0, y: 0 };
+  const gridWidth = 100;
+  const gridHeight = 100;
+  const chunkSize = 400;
+  const bitmap = Buffer.alloc(Math.ceil(gridWidth * gridHeight / 8));
+  for (let y = 0; y < gridHeight; y++) {
+    if (y != 50) {
+      const index = y * gridWidth + 30;
+      bitmap[Math.floor(index / 8)] |= 1 << (index % 8);
+    }
+  }
+
+  // Listen events
+  let listeners = [];
+  let events = [];
+
+  before(async () => {
+    listeners.push(program.addEventListener("realmDimensionEvent", (event) => {
+      events.push(event);
+    }));
+    listeners.push(program.addEventListener("journeyEvent", (event) => {
+      events.push(event);
+    }));
+  });
+
+  after(async () => {
+    for (const listener of listeners) {
+      await program.removeEventListener(listener);
+    }
+  });
+
+  function getGridPDA() {
+    const [pda, _] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("walkability_grid"), helper.getDimensionPDA(realmId, dimension.id, program).toBuffer()],
+      program.programId
+    );
+    return pda;
+  }
+
+  // Mirrors the lookup of the program
+  function isWalkable(grid: any, position: any) {
+    const x = position.x - grid.origin.x;
+    const y = position.y - grid.origin.y;
+    if (x < 0 || y < 0 || x >= grid.width || y >= grid.height) {
+      return true;
+    }
+    const index = y * grid.width + x;
+    return (grid.blocked[Math.floor(index / 8)] & (1 << (index % 8))) == 0;
+  }
+
+  async function moveHero(position: any, withGrid = true) {
+    const tx = await program.methods
+      .moveHero(realmId, position)
+      .accounts({ player: player.publicKey, authority: player.publicKey, sessionKey: null, grid: withGrid ? getGridPDA() : null })
+      .signers([player])
+      .rpc();
+    await helper.confirmTransaction(tx);
+
+    const journeyAccount = await program.account.journey.fetch(helper.getJourneyPDA(realmId, player.publicKey, program));
+    expect(journeyAccount.position).to.deep.equal(position);
+  }
+
+  async function expectMoveFails(position: any, errorCode: string, withGrid = true) {
+    try {
+      await moveHero(position, withGrid);
+      expect.fail("Hero should not move to " + JSON.stringify(position));
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal(errorCode);
+    }
+  }
+
+  it("Airdrop to realm master", async () => await helper.airdrop(realmMaster.publicKey, 1 * anchor.web3.LAMPORTS_PER_SOL));
+  it("Airdrop to player", async () => await helper.airdrop(player.publicKey, 1 * anchor.web3.LAMPORTS_PER_SOL));
+  it("Create the realm", async () => await steps.createRealm(realmMaster, program, realmId, realmDescription, events));
+  it("Add the dimension", async () => await steps.addRealmDimension(realmMaster, program, realmId, dimension, events));
+
+  it("Player tries to create the grid, and it fails", async () => {
+    try {
+      await program.methods
+        .createWalkabilityGrid(realmId, dimension.id, gridOrigin, gridWidth, gridHeight)
+        .accounts({ master: player.publicKey })
+        .signers([player])
+        .rpc();
+      expect.fail("Only realm masters should create grids");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("AccountNotInitialized");
+    }
+  });
+
+  it("Try to create an empty grid", async () => {
+    try {
+      await program.methods
+        .createWalkabilityGrid(realmId, dimension.id, gridOrigin, 0, gridHeight)
+        .accounts({ master: realmMaster.publicKey })
+        .signers([realmMaster])
+        .rpc();
+      expect.fail("Empty grid should be rejected");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("InvalidWalkabilityGridSize");
+    }
+  });
+
+  it("Create the grid", async () => {
+    const tx = await program.methods
+      .createWalkabilityGrid(realmId, dimension.id, gridOrigin, gridWidth, gridHeight)
+      .accounts({ master: realmMaster.publicKey })
+      .signers([realmMaster])
+      .rpc();
+    await helper.confirmTransaction(tx);
+
+    const gridAccount = await program.account.walkabilityGrid.fetch(getGridPDA());
+    expect(gridAccount.width).to.equal(gridWidth);
+    expect(gridAccount.height).to.equal(gridHeight);
+    expect(Buffer.from(gridAccount.blocked).equals(Buffer.alloc(bitmap.length))).to.be.true;
+
+    const dimensionAccount = await program.account.realmDimension.fetch(helper.getDimensionPDA(realmId, dimension.id, program));
+    expect(dimensionAccount.hasWalkabilityGrid).to.be.true;
+
+    let event = events[events.length - 1];
+    expect(event.eventType.walkabilityGridCreated.gridPubkey.toBase58()).to.equal(getGridPDA().toBase58());
+  });
+
+  it("Upload the grid in chunks", async () => {
+    for (let offset = 0; offset < bitmap.length; offset += chunkSize) {
+      const chunk = bitmap.subarray(offset, offset + chunkSize);
+      const tx = await program.methods
+        .uploadWalkabilityChunk(realmId, dimension.id, offset, chunk)
+        .accounts({ master: realmMaster.publicKey })
+        .signers([realmMaster])
+        .rpc();
+      await helper.confirmTransaction(tx);
+
+      let event = events[events.length - 1];
+      expect(event.eventType.walkabilityChunkUploaded.offset).to.equal(offset);
+      expect(event.eventType.walkabilityChunkUploaded.len).to.equal(chunk.length);
+    }
+
+    const gridAccount = await program.account.walkabilityGrid.fetch(getGridPDA());
+    expect(Buffer.from(gridAccount.blocked).equals(bitmap)).to.be.true;
+  });
+
+  it("Try to upload a chunk past the end of the grid", async () => {
+    try {
+      await program.methods
+        .uploadWalkabilityChunk(realmId, dimension.id, bitmap.length - 10, Buffer.alloc(20))
+        .accounts({ master: realmMaster.publicKey })
+        .signers([realmMaster])
+        .rpc();
+      expect.fail("Chunk should fit the grid");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("WalkabilityChunkOutOfBounds");
+    }
+  });
+
+  it("Look up cells of the uploaded grid", async () => {
+    const gridAccount = await program.account.walkabilityGrid.fetch(getGridPDA());
+    expect(isWalkable(gridAccount, { x: 30, y: 10 })).to.be.false;
+    expect(isWalkable(gridAccount, { x: 30, y: 50 })).to.be.true;
+    expect(isWalkable(gridAccount, { x: 29, y: 10 })).to.be.true;
+    expect(isWalkable(gridAccount, { x: 31, y: 99 })).to.be.true;
+    expect(isWalkable(gridAccount, { x: 30, y: 99 })).to.be.false;
+    expect(isWalkable(gridAccount, { x: 150, y: 10 })).to.be.true;
+  });
+
+  it("Player starts a journey", async () => {
+    let tx = await program.methods
+      .setRealmStartingPoint(realmId, dimension.id, startingPosition)
+      .accounts({ master: realmMaster.publicKey })
+      .signers([realmMaster])
+      .rpc();
+    await helper.confirmTransaction(tx);
+
+    tx = await program.methods
+      .startJourney(realmId, [], null)
+      .accounts({ player: player.publicKey, spawnPoint: null, playerHero: null, hero: null })
+      .signers([player])
+      .rpc();
+    await helper.confirmTransaction(tx);
+  });
+
+  it("Try to move without the grid", async () => await expectMoveFails({ x: 25, y: 10 }, "WalkabilityGridNotProvided", false));
+  it("Try to move into the wall", async () => await expectMoveFails({ x: 30, y: 10 }, "PositionNotWalkable"));
+  it("Try to move across the wall", async () => await expectMoveFails({ x: 35, y: 10 }, "PositionNotWalkable"));
+
+  it("Walk along the wall to the door", async () => {
+    for (const position of [{ x: 25, y: 28 }, { x: 25, y: 46 }, { x: 25, y: 50 }]) {
+      await moveHero(position);
+    }
+  });
+
+  it("Walk through the door", async () => await moveHero({ x: 35, y: 50 }));
+  it("Try to cut diagonally back through the wall", async () => await expectMoveFails({ x: 25, y: 40 }, "PositionNotWalkable"));
+
+  it("Remove the grid and walk through the wall", async () => {
+    const tx = await program.methods
+      .removeWalkabilityGrid(realmId, dimension.id)
+      .accounts({ master: realmMaster.publicKey, owner: realmMaster.publicKey })
+      .signers([realmMaster])
+      .rpc();
+    await helper.confirmTransaction(tx);
+
+    expect(await anchor.getProvider().connection.getAccountInfo(getGridPDA())).to.be.null;
+    const dimensionAccount = await program.account.realmDimension.fetch(helper.getDimensionPDA(realmId, dimension.id, program));
+    expect(dimensionAccount.hasWalkabilityGrid).to.be.false;
+
+    await moveHero({ x: 25, y: 40 }, false);
+  });
+
+  it("Create the grid again", async () => {
+    const tx = await program.methods
+      .createWalkabilityGrid(realmId, dimension.id, gridOrigin, gridWidth, gridHeight)
+      .accounts({ master: realmMaster.publicKey })
+      .signers([realmMaster])
+      .rpc();
+    await helper.confirmTransaction(tx);
+  });
+
+  it("Try to remove the dimension without its grid", async () => {
+    try {
+      await program.methods
+        .removeRealmDimension(realmId, dimension.id)
+        .accounts({ master: realmMaster.publicKey, owner: realmMaster.publicKey, queuedAction: null })
+        .signers([realmMaster])
+        .rpc();
+      expect.fail("Dimension should not be removed while leaving its grid behind");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("WalkabilityGridNotProvided");
+    }
+  });
+
+  it("Remove the dimension along with its grid", async () => {
+    await steps.removeRealmDimension(realmMaster, program, realmId, dimension.id, events, [getGridPDA()]);
+    expect(await anchor.getProvider().connection.getAccountInfo(getGridPDA())).to.be.null;
+  });
+});
\ No newline at end of file